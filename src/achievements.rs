@@ -0,0 +1,245 @@
+use crate::Game;
+
+// Running totals across every game recorded so far, including the current
+// one, for achievements that span games.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Streak {
+    pub played:  u32,
+    pub won:     u32,
+    pub current: u32,
+    pub best:    u32,
+}
+
+type CheckFn = Box<dyn Fn(&Game, &Streak) -> bool + Send + Sync>;
+
+pub struct Achievement {
+    pub id:          String,
+    pub name:        String,
+    pub description: String,
+    check:           CheckFn,
+}
+
+impl Achievement {
+    pub fn new<F>(id: &str, name: &str, description: &str, check: F) -> Self
+    where
+        F: Fn(&Game, &Streak) -> bool + Send + Sync + 'static,
+    {
+        Self {
+            id:          id.to_owned(),
+            name:        name.to_owned(),
+            description: description.to_owned(),
+            check:       Box::new(check),
+        }
+    }
+}
+
+pub struct Achievements {
+    achievements: Vec<Achievement>,
+    unlocked:     Vec<String>,
+    streak:       Streak,
+}
+
+impl Achievements {
+    pub fn new() -> Self {
+        let mut achievements = Self::empty();
+        achievements.register(Achievement::new(
+            "win-in-3",
+            "Quick Thinker",
+            "Break the code in three guesses or fewer.",
+            |game, _| game.is_solved() && game.guesses().len() <= 3,
+        ));
+        achievements.register(Achievement::new(
+            "no-duplicates",
+            "Purist",
+            "Win without using any color twice in the same guess.",
+            |game, _| {
+                game.is_solved()
+                    && game.guesses().iter().all(|g| is_distinct(g))
+            },
+        ));
+        achievements.register(Achievement::new(
+            "streak-10",
+            "On a Roll",
+            "Win ten games in a row.",
+            |_, streak| streak.current >= 10,
+        ));
+        achievements
+    }
+
+    // A tracker with no achievements, for applications that only want their
+    // own.
+    pub fn empty() -> Self {
+        Self {
+            achievements: Vec::new(),
+            unlocked:     Vec::new(),
+            streak:       Streak::default(),
+        }
+    }
+
+    pub fn register(&mut self, achievement: Achievement) {
+        self.achievements.retain(|a| a.id != achievement.id);
+        self.achievements.push(achievement);
+    }
+
+    pub fn all(&self) -> &[Achievement] { &self.achievements }
+    pub fn streak(&self) -> Streak { self.streak }
+    pub fn is_unlocked(&self, id: &str) -> bool {
+        self.unlocked.iter().any(|unlocked| unlocked == id)
+    }
+
+    // Unlocks achievements by id, e.g. to restore previously saved state.
+    pub fn unlock(&mut self, id: &str) {
+        if !self.is_unlocked(id) {
+            self.unlocked.push(id.to_owned());
+        }
+    }
+
+    // Restores the streak and unlocked achievements from saved state.
+    pub fn restore(&mut self, streak: Streak, unlocked: &[String]) {
+        self.streak = streak;
+        unlocked.iter().for_each(|id| self.unlock(id));
+    }
+
+    pub fn unlocked_ids(&self) -> &[String] { &self.unlocked }
+    pub fn unlocked(&self) -> impl Iterator<Item = &Achievement> {
+        self.achievements.iter().filter(|a| self.is_unlocked(&a.id))
+    }
+
+    // Records a finished game, returning the ids of newly unlocked
+    // achievements. Games still in progress are ignored.
+    pub fn record(&mut self, game: &Game) -> Vec<String> {
+        if !game.is_over() {
+            return Vec::new();
+        }
+
+        self.streak.played += 1;
+        if game.is_solved() {
+            self.streak.won += 1;
+            self.streak.current += 1;
+            self.streak.best = self.streak.best.max(self.streak.current);
+        }
+        else {
+            self.streak.current = 0;
+        }
+
+        let newly_unlocked = self
+            .achievements
+            .iter()
+            .filter(|a| !self.is_unlocked(&a.id))
+            .filter(|a| (a.check)(game, &self.streak))
+            .map(|a| a.id.clone())
+            .collect::<Vec<_>>();
+
+        self.unlocked.extend(newly_unlocked.iter().cloned());
+        newly_unlocked
+    }
+}
+
+impl Default for Achievements {
+    fn default() -> Self { Self::new() }
+}
+
+fn is_distinct(guess: &[u8]) -> bool {
+    guess
+        .iter()
+        .enumerate()
+        .all(|(i, peg)| !guess[i + 1..].contains(peg))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GameBuilder;
+
+    fn play(pegs: &[u8], guesses: &[&[u8]]) -> Game {
+        let mut game = GameBuilder::new().pegs(pegs).build();
+        for guess in guesses {
+            game.guess(guess).unwrap();
+        }
+        game
+    }
+
+    #[test]
+    fn quick_wins_unlock_win_in_3() {
+        let mut achievements = Achievements::new();
+        let game = play(&[1, 2, 3, 3], &[&[0, 0, 0, 0], &[1, 2, 3, 3]]);
+
+        assert_eq!(achievements.record(&game), vec!["win-in-3"]);
+        assert!(achievements.is_unlocked("win-in-3"));
+        assert!(!achievements.is_unlocked("no-duplicates"));
+
+        // Achievements are only reported once.
+        assert!(achievements.record(&game).is_empty());
+    }
+
+    #[test]
+    fn distinct_guesses_unlock_no_duplicates() {
+        let mut achievements = Achievements::new();
+        let game = play(
+            &[1, 2, 3, 4],
+            &[&[0, 1, 2, 5], &[5, 4, 3, 1], &[2, 1, 4, 3], &[1, 2, 3, 4]],
+        );
+        assert_eq!(achievements.record(&game), vec!["no-duplicates"]);
+    }
+
+    #[test]
+    fn streaks_are_tracked() {
+        let mut achievements = Achievements::new();
+        let won = play(
+            &[1, 1, 1, 1],
+            &[&[0, 0, 0, 0], &[2, 2, 2, 2], &[3, 3, 3, 3], &[1, 1, 1, 1]],
+        );
+        let mut lost = play(&[1, 1, 1, 1], &[&[0, 0, 0, 0]]);
+        lost.forfeit();
+
+        for _ in 0..9 {
+            achievements.record(&won);
+        }
+        achievements.record(&lost);
+        assert_eq!(achievements.streak().current, 0);
+        assert_eq!(achievements.streak().best, 9);
+
+        for _ in 0..9 {
+            achievements.record(&won);
+        }
+        assert_eq!(achievements.record(&won), vec!["streak-10"]);
+        assert_eq!(achievements.streak().played, 20);
+        assert_eq!(achievements.streak().won, 19);
+    }
+
+    #[test]
+    fn games_in_progress_are_not_recorded() {
+        let mut achievements = Achievements::new();
+        let won = play(&[1, 2, 3, 4], &[&[1, 2, 3, 4]]);
+        achievements.record(&won);
+
+        let unfinished = play(&[1, 2, 3, 4], &[&[0, 0, 0, 0]]);
+        assert!(achievements.record(&unfinished).is_empty());
+        assert_eq!(
+            achievements.streak(),
+            Streak {
+                played:  1,
+                won:     1,
+                current: 1,
+                best:    1,
+            }
+        );
+    }
+
+    #[test]
+    fn custom_achievements_can_be_registered() {
+        let mut achievements = Achievements::empty();
+        achievements.register(Achievement::new(
+            "lose",
+            "Good Sport",
+            "Lose a game.",
+            |game, _| !game.is_solved(),
+        ));
+
+        let mut lost = play(&[1, 1, 1, 1], &[&[0, 0, 0, 0]]);
+        lost.forfeit();
+        assert_eq!(achievements.record(&lost), vec!["lose"]);
+        assert_eq!(achievements.unlocked().count(), 1);
+    }
+}
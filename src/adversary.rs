@@ -0,0 +1,121 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    knowledge::codes,
+    solver::{Solver, Strategy},
+    tournament::Tournament,
+    Game, GameBuilder, GuessError, Rules,
+};
+
+// Plays `strategy` against a codemaker that never commits to a secret: every
+// guess gets whichever score keeps the most secrets possible, and the guess
+// is only accepted as right once nothing else is left. The returned game has
+// the one secret consistent with every answer.
+pub fn adaptive(
+    peg_count: u8,
+    colors: u8,
+    strategy: Strategy,
+    seed: u64,
+) -> Result<Game, GuessError> {
+    // Any remaining candidate fits every answer so far, so the game can be
+    // replayed as if it had been the secret all along.
+    let replay = |secret: &[u8], guesses: &[Vec<u8>]| {
+        let mut game = GameBuilder::new()
+            .peg_count(peg_count)
+            .peg_range(colors)
+            .pegs(secret)
+            .unlimited_guesses(true)
+            .build();
+        for guess in guesses {
+            game.guess(guess)?;
+        }
+        Ok::<_, GuessError>(game)
+    };
+
+    let alphabet = (0..colors).collect::<Vec<_>>();
+    let mut candidates = codes(&alphabet, peg_count..=peg_count);
+    let mut game = replay(&candidates[0], &[])?;
+    let mut solver = Solver::new(&game).strategy(strategy).seed(seed);
+    let mut guesses = Vec::new();
+
+    while !game.is_solved() {
+        let guess = solver.next_guess(&game);
+        let mut groups = BTreeMap::<_, Vec<_>>::new();
+        for candidate in candidates {
+            let score = Rules::default().score(&candidate, &guess);
+            groups.entry(score).or_default().push(candidate);
+        }
+        candidates = groups
+            .into_values()
+            .max_by_key(|group| (group.len(), !group.contains(&guess)))
+            .unwrap_or_default();
+
+        guesses.push(guess);
+        game = replay(&candidates[0], &guesses)?;
+        solver.update(&game);
+    }
+    Ok(game)
+}
+
+// The secrets that took `strategy` the most guesses over every game of
+// `tournament`, and how many guesses that was.
+pub fn hardest_secrets(
+    tournament: &Tournament,
+    strategy: Strategy,
+) -> Result<(usize, Vec<Vec<u8>>), GuessError> {
+    let tournament = tournament.clone().strategies(&[strategy]);
+    let standing = tournament.run()?.remove(0);
+    let worst = standing.worst();
+    let secrets = tournament
+        .secrets()
+        .into_iter()
+        .zip(&standing.guesses)
+        .filter(|&(_, &guesses)| guesses == worst)
+        .map(|(secret, _)| secret)
+        .collect();
+    Ok((worst, secrets))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adaptive_codemaker_stays_consistent() {
+        for strategy in [Strategy::Knuth, Strategy::Entropy] {
+            let adaptive = adaptive(3, 4, strategy, 1).unwrap();
+            assert!(adaptive.is_solved());
+
+            // Every answer fits the final secret, so a deterministic
+            // strategy plays the same game against it.
+            let mut game = GameBuilder::new()
+                .peg_count(3)
+                .peg_range(4)
+                .pegs(adaptive.pegs())
+                .build();
+            Solver::new(&game)
+                .strategy(strategy)
+                .solve(&mut game)
+                .unwrap();
+            assert_eq!(game.guesses(), adaptive.guesses());
+        }
+        assert!(adaptive(3, 4, Strategy::Random, 1).unwrap().is_solved());
+    }
+
+    #[test]
+    fn finds_the_hardest_secrets() {
+        let tournament = Tournament::new().config(3, 3);
+        let (worst, secrets) =
+            hardest_secrets(&tournament, Strategy::Knuth).unwrap();
+
+        assert!(!secrets.is_empty());
+        for secret in secrets {
+            let mut game = GameBuilder::new()
+                .peg_count(3)
+                .peg_range(3)
+                .pegs(&secret)
+                .build();
+            assert_eq!(Solver::new(&game).solve(&mut game), Ok(worst));
+        }
+    }
+}
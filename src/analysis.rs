@@ -0,0 +1,166 @@
+use std::{collections::HashMap, time::Instant};
+
+use crate::{
+    knowledge::{is_consistent, Knowledge},
+    solver::{SearchStats, Solver},
+    Game, Rules,
+};
+
+// Comparing a guess against the best one means scoring every candidate
+// against every other, so it's skipped while more than this many remain.
+const MAX_COMPARED: usize = 300;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Good,
+    // Left at least twice as many candidates in the worst case as the best
+    // guess would have.
+    Inaccuracy,
+    // Ruled nothing out.
+    Blunder,
+}
+
+// How one guess of a finished game measured up.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GuessReport {
+    pub guess:      Vec<u8>,
+    pub hits:       u8,
+    pub near_hits:  u8,
+    // Candidates left before and after the guess.
+    pub before:     usize,
+    pub after:      usize,
+    pub bits:       f64,
+    // Whether the guess could have been the secret.
+    pub consistent: bool,
+    // The most candidates the guess could have left, whatever the feedback,
+    // and the same for the solver's choice when it was worked out.
+    pub worst_case: usize,
+    pub best_case:  Option<usize>,
+    // What finding the solver's choice took, when it was worked out.
+    pub search:     Option<SearchStats>,
+    pub verdict:    Verdict,
+}
+
+pub fn analyze(game: &Game) -> Vec<GuessReport> {
+    let rules = game.rules();
+
+    (0..game.guesses().len())
+        .map(|i| {
+            let guess = &game.guesses()[i];
+            let (hits, near_hits) = game.hits(i).unwrap();
+            let before =
+                Knowledge::new(&game.truncated(i)).candidates().to_vec();
+            let next = game.truncated(i + 1);
+            let after = before
+                .iter()
+                .filter(|code| is_consistent(&next, code))
+                .count();
+
+            let worst_case = largest_partition(rules, &before, guess);
+            let (best, search) = if before.len() <= MAX_COMPARED {
+                let solver = Solver::from_candidates(before.clone());
+                let started = Instant::now();
+                let best = solver.best_guess(rules);
+                let search = SearchStats {
+                    elapsed:     started.elapsed(),
+                    evaluations: solver.evaluations(),
+                };
+                (best, Some(search))
+            }
+            else {
+                (None, None)
+            };
+            let best_case =
+                best.map(|best| largest_partition(rules, &before, &best));
+
+            let verdict = if after == before.len() && !next.is_solved() {
+                Verdict::Blunder
+            }
+            else if best_case.is_some_and(|best| worst_case >= best * 2) {
+                Verdict::Inaccuracy
+            }
+            else {
+                Verdict::Good
+            };
+
+            GuessReport {
+                guess: guess.clone(),
+                hits,
+                near_hits,
+                before: before.len(),
+                after,
+                bits: (before.len() as f64 / after.max(1) as f64).log2(),
+                consistent: before.contains(guess),
+                worst_case,
+                best_case,
+                search,
+                verdict,
+            }
+        })
+        .collect()
+}
+
+fn largest_partition(
+    rules: &Rules,
+    candidates: &[Vec<u8>],
+    guess: &[u8],
+) -> usize {
+    let mut partitions = HashMap::new();
+    for candidate in candidates {
+        *partitions.entry(rules.score(candidate, guess)).or_insert(0) += 1;
+    }
+    partitions.into_values().max().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GameBuilder;
+
+    #[test]
+    fn reports_follow_the_candidates() {
+        let mut game = GameBuilder::new().pegs(&[1, 2, 3, 4]).build();
+        for guess in [[0, 0, 1, 1], [1, 2, 2, 3], [1, 3, 4, 5], [1, 2, 3, 4]] {
+            game.guess(&guess).unwrap();
+        }
+
+        let reports = analyze(&game);
+        assert_eq!(reports.len(), 4);
+        assert_eq!(reports[0].before, 1296);
+        assert_eq!(reports[0].best_case, None);
+        for pair in reports.windows(2) {
+            assert_eq!(pair[0].after, pair[1].before);
+        }
+        assert_eq!(reports[3].after, 1);
+        assert!(reports[3].consistent);
+        assert!(reports.iter().all(|report| report.bits >= 0.0));
+    }
+
+    #[test]
+    fn wasted_guesses_are_blunders() {
+        let mut game = GameBuilder::new().pegs(&[1, 2, 3, 4]).build();
+        game.guess(&[0, 0, 0, 0]).unwrap();
+        game.guess(&[0, 0, 5, 5]).unwrap();
+        game.guess(&[5, 0, 5, 0]).unwrap();
+
+        let reports = analyze(&game);
+        assert_eq!(reports[2].verdict, Verdict::Blunder);
+        assert_eq!(reports[2].bits, 0.0);
+        assert!(!reports[2].consistent);
+    }
+
+    #[test]
+    fn much_worse_guesses_are_inaccuracies() {
+        let mut game = GameBuilder::new().pegs(&[1, 2, 3, 4]).build();
+        game.guess(&[0, 0, 1, 1]).unwrap();
+        game.guess(&[2, 2, 2, 2]).unwrap();
+
+        // 256 candidates are left after the first guess. The best second
+        // guess leaves at most 44 of them, but this one can leave 108.
+        let report = &analyze(&game)[1];
+        assert_eq!(report.best_case, Some(44));
+        assert_eq!(report.worst_case, 108);
+        assert_eq!(report.verdict, Verdict::Inaccuracy);
+        assert_eq!(report.search.unwrap().evaluations, 256 * 256);
+    }
+}
@@ -0,0 +1,173 @@
+use crate::{Game, GameBuilder};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stage {
+    pub peg_count:   u8,
+    pub peg_range:   u8,
+    pub max_guesses: u8,
+}
+
+impl Stage {
+    pub fn builder(&self) -> GameBuilder {
+        GameBuilder::new()
+            .peg_count(self.peg_count)
+            .peg_range(self.peg_range)
+            .max_guesses(self.max_guesses)
+    }
+}
+
+// Progress through a campaign, kept separate from the stages themselves so it
+// can be stored and restored by frontends.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Progress {
+    // The fewest guesses used to beat each stage, if it has been beaten.
+    pub best: Vec<Option<u8>>,
+}
+
+pub struct Campaign {
+    stages:   Vec<Stage>,
+    progress: Progress,
+}
+
+impl Campaign {
+    pub fn new(stages: Vec<Stage>) -> Self {
+        Self::with_progress(stages, Progress::default())
+    }
+
+    pub fn with_progress(stages: Vec<Stage>, mut progress: Progress) -> Self {
+        progress.best.resize(stages.len(), None);
+        Self { stages, progress }
+    }
+
+    // Starts small and adds colors, then pegs, while tightening the guess
+    // limit.
+    pub fn standard() -> Self {
+        let stage = |peg_count, peg_range, max_guesses| Stage {
+            peg_count,
+            peg_range,
+            max_guesses,
+        };
+
+        Self::new(vec![
+            stage(3, 4, 10),
+            stage(4, 4, 10),
+            stage(4, 5, 10),
+            stage(4, 6, 10),
+            stage(4, 6, 8),
+            stage(5, 6, 10),
+            stage(5, 7, 10),
+            stage(5, 8, 10),
+            stage(5, 8, 8),
+            stage(6, 8, 10),
+        ])
+    }
+
+    pub fn stages(&self) -> &[Stage] { &self.stages }
+    pub fn progress(&self) -> &Progress { &self.progress }
+
+    pub fn is_complete(&self, stage: usize) -> bool {
+        matches!(self.progress.best.get(stage), Some(Some(_)))
+    }
+
+    pub fn is_unlocked(&self, stage: usize) -> bool {
+        stage < self.stages.len() && (stage == 0 || self.is_complete(stage - 1))
+    }
+
+    // The first stage that hasn't been beaten yet, or `None` once the whole
+    // campaign is finished.
+    pub fn current_stage(&self) -> Option<usize> {
+        (0..self.stages.len()).find(|&stage| !self.is_complete(stage))
+    }
+
+    pub fn start(&self, stage: usize) -> Option<Game> {
+        self.is_unlocked(stage)
+            .then(|| self.stages[stage].builder().build())
+    }
+
+    // Records a finished game for `stage`, returning whether it was won.
+    pub fn record(&mut self, stage: usize, game: &Game) -> bool {
+        if !self.is_unlocked(stage) || !game.is_solved() {
+            return false;
+        }
+
+        let guesses = game.guesses().len() as u8;
+        let best = &mut self.progress.best[stage];
+        *best = Some(best.map_or(guesses, |best| best.min(guesses)));
+        true
+    }
+}
+
+impl Default for Campaign {
+    fn default() -> Self { Self::standard() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn win(game: &mut Game) {
+        let pegs = game.pegs().to_vec();
+        game.guess(&pegs).unwrap();
+    }
+
+    #[test]
+    fn stages_escalate() {
+        let campaign = Campaign::standard();
+        for pair in campaign.stages().windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            assert!(
+                b.peg_count > a.peg_count
+                    || b.peg_range > a.peg_range
+                    || b.max_guesses < a.max_guesses
+            );
+        }
+    }
+
+    #[test]
+    fn stages_unlock_in_order() {
+        let mut campaign = Campaign::standard();
+        assert_eq!(campaign.current_stage(), Some(0));
+        assert!(campaign.is_unlocked(0));
+        assert!(!campaign.is_unlocked(1));
+        assert!(campaign.start(1).is_none());
+
+        let mut game = campaign.start(0).unwrap();
+        assert_eq!(game.pegs().len(), 3);
+        win(&mut game);
+
+        assert!(campaign.record(0, &game));
+        assert!(campaign.is_unlocked(1));
+        assert_eq!(campaign.current_stage(), Some(1));
+        assert_eq!(campaign.progress().best[0], Some(1));
+    }
+
+    #[test]
+    fn lost_games_do_not_unlock() {
+        let mut campaign = Campaign::standard();
+        let game = campaign.start(0).unwrap();
+        assert!(!campaign.record(0, &game));
+        assert!(!campaign.is_unlocked(1));
+    }
+
+    #[test]
+    fn progress_can_be_restored() {
+        let progress = Progress {
+            best: vec![Some(4), Some(6)],
+        };
+        let campaign =
+            Campaign::with_progress(Campaign::standard().stages, progress);
+        assert_eq!(campaign.current_stage(), Some(2));
+        assert_eq!(campaign.progress().best.len(), campaign.stages().len());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn progress_round_trips() {
+        let progress = Progress {
+            best: vec![Some(4), None],
+        };
+        let json = serde_json::to_string(&progress).unwrap();
+        assert_eq!(serde_json::from_str::<Progress>(&json).unwrap(), progress);
+    }
+}
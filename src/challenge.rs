@@ -0,0 +1,154 @@
+use std::{fmt, str::FromStr};
+
+use crate::{Difficulty, GameBuilder};
+
+// Crockford's base 32, which leaves out letters easily mistaken for digits.
+const DIGITS: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+// A game a player can send to a friend as a short code like `H-3KX9TQ2A`:
+// everyone playing the same code gets the same secret.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Challenge {
+    pub difficulty: Difficulty,
+    pub seed:       u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChallengeError {
+    Malformed,
+    UnknownDifficulty(char),
+    InvalidDigit(char),
+}
+
+impl Challenge {
+    pub fn new(difficulty: Difficulty, seed: u64) -> Self {
+        Self { difficulty, seed }
+    }
+
+    pub fn builder(&self) -> GameBuilder {
+        GameBuilder::from(self.difficulty).seed(self.seed)
+    }
+
+    pub fn code(&self) -> String { self.to_string() }
+}
+
+impl fmt::Display for Challenge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut digits = Vec::new();
+        let mut seed = self.seed;
+        loop {
+            digits.push(DIGITS[(seed % 32) as usize] as char);
+            seed /= 32;
+            if seed == 0 {
+                break;
+            }
+        }
+
+        let difficulty = self.difficulty.name().chars().next().unwrap();
+        let digits = digits.iter().rev().collect::<String>();
+        write!(f, "{}-{}", difficulty.to_ascii_uppercase(), digits)
+    }
+}
+
+impl FromStr for Challenge {
+    type Err = ChallengeError;
+
+    // Codes are read leniently: case doesn't matter, and O, I and L are read
+    // as the digits they look like.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (difficulty, digits) =
+            s.trim().split_once('-').ok_or(ChallengeError::Malformed)?;
+
+        let mut letters = difficulty.chars();
+        let (Some(letter), None) = (letters.next(), letters.next())
+        else {
+            return Err(ChallengeError::Malformed);
+        };
+        let difficulty = Difficulty::ALL
+            .into_iter()
+            .find(|d| d.name().starts_with(letter.to_ascii_lowercase()))
+            .ok_or(ChallengeError::UnknownDifficulty(letter))?;
+
+        if digits.is_empty() || digits.len() > 13 {
+            return Err(ChallengeError::Malformed);
+        }
+        let mut seed = 0u64;
+        for c in digits.chars() {
+            let digit = match c.to_ascii_uppercase() {
+                'O' => '0',
+                'I' | 'L' => '1',
+                c => c,
+            };
+            let value = DIGITS
+                .iter()
+                .position(|&d| d as char == digit)
+                .ok_or(ChallengeError::InvalidDigit(c))?;
+            seed = seed
+                .checked_mul(32)
+                .and_then(|seed| seed.checked_add(value as u64))
+                .ok_or(ChallengeError::Malformed)?;
+        }
+        Ok(Self::new(difficulty, seed))
+    }
+}
+
+impl fmt::Display for ChallengeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Malformed => f.write_str("not a challenge code"),
+            Self::UnknownDifficulty(c) => write!(f, "no difficulty `{}`", c),
+            Self::InvalidDigit(c) => {
+                write!(f, "`{}` can't appear in a challenge code", c)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ChallengeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_round_trip() {
+        for seed in [0, 31, 32, 123_456_789, u64::MAX] {
+            for difficulty in Difficulty::ALL {
+                let challenge = Challenge::new(difficulty, seed);
+                assert_eq!(challenge.code().parse(), Ok(challenge));
+            }
+        }
+        assert_eq!(Challenge::new(Difficulty::Hard, 32).code(), "H-10");
+    }
+
+    #[test]
+    fn codes_are_read_leniently() {
+        let challenge = Challenge::new(Difficulty::Easy, 32 * 32 + 1);
+        assert_eq!(challenge.code(), "E-101");
+        assert_eq!(" e-1o1 ".parse(), Ok(challenge));
+        assert_eq!("E-lOI".parse(), Ok(challenge));
+
+        assert_eq!("E101".parse::<Challenge>(), Err(ChallengeError::Malformed));
+        assert_eq!(
+            "X-101".parse::<Challenge>(),
+            Err(ChallengeError::UnknownDifficulty('X'))
+        );
+        assert_eq!(
+            "E-1U1".parse::<Challenge>(),
+            Err(ChallengeError::InvalidDigit('U'))
+        );
+        assert_eq!(
+            "E-ZZZZZZZZZZZZZ".parse::<Challenge>(),
+            Err(ChallengeError::Malformed)
+        );
+    }
+
+    #[test]
+    fn challenges_are_reproducible() {
+        let challenge = "M-3KX9TQ2A".parse::<Challenge>().unwrap();
+        let a = challenge.builder().build();
+        let b = challenge.builder().build();
+        assert_eq!(a.pegs(), b.pegs());
+        assert_eq!(a.max_guesses(), Some(10));
+    }
+}
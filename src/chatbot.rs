@@ -0,0 +1,271 @@
+use rand::random;
+
+use crate::{
+    messages::{Catalog, English, Message},
+    render::{Description, Text},
+    transcript::parse_guess,
+    Game, GameBuilder,
+};
+
+const HELP: &str = "\
+Commands:
+  new [PEGSxCOLORS]  start a game, 4x6 by default
+  guess CODE         guess by color initials (rgby) or numbers (0123)
+  board              show the guesses so far
+  give up            reveal the code
+  help               show this message";
+
+// What the bot should say, and the state to pass back with the next command.
+// The state is `None` when no game is in progress.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reply {
+    pub text:  String,
+    pub state: Option<String>,
+}
+
+// Runs a game one chat message at a time, keeping nothing between calls: the
+// caller stores the state blob (per channel or per user) and hands it back
+// with the next command. The blob names the game's seed rather than its
+// secret, so peeking at it doesn't give the code away at a glance.
+pub fn reply(command: &str, state: Option<&str>) -> Reply {
+    let game = state.and_then(decode);
+    let damaged = state.is_some() && game.is_none();
+    let command = command.trim().to_lowercase();
+    let (verb, rest) = command.split_once(' ').unwrap_or((&command, ""));
+
+    match (verb, game) {
+        ("new" | "start", _) => new_game(rest.trim()),
+        ("help", game) => Reply {
+            text:  HELP.to_owned(),
+            state: game.map(|(game, seed)| encode(&game, seed)),
+        },
+        (_, None) if damaged => Reply {
+            text:  "That game's state is damaged. Start a new one with `new`."
+                .to_owned(),
+            state: None,
+        },
+        (_, None) => Reply {
+            text:  "No game in progress. Start one with `new`.".to_owned(),
+            state: None,
+        },
+        ("guess" | "g", Some((game, seed))) => guess(game, seed, rest),
+        ("board", Some((game, seed))) => Reply {
+            text:  board(&game),
+            state: Some(encode(&game, seed)),
+        },
+        ("give" | "reveal" | "forfeit", Some((game, _))) => Reply {
+            text:  format!("The code was {}.", describe_code(game.pegs())),
+            state: None,
+        },
+        (_, Some((game, seed))) => Reply {
+            text:  format!("I don't know `{}`. Try `help`.", verb),
+            state: Some(encode(&game, seed)),
+        },
+    }
+}
+
+fn new_game(config: &str) -> Reply {
+    let config = if config.is_empty() { "4x6" } else { config };
+    let Some((pegs, colors)) = parse_config(config)
+    else {
+        return Reply {
+            text:  format!(
+                "`{}` isn't a game like 4x6 (up to 10 pegs and 10 colors).",
+                config
+            ),
+            state: None,
+        };
+    };
+
+    let seed = random::<u64>() >> 24;
+    let game = builder(pegs, colors, seed).build();
+    let names = (0..colors)
+        .map(|peg| Description::default().name(peg))
+        .collect::<Vec<_>>();
+    Reply {
+        text:  format!(
+            "New game: {} pegs from {}. {} guesses to crack it.",
+            pegs,
+            names.join(", "),
+            game.max_guesses().unwrap_or_default()
+        ),
+        state: Some(encode(&game, seed)),
+    }
+}
+
+fn guess(mut game: Game, seed: u64, code: &str) -> Reply {
+    let words = code.split_whitespace().collect::<Vec<_>>();
+    let text = match parse_guess(&words, &Description::default()) {
+        None => format!("`{}` isn't a guess. Try `guess rgby`.", code.trim()),
+        Some(guess) => {
+            match guess.iter().find(|&&peg| !game.is_guessable(peg)) {
+                Some(&peg) => English.message(&Message::NotInAlphabet(peg)),
+                None => match game.guess(&guess) {
+                    Ok((hits, near_hits)) => format!(
+                        "{}: {}",
+                        describe_code(&guess),
+                        English.message(&Message::Feedback { hits, near_hits })
+                    ),
+                    Err(e) => English.message(&(&e).into()),
+                },
+            }
+        }
+    };
+
+    let ending = match (game.is_solved(), game.is_over()) {
+        (true, _) => Message::Solved {
+            guesses: game.guesses().len(),
+        },
+        (false, true) => Message::OutOfGuesses {
+            code: &describe_code(game.pegs()),
+        },
+        _ => {
+            return Reply {
+                text,
+                state: Some(encode(&game, seed)),
+            }
+        }
+    };
+    Reply {
+        text:  format!("{}\n{}", text, English.message(&ending)),
+        state: None,
+    }
+}
+
+fn board(game: &Game) -> String {
+    let prompt = English.message(&Message::Prompt {
+        guess:       game.guesses().len() + 1,
+        max_guesses: game.max_guesses(),
+    });
+    format!(
+        "{}{}",
+        Text::new().render(game),
+        prompt.trim_end_matches([':', ' '])
+    )
+}
+
+fn describe_code(pegs: &[u8]) -> String {
+    let names = pegs.iter().map(|&peg| Description::default().name(peg));
+    names.collect::<Vec<_>>().join(" ")
+}
+
+// `PEGSxCOLORS`, for games of up to 10 pegs and 10 colors.
+fn parse_config(config: &str) -> Option<(u8, u8)> {
+    let (pegs, colors) = config.split_once('x')?;
+    let (pegs, colors) = (pegs.parse().ok()?, colors.parse().ok()?);
+    ((1..=10).contains(&pegs) && (2..=10).contains(&colors))
+        .then_some((pegs, colors))
+}
+
+fn builder(pegs: u8, colors: u8, seed: u64) -> GameBuilder {
+    GameBuilder::new()
+        .peg_count(pegs)
+        .peg_range(colors)
+        .seed(seed)
+}
+
+// `v1 PEGSxCOLORS SEED GUESS...`, with each guess written as digits. The
+// blob comes back from wherever the caller stored it, so `decode` checks it as
+// carefully as a new command.
+fn encode(game: &Game, seed: u64) -> String {
+    let mut state = format!(
+        "v1 {}x{} {}",
+        game.peg_counts().start(),
+        game.alphabet().len(),
+        seed
+    );
+    for guess in game.guesses() {
+        state.push(' ');
+        state.extend(guess.iter().map(|&peg| char::from(b'0' + peg)));
+    }
+    state
+}
+
+fn decode(state: &str) -> Option<(Game, u64)> {
+    let mut fields = state.split_whitespace();
+    if fields.next()? != "v1" {
+        return None;
+    }
+    let (pegs, colors) = parse_config(fields.next()?)?;
+    let seed = fields.next()?.parse().ok()?;
+
+    let mut game = builder(pegs, colors, seed).try_build().ok()?;
+    for guess in fields {
+        let guess = guess
+            .chars()
+            .map(|c| c.to_digit(10).map(|peg| peg as u8))
+            .collect::<Option<Vec<_>>>()?;
+        // The blob comes from the user, so it gets the same checks as a
+        // live guess.
+        if !guess.iter().all(|&peg| game.is_guessable(peg)) {
+            return None;
+        }
+        game.guess(&guess).ok()?;
+    }
+    Some((game, seed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plays_a_game_through_the_state_blob() {
+        let started = reply("new", None);
+        assert!(started.text.starts_with("New game: 4 pegs from red, green"));
+        let state = started.state.unwrap();
+
+        let guessed = reply("guess rrgg", Some(&state));
+        assert!(guessed.text.starts_with("red red green green: "));
+        let state = guessed.state.unwrap();
+        assert!(state.ends_with(" 0011"));
+
+        let board = reply("board", Some(&state));
+        assert!(board.text.starts_with("1 | 0 0 1 1 | "));
+        assert!(board.text.ends_with("Guess 2/12"));
+        assert_eq!(board.state.as_deref(), Some(state.as_str()));
+
+        let (game, _) = decode(&state).unwrap();
+        let secret = game.pegs().iter().map(|peg| peg.to_string());
+        let solved = reply(
+            &format!("guess {}", secret.collect::<String>()),
+            Some(&state),
+        );
+        assert!(solved.text.ends_with("You cracked it in 2 guesses!"));
+        assert_eq!(solved.state, None);
+    }
+
+    #[test]
+    fn explains_mistakes() {
+        assert_eq!(reply("guess rgby", None).state, None);
+        assert!(reply("new 4 by 6", None).text.contains("isn't a game"));
+
+        let state = reply("new 3x4", None).state.unwrap();
+        assert!(reply("guess xyz", Some(&state))
+            .text
+            .contains("isn't a guess"));
+        assert!(reply("guess 019", Some(&state)).text.contains("no peg 9"));
+        assert!(reply("guess 01", Some(&state))
+            .text
+            .contains("wrong length"));
+        assert!(reply("dance", Some(&state)).text.contains("`help`"));
+        assert_eq!(reply("guess 01", Some(&state)).state, Some(state.clone()));
+
+        for state in [
+            "v1 4x0 5",
+            "v1 0x6 5",
+            "v1 99x6 5",
+            "v1 4x6 x",
+            "v2",
+            "v1 4x6 5 0079",
+        ] {
+            let reply = reply("board", Some(state));
+            assert!(reply.text.contains("damaged"));
+            assert_eq!(reply.state, None);
+        }
+
+        let revealed = reply("give up", Some(&state));
+        assert!(revealed.text.starts_with("The code was "));
+        assert_eq!(revealed.state, None);
+    }
+}
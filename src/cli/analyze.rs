@@ -0,0 +1,160 @@
+use std::io::{self, Write};
+
+use mastermind::analysis::{GuessReport, Verdict};
+
+use super::input::format_pegs;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Markdown,
+    Html,
+}
+
+impl Format {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "text" => Some(Self::Text),
+            "markdown" | "md" => Some(Self::Markdown),
+            "html" => Some(Self::Html),
+            _ => None,
+        }
+    }
+}
+
+const HEADINGS: [&str; 8] = [
+    "#", "guess", "feedback", "before", "after", "bits", "search", "note",
+];
+
+fn note(report: &GuessReport) -> String {
+    match report.verdict {
+        Verdict::Good => String::new(),
+        Verdict::Inaccuracy => format!(
+            "inaccuracy: could leave {}, the best guess at most {}",
+            report.worst_case,
+            report.best_case.unwrap_or_default()
+        ),
+        Verdict::Blunder => "blunder: ruled nothing out".to_owned(),
+    }
+}
+
+// The solver's work finding its own choice, when it was worked out.
+fn search(report: &GuessReport) -> String {
+    report.search.map_or_else(String::new, |search| {
+        format!(
+            "{} in {:.1}ms",
+            search.evaluations,
+            search.elapsed.as_secs_f64() * 1000.0
+        )
+    })
+}
+
+fn cells(index: usize, report: &GuessReport) -> [String; 8] {
+    [
+        (index + 1).to_string(),
+        format_pegs(&report.guess),
+        format!("{}/{}", report.hits, report.near_hits),
+        report.before.to_string(),
+        report.after.to_string(),
+        format!("{:.2}", report.bits),
+        search(report),
+        note(report),
+    ]
+}
+
+pub fn write_report(
+    reports: &[GuessReport],
+    format: Format,
+    mut output: impl Write,
+) -> io::Result<()> {
+    let rows = reports
+        .iter()
+        .enumerate()
+        .map(|(i, report)| cells(i, report))
+        .collect::<Vec<_>>();
+
+    match format {
+        Format::Text => {
+            let line = |cells: &[&str]| {
+                format!(
+                    "{:>3}  {:<12} {:<8} {:>6} {:>6} {:>6} {:>18}  {}",
+                    cells[0],
+                    cells[1],
+                    cells[2],
+                    cells[3],
+                    cells[4],
+                    cells[5],
+                    cells[6],
+                    cells[7]
+                )
+            };
+            writeln!(output, "{}", line(&HEADINGS).trim_end())?;
+            for row in &rows {
+                let row = row.iter().map(String::as_str).collect::<Vec<_>>();
+                writeln!(output, "{}", line(&row).trim_end())?;
+            }
+        }
+        Format::Markdown => {
+            writeln!(output, "| {} |", HEADINGS.join(" | "))?;
+            writeln!(output, "|{}", "---|".repeat(HEADINGS.len()))?;
+            for row in &rows {
+                writeln!(output, "| {} |", row.join(" | "))?;
+            }
+        }
+        Format::Html => {
+            writeln!(output, "<table>")?;
+            let headings = HEADINGS.map(|h| format!("<th>{}</th>", h));
+            writeln!(output, "  <tr>{}</tr>", headings.concat())?;
+            for row in &rows {
+                let row = row.iter().map(|cell| format!("<td>{}</td>", cell));
+                writeln!(output, "  <tr>{}</tr>", row.collect::<String>())?;
+            }
+            writeln!(output, "</table>")?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use mastermind::{analysis::analyze, GameBuilder};
+
+    use super::*;
+
+    fn run(format: Format) -> String {
+        let mut game = GameBuilder::new().pegs(&[1, 2, 3, 4]).build();
+        for guess in [[0, 0, 0, 0], [0, 0, 0, 0], [1, 2, 3, 4]] {
+            game.guess(&guess).unwrap();
+        }
+
+        let mut output = Vec::new();
+        write_report(&analyze(&game), format, &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn writes_text_tables() {
+        let output = run(Format::Text);
+        let lines = output.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("  #  guess"));
+        assert!(lines[1].starts_with("  1  0 0 0 0      0/0"));
+        assert!(lines[2].ends_with("blunder: ruled nothing out"));
+        // Too many candidates remain to search for the best guess.
+        assert!(lines[0].contains(" search  note"));
+        assert!(!lines[3].contains("ms"));
+    }
+
+    #[test]
+    fn writes_markdown_and_html() {
+        let output = run(Format::Markdown);
+        assert!(output.starts_with("| # | guess | feedback |"));
+        assert!(output.contains("\n|---|---|"));
+        assert!(output.contains("| 2 | 0 0 0 0 | 0/0 |"));
+
+        let output = run(Format::Html);
+        assert!(output.starts_with("<table>\n  <tr><th>#</th>"));
+        assert!(output.contains("<td>1 2 3 4</td><td>4/0</td>"));
+        assert!(output.ends_with("</table>\n"));
+    }
+}
@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+
+// Minimal argument parsing: a command, positional arguments, and
+// `--name value` options. An option with no value is treated as a flag.
+#[derive(Debug, Default)]
+pub struct Args {
+    positional: Vec<String>,
+    options:    HashMap<String, String>,
+}
+
+impl Args {
+    pub fn parse(args: &[String]) -> Self {
+        let mut parsed = Self::default();
+        let mut args = args.iter().peekable();
+
+        while let Some(arg) = args.next() {
+            match arg.strip_prefix("--") {
+                Some(name) => {
+                    let value = args
+                        .next_if(|next| !next.starts_with("--"))
+                        .cloned()
+                        .unwrap_or_default();
+                    parsed.options.insert(name.to_owned(), value);
+                }
+                None => parsed.positional.push(arg.clone()),
+            }
+        }
+        parsed
+    }
+
+    pub fn command(&self) -> Option<&str> {
+        self.positional.first().map(String::as_str)
+    }
+
+    // Positional arguments after the command.
+    pub fn positional(&self, index: usize) -> Option<&str> {
+        self.positional.get(index + 1).map(String::as_str)
+    }
+
+    pub fn option(&self, name: &str) -> Option<&str> {
+        self.options.get(name).map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &str) -> Args {
+        let args = args
+            .split_whitespace()
+            .map(String::from)
+            .collect::<Vec<_>>();
+        Args::parse(&args)
+    }
+
+    #[test]
+    fn parses_commands_and_options() {
+        let args = parse("play --variant number --save weekend extra --quiet");
+        assert_eq!(args.command(), Some("play"));
+        assert_eq!(args.positional(0), Some("extra"));
+        assert_eq!(args.option("variant"), Some("number"));
+        assert_eq!(args.option("save"), Some("weekend"));
+        assert_eq!(args.option("quiet"), Some(""));
+        assert_eq!(args.option("loud"), None);
+    }
+}
@@ -0,0 +1,99 @@
+use std::io::{self, Write};
+
+use mastermind::{
+    solver::{Personality, Strategy},
+    tournament::Standing,
+};
+
+// Parses a configuration like `4x6`: four pegs of six colors.
+pub fn parse_config(config: &str) -> Option<(u8, u8)> {
+    let (pegs, colors) = config.split_once(['x', 'X'])?;
+    let pegs = pegs.trim().parse().ok().filter(|&pegs| pegs > 0)?;
+    let colors = colors.trim().parse().ok().filter(|&colors| colors > 1)?;
+    Some((pegs, colors))
+}
+
+// Parses a strategy or personality name.
+pub fn parse_solver(name: &str) -> Result<Strategy, String> {
+    Strategy::from_name(name)
+        .or_else(|| Personality::from_name(name).map(|p| p.strategy()))
+        .ok_or_else(|| format!("unknown solver `{}`", name))
+}
+
+// Parses a comma-separated list of solver names.
+pub fn parse_solvers(solvers: &str) -> Result<Vec<Strategy>, String> {
+    solvers
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(parse_solver)
+        .collect()
+}
+
+pub fn write_table(
+    standings: &[Standing],
+    mut output: impl Write,
+) -> io::Result<()> {
+    writeln!(
+        output,
+        "{:<10} {:>6} {:>8} {:>6} {:>10}",
+        "solver", "games", "average", "worst", "time"
+    )?;
+    for standing in standings {
+        writeln!(
+            output,
+            "{:<10} {:>6} {:>8.3} {:>6} {:>9.2}s",
+            standing.strategy.name(),
+            standing.games(),
+            standing.average(),
+            standing.worst(),
+            standing.elapsed.as_secs_f64()
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn parses_configs_and_solvers() {
+        assert_eq!(parse_config("4x6"), Some((4, 6)));
+        assert_eq!(parse_config("5X8"), Some((5, 8)));
+        assert_eq!(parse_config("4x1"), None);
+        assert_eq!(parse_config("four"), None);
+
+        assert_eq!(
+            parse_solvers("knuth, random"),
+            Ok(vec![Strategy::Knuth, Strategy::Random])
+        );
+        assert_eq!(
+            parse_solvers("safe,aggressive,human-like"),
+            Ok(vec![Strategy::Knuth, Strategy::Entropy, Strategy::Human])
+        );
+        assert!(parse_solvers("knuth,best").is_err());
+    }
+
+    #[test]
+    fn writes_a_row_per_strategy() {
+        let standing = Standing {
+            strategy: Strategy::Entropy,
+            guesses:  vec![3, 4, 5, 4],
+            elapsed:  Duration::from_millis(1500),
+        };
+        let mut output = Vec::new();
+        write_table(&[standing], &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        let rows = output.lines().collect::<Vec<_>>();
+        assert_eq!(rows.len(), 2);
+        assert!(rows[0].starts_with("solver"));
+        assert_eq!(
+            rows[1].split_whitespace().collect::<Vec<_>>(),
+            ["entropy", "4", "4.000", "5", "1.50s"]
+        );
+    }
+}
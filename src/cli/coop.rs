@@ -0,0 +1,109 @@
+use std::io::{self, Write};
+
+use mastermind::{
+    coop::Coop,
+    messages::{Catalog, Message},
+};
+
+use super::{
+    input::{format_pegs, parse_pegs},
+    play::{ending, feedback},
+    prompt::Prompt,
+};
+
+// Plays a cooperative game at one keyboard, passing the turn round the
+// players after every guess.
+pub fn play(
+    coop: &mut Coop,
+    catalog: &dyn Catalog,
+    prompt: &mut impl Prompt,
+    mut output: impl Write,
+) -> io::Result<()> {
+    while !coop.game().is_over() {
+        let game = coop.game();
+        let prompt_text = format!(
+            "Player {}, {}",
+            coop.turn() + 1,
+            catalog.message(&Message::Prompt {
+                guess:       game.guesses().len() + 1,
+                max_guesses: game.max_guesses(),
+            })
+        );
+        let Some(line) = prompt.read_line(&prompt_text, &mut output)?
+        else {
+            return Ok(());
+        };
+
+        let guess = match parse_pegs(&line) {
+            Ok(guess) => guess,
+            Err(token) => {
+                let message = Message::NotAPeg(&token);
+                writeln!(output, "{}", catalog.message(&message))?;
+                continue;
+            }
+        };
+        match coop.guess(coop.turn(), &guess) {
+            Ok(_) => {
+                let game = coop.game();
+                let index = game.guesses().len() - 1;
+                writeln!(output, "  {}", feedback(game, index, catalog))?
+            }
+            Err(e) => writeln!(output, "{}", catalog.message(&(&e).into()))?,
+        }
+    }
+
+    let game = coop.game();
+    let code = format_pegs(game.pegs());
+    writeln!(output, "{}", catalog.message(&ending(game, &code)))?;
+
+    for player in 0..coop.players() {
+        let contribution = coop.contribution(player).unwrap_or_default();
+        writeln!(
+            output,
+            "Player {}: {} guesses, ruled out {} codes{}",
+            player + 1,
+            contribution.guesses,
+            contribution.eliminated,
+            if contribution.solved {
+                ", cracked it"
+            }
+            else {
+                ""
+            }
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufRead;
+
+    use mastermind::{messages::English, GameBuilder};
+
+    use super::*;
+
+    #[test]
+    fn passes_the_turn_round() {
+        let builder = GameBuilder::new().pegs(&[1, 2, 3, 4]);
+        let mut coop = Coop::new(builder, 2);
+        let input = "0000\nnonsense\n1234\n";
+        let mut output = Vec::new();
+        play(
+            &mut coop,
+            &English,
+            &mut input.as_bytes().lines(),
+            &mut output,
+        )
+        .unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert!(output.starts_with("Player 1, "));
+        assert!(output.contains("Player 2, "));
+        assert!(output.contains("You cracked it in 2 guesses!\n"));
+        assert!(output.contains("Player 1: 1 guesses, ruled out 671 codes\n"));
+        assert!(output.ends_with(
+            "Player 2: 1 guesses, ruled out 624 codes, cracked it\n"
+        ));
+    }
+}
@@ -0,0 +1,68 @@
+#[cfg(feature = "serde")]
+use mastermind::puzzle::Puzzle;
+use mastermind::{challenge::Challenge, Difficulty};
+use rand::{thread_rng, Rng};
+
+// The seed for each item of a pack: consecutive from `base` so a pack can be
+// regenerated from its seed, or random without one. Random seeds are kept to
+// 40 bits so their challenge codes stay short.
+pub fn seeds(base: Option<u64>) -> impl Iterator<Item = u64> {
+    let mut rng = thread_rng();
+    (0..).map(move |i| match base {
+        Some(base) => base.wrapping_add(i),
+        None => rng.gen::<u64>() >> 24,
+    })
+}
+
+pub fn challenges(
+    difficulty: Difficulty,
+    base: Option<u64>,
+    count: usize,
+) -> Vec<Challenge> {
+    seeds(base)
+        .take(count)
+        .map(|seed| Challenge::new(difficulty, seed))
+        .collect()
+}
+
+// One puzzle per game: the position right before the solver's decisive
+// guess. Games the solver wins too quickly to give a puzzle are skipped.
+#[cfg(feature = "serde")]
+pub fn puzzles(
+    difficulty: Difficulty,
+    base: Option<u64>,
+    count: usize,
+) -> Vec<Puzzle> {
+    seeds(base)
+        .filter_map(|seed| {
+            let builder = Challenge::new(difficulty, seed).builder();
+            Puzzle::from_solver_trace(builder).pop()
+        })
+        .take(count)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeded_challenges_are_reproducible() {
+        let a = challenges(Difficulty::Easy, Some(7), 3);
+        assert_eq!(a, challenges(Difficulty::Easy, Some(7), 3));
+        assert_eq!(a.iter().map(|c| c.seed).collect::<Vec<_>>(), [7, 8, 9]);
+        assert_ne!(challenges(Difficulty::Easy, None, 3), a);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn seeded_puzzle_packs_are_reproducible() {
+        let a = puzzles(Difficulty::Easy, Some(7), 2);
+        let b = puzzles(Difficulty::Easy, Some(7), 2);
+        assert_eq!(a.len(), 2);
+        for (a, b) in a.iter().zip(&b) {
+            assert_eq!(a.board().guesses(), b.board().guesses());
+            assert_eq!(a.guesses_needed(), b.guesses_needed());
+        }
+    }
+}
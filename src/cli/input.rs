@@ -0,0 +1,65 @@
+use mastermind::{render::Description, EMPTY};
+
+// Parses a guess such as `1234`, `1 2 3 4`, `1,2,3,4` or `red blue red green`.
+// Empty holes are written as `-`, `_` or `empty`. On failure, returns the
+// token that isn't a peg.
+pub fn parse_pegs(text: &str) -> Result<Vec<u8>, String> {
+    let text = text.trim();
+    let tokens = if text.contains(|c: char| c.is_whitespace() || c == ',') {
+        text.split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|token| !token.is_empty())
+            .collect::<Vec<_>>()
+    }
+    else {
+        text.char_indices()
+            .map(|(i, c)| &text[i..i + c.len_utf8()])
+            .collect()
+    };
+
+    let names = Description::default();
+    tokens
+        .into_iter()
+        .map(|token| match token {
+            "-" | "_" => Ok(EMPTY),
+            _ if token.eq_ignore_ascii_case("empty") => Ok(EMPTY),
+            _ => token.parse().or_else(|_| {
+                names
+                    .names
+                    .iter()
+                    .position(|name| name.eq_ignore_ascii_case(token))
+                    .map(|peg| peg as u8)
+                    .ok_or_else(|| token.to_owned())
+            }),
+        })
+        .collect()
+}
+
+pub fn format_pegs(pegs: &[u8]) -> String {
+    pegs.iter()
+        .map(|&peg| match peg {
+            EMPTY => "-".to_owned(),
+            _ => peg.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_guesses() {
+        assert_eq!(parse_pegs("1234"), Ok(vec![1, 2, 3, 4]));
+        assert_eq!(parse_pegs(" 1 2 3 4\n"), Ok(vec![1, 2, 3, 4]));
+        assert_eq!(parse_pegs("10,11, 2"), Ok(vec![10, 11, 2]));
+        assert_eq!(parse_pegs("1-2_"), Ok(vec![1, EMPTY, 2, EMPTY]));
+        assert_eq!(parse_pegs("Red blue, empty"), Ok(vec![0, 2, EMPTY]));
+        assert_eq!(parse_pegs("12x4"), Err("x".to_owned()));
+    }
+
+    #[test]
+    fn formats_pegs() {
+        assert_eq!(format_pegs(&[1, EMPTY, 10]), "1 - 10");
+    }
+}
@@ -0,0 +1,153 @@
+use std::{fs, io, time::Duration};
+
+use mastermind::{
+    leaderboard::{Entry, Leaderboard},
+    Game,
+};
+
+use super::paths;
+
+// Bump when the leaderboard format changes, and teach `decode` to read the
+// older version.
+const VERSION: u32 = 1;
+
+// The saved leaderboard, or an empty one if there isn't one yet. A file that
+// can't be read is an error, so it isn't overwritten by the next `store`.
+pub fn load() -> Result<Leaderboard, String> {
+    match fs::read_to_string(paths::leaderboard()) {
+        Ok(text) => decode(&text),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Leaderboard::new()),
+        Err(e) => Err(format!("couldn't read leaderboard: {}", e)),
+    }
+}
+
+pub fn store(leaderboard: &Leaderboard) -> Result<(), String> {
+    let path = paths::leaderboard();
+    path.parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|_| fs::write(&path, encode(leaderboard)))
+        .map_err(|e| format!("couldn't save leaderboard: {}", e))
+}
+
+// Submits a finished game to `board` and saves the result, printing the
+// player's rank if it's a new personal best.
+pub fn submit(
+    board: &str,
+    player: &str,
+    game: &Game,
+    duration: Duration,
+) -> Result<(), String> {
+    let mut leaderboard = load()?;
+    if let Some(rank) = leaderboard.submit(board, player, game, duration) {
+        store(&leaderboard)?;
+        println!("New best! You're #{} on the {} leaderboard.", rank, board);
+    }
+    Ok(())
+}
+
+// A version line, then each board's name on a line of its own, followed by
+// its entries:
+//
+//     version 1
+//     board classic-4x6
+//     entry 4 31250 7 ann
+//
+// An entry is guesses, time in milliseconds, submission order and the
+// player's name. Names come last, so they and board names may contain spaces.
+pub fn encode(leaderboard: &Leaderboard) -> String {
+    let mut boards = leaderboard.boards().collect::<Vec<_>>();
+    boards.sort_unstable();
+
+    let mut text = format!("version {}\n", VERSION);
+    for board in boards {
+        text += &format!("board {}\n", board);
+        for entry in leaderboard.entries(board) {
+            text += &format!(
+                "entry {} {} {} {}\n",
+                entry.guesses,
+                entry.duration.as_millis(),
+                entry.sequence,
+                entry.player
+            );
+        }
+    }
+    text
+}
+
+pub fn decode(text: &str) -> Result<Leaderboard, String> {
+    let mut leaderboard = Leaderboard::new();
+    let mut board = None;
+
+    for line in text.lines().filter(|line| !line.trim().is_empty()) {
+        let bad_entry = || format!("bad entry `{}`", line);
+        let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+        let fields = match key {
+            "version" => match value.trim().parse::<u32>() {
+                Ok(version) if version <= VERSION => continue,
+                _ => {
+                    return Err(format!(
+                        "unsupported leaderboard version `{}`",
+                        value
+                    ))
+                }
+            },
+            "board" => {
+                board = Some(value.trim());
+                continue;
+            }
+            "entry" => value.splitn(4, ' ').collect::<Vec<_>>(),
+            _ => return Err(format!("unexpected line `{}`", line)),
+        };
+        let board = board
+            .ok_or_else(|| format!("entry before any board: `{}`", line))?;
+        match fields[..] {
+            [guesses, millis, sequence, player] => {
+                let entry = Entry {
+                    player:   player.trim().to_owned(),
+                    guesses:  guesses.parse().map_err(|_| bad_entry())?,
+                    duration: Duration::from_millis(
+                        millis.parse().map_err(|_| bad_entry())?,
+                    ),
+                    sequence: sequence.parse().map_err(|_| bad_entry())?,
+                };
+                leaderboard.restore(board, entry);
+            }
+            _ => return Err(bad_entry()),
+        }
+    }
+    Ok(leaderboard)
+}
+
+#[cfg(test)]
+mod tests {
+    use mastermind::GameBuilder;
+
+    use super::*;
+
+    #[test]
+    fn leaderboards_round_trip() {
+        let mut game = GameBuilder::new().pegs(&[1, 2, 3, 4]).build();
+        game.guess(&[1, 2, 3, 4]).unwrap();
+
+        let mut leaderboard = Leaderboard::new();
+        assert_eq!(encode(&leaderboard), "version 1\n");
+        assert_eq!(decode(&encode(&leaderboard)), Ok(leaderboard.clone()));
+
+        let secs = Duration::from_secs;
+        leaderboard.submit("classic-4x6", "ann lee", &game, secs(30));
+        leaderboard.submit("classic-4x6", "bob", &game, secs(20));
+        leaderboard.submit("daily-20000", "ann lee", &game, secs(45));
+        // Variants registered by applications may have spaces in their names.
+        leaderboard.submit("office rules-4x6", "bob", &game, secs(25));
+        assert_eq!(decode(&encode(&leaderboard)), Ok(leaderboard));
+    }
+
+    #[test]
+    fn rejects_bad_lines() {
+        assert!(decode("board 4x6\nentry 4 fast 1 ann").is_err());
+        assert!(decode("board 4x6\nentry 4").is_err());
+        assert!(decode("entry 4 100 1 ann").is_err());
+        assert!(decode("score 4x6 4 100 1 ann").is_err());
+        assert!(decode("version 2\nboard 4x6").is_err());
+    }
+}
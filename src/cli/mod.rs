@@ -0,0 +1,649 @@
+mod analyze;
+mod args;
+mod bench;
+mod coop;
+mod gen;
+mod input;
+mod leaderboard;
+mod paths;
+mod play;
+mod profile;
+mod prompt;
+mod saves;
+mod selftest;
+mod share;
+mod solve;
+mod versus;
+mod watch;
+
+#[cfg(feature = "readline")]
+use std::io::IsTerminal;
+use std::{
+    fs,
+    io::{self, BufRead, Write},
+    time::{Duration, Instant},
+};
+
+use args::Args;
+#[cfg(feature = "serde")]
+use mastermind::exchange::PuzzlePack;
+use mastermind::{
+    analysis,
+    challenge::Challenge,
+    coop::Coop,
+    daily::Daily,
+    leaderboard::Leaderboard,
+    messages::{Catalog, English, Message},
+    solver::{Level, Solver},
+    tournament::Tournament,
+    transcript::Transcript,
+    Difficulty, Game, GameBuilder, Variant,
+};
+use saves::Save;
+
+const USAGE: &str = "\
+usage: mastermind <command> [options]
+
+commands:
+    play [--variant NAME] [--save SLOT] [--seed PHRASE]
+                                          start a new game
+    play --difficulty NAME | --challenge CODE [--save SLOT] [--seed PHRASE]
+                                          start a game that can be shared
+                                          as a challenge code
+    coop [--players N] [--variant NAME]   take turns at one secret with
+                                          friends at the same keyboard
+    resume SLOT                           carry on with a saved game
+    saves                                 list saved games in progress
+    share [SLOT]                          print the challenge code of a saved
+                                          game, or of the last game played
+    daily                                 play today's puzzle and keep a
+                                          daily streak
+    leaderboard [BOARD]                   list the best results on a board,
+                                          or the boards there are
+    solve --history FILE [--colors N]     list the secrets that fit a game's
+                                          history and suggest a next guess
+    analyze FILE [--format text|markdown|html]
+                                          report how good each guess of a
+                                          finished game was
+    gen [--kind puzzles|challenges] [--difficulty NAME] [--count N]
+        [--seed PHRASE] [--out FILE]      generate a puzzle pack or a list of
+                                          challenge codes
+    bench [--config PEGSxCOLORS] [--solvers NAME,...] [--games N]
+          [--seed PHRASE] [--parallel]    compare solver strategies over
+                                          every secret, or a sample of N;
+                                          solvers are knuth, entropy, random
+                                          and human, or the personalities
+                                          safe, aggressive and human-like;
+                                          --parallel uses every core (with
+                                          the rayon feature)
+    watch [--solver NAME] [--delay MS] [--config PEGSxCOLORS]
+          [--seed PHRASE]                 watch a solver play a random game
+    codemaker [--level beginner|casual|expert] [--solver NAME] [--delay MS]
+                                          pick a secret for the computer to
+                                          crack
+    --json                                play one game at a time through
+                                          JSON commands (new, guess, state,
+                                          forfeit), one per line, on stdin
+                                          and stdout
+    rpc                                   answer JSON-RPC 2.0 requests, one
+                                          per line, on stdin and stdout
+    versus [--codemaker adaptive|hardest] [--config PEGSxCOLORS]
+           [--solvers NAME,...]           pit solvers against a codemaker
+                                          that makes them work hardest
+    selftest                              check feedback and the solvers
+                                          against known bounds for the
+                                          classic 4x6 game
+
+files:
+    Profiles, saves and the leaderboard go in the platform's data directory,
+    and the last challenge played in its cache directory, or all of them in
+    ~/.mastermind if it exists. $MASTERMIND_DATA_DIR and $MASTERMIND_CACHE_DIR
+    move them.
+";
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let args = Args::parse(args);
+
+    match args.command() {
+        Some("play") => play(&args),
+        Some("resume") => resume(&args),
+        Some("coop") => coop(&args),
+        Some("saves") => list_saves(),
+        Some("share") => share(&args),
+        Some("daily") => daily(),
+        Some("leaderboard") => show_leaderboard(&args),
+        Some("solve") => solve(&args),
+        Some("analyze") => analyze(&args),
+        Some("gen") => generate(&args),
+        Some("bench") => bench(&args),
+        Some("watch") => watch(&args),
+        Some("versus") => versus(&args),
+        Some("codemaker") => codemaker(&args),
+        Some("rpc") => rpc(),
+        Some("selftest") => self_test(),
+        None if args.option("json").is_some() => json_lines(),
+        Some("help") | None => {
+            print!("{}", USAGE);
+            Ok(())
+        }
+        Some(command) => {
+            Err(format!("unknown command `{}`\n\n{}", command, USAGE))
+        }
+    }
+}
+
+fn variant(name: &str) -> Result<Variant, String> {
+    Variant::from_name(name)
+        .ok_or_else(|| English.message(&Message::UnknownVariant(name)))
+}
+
+fn variant_builder(variant: Variant) -> Result<GameBuilder, String> {
+    GameBuilder::try_from(variant)
+        .map_err(|e| English.message(&Message::InvalidGame(&e)))
+}
+
+// The builder for a saved game's variant, or for its difficulty if it was
+// started from one.
+fn saved_builder(name: &str) -> Result<GameBuilder, String> {
+    match Difficulty::from_name(name) {
+        Some(difficulty) => Ok(difficulty.into()),
+        None => variant_builder(variant(name)?),
+    }
+}
+
+// The challenge to play, if the game should be shareable.
+fn challenge(args: &Args) -> Result<Option<Challenge>, String> {
+    if let Some(code) = args.option("challenge") {
+        let challenge =
+            code.parse().map_err(|e| format!("`{}` is {}", code, e))?;
+        return Ok(Some(challenge));
+    }
+
+    let Some(name) = args.option("difficulty")
+    else {
+        return Ok(None);
+    };
+    let difficulty = Difficulty::from_name(name)
+        .ok_or_else(|| format!("unknown difficulty `{}`", name))?;
+    let base = args
+        .option("seed")
+        .map(|phrase| GameBuilder::new().seed_phrase(phrase).seed.unwrap());
+    let seed = gen::seeds(base).next().unwrap();
+    Ok(Some(Challenge::new(difficulty, seed)))
+}
+
+fn play(args: &Args) -> Result<(), String> {
+    let (name, builder) = match challenge(args)? {
+        Some(challenge) => {
+            println!("Challenge code: {}", challenge);
+            share::remember(&challenge);
+            (challenge.difficulty.name().to_owned(), challenge.builder())
+        }
+        None => {
+            let variant = variant(args.option("variant").unwrap_or("classic"))?;
+            let name = variant.name().to_owned();
+            let mut builder = variant_builder(variant)?;
+            if let Some(phrase) = args.option("seed") {
+                builder = builder.seed_phrase(phrase);
+            }
+            (name, builder)
+        }
+    };
+
+    let started = Instant::now();
+    let game = match args.option("save") {
+        Some(slot) => {
+            let slot = saves::Slot::new(slot)?;
+            let mut game = slot.autosave(builder, &name).build();
+            play_interactively(&mut game)?;
+            slot.finish(&game)?;
+            game
+        }
+        None => {
+            let mut game = builder.build();
+            play_interactively(&mut game)?;
+            game
+        }
+    };
+
+    let board = format!("{}-{}", name, Leaderboard::board_for(&game));
+    let player = profile::load()?.name;
+    leaderboard::submit(&board, &player, &game, started.elapsed())
+}
+
+fn coop(args: &Args) -> Result<(), String> {
+    let players = match args.option("players") {
+        Some(players) => players
+            .parse()
+            .ok()
+            .filter(|&players| players > 0)
+            .ok_or_else(|| {
+                format!("`{}` is not a number of players", players)
+            })?,
+        None => 2,
+    };
+    let variant = variant(args.option("variant").unwrap_or("classic"))?;
+    let mut coop = Coop::new(variant_builder(variant)?, players);
+
+    let mut lines = io::stdin().lock().lines();
+    coop::play(&mut coop, &English, &mut lines, io::stdout())
+        .map_err(|e| e.to_string())
+}
+
+fn resume(args: &Args) -> Result<(), String> {
+    let slot = args.positional(0).ok_or("resume needs a save slot name")?;
+    let slot = saves::Slot::new(slot)?;
+    let Save {
+        variant: name,
+        checkpoint,
+    } = slot.read()?;
+
+    let builder = saved_builder(&name)?;
+    let mut game = slot.autosave(builder, &name).resume(&checkpoint);
+
+    play_interactively(&mut game)?;
+    slot.finish(&game)
+}
+
+fn daily() -> Result<(), String> {
+    let mut profile = profile::load()?;
+    let daily = Daily::today();
+    if profile.daily.has_played(daily) {
+        println!(
+            "You've already played today's puzzle (#{}). Streak: {}",
+            daily.number(),
+            profile.daily.current
+        );
+        return Ok(());
+    }
+
+    println!("Daily puzzle #{} for {}", daily.number(), daily.date());
+    let started = Instant::now();
+    let mut game = daily.builder().build();
+    play_interactively(&mut game)?;
+    let duration = started.elapsed();
+    if !game.is_over() {
+        return Ok(());
+    }
+
+    share::remember(&daily.challenge());
+    profile.record(&game);
+    profile.daily.record(daily, game.is_solved());
+    profile::store(&profile)?;
+
+    print!("\n{}", daily.share(&game));
+    println!(
+        "Streak: {} (best {})",
+        profile.daily.current, profile.daily.best
+    );
+    let board = format!("daily-{}", daily.number());
+    leaderboard::submit(&board, &profile.name, &game, duration)
+}
+
+fn show_leaderboard(args: &Args) -> Result<(), String> {
+    let leaderboard = leaderboard::load()?;
+    let board = match args.positional(0) {
+        Some(board) => board,
+        None => {
+            let mut boards = leaderboard.boards().collect::<Vec<_>>();
+            boards.sort_unstable();
+            match boards.is_empty() {
+                true => println!("No results yet."),
+                false => println!("{}", boards.join("\n")),
+            }
+            return Ok(());
+        }
+    };
+
+    for (i, entry) in leaderboard.top(board, 10).iter().enumerate() {
+        println!(
+            "{:>2}. {} - {} guesses in {:.1}s",
+            i + 1,
+            entry.player,
+            entry.guesses,
+            entry.duration.as_secs_f64()
+        );
+    }
+    Ok(())
+}
+
+fn share(args: &Args) -> Result<(), String> {
+    let challenge = match args.positional(0) {
+        Some(slot) => share::from_save(&saves::Slot::new(slot)?.read()?)
+            .ok_or_else(|| {
+                format!(
+                    "`{}` wasn't started from a difficulty or challenge, so \
+                     it has no code",
+                    slot
+                )
+            })?,
+        None => share::last().ok_or(
+            "no shareable game played yet; start one with `play \
+             --difficulty NAME`",
+        )?,
+    };
+    println!("{}", challenge);
+    Ok(())
+}
+
+// Plays on stdin and stdout, with line editing and history when stdin is a
+// terminal and the `readline` feature is enabled.
+fn play_interactively(game: &mut Game) -> Result<(), String> {
+    #[cfg(feature = "readline")]
+    if io::stdin().is_terminal() {
+        let mut prompt = prompt::Readline::new(game)?;
+        return play::play(game, &English, &mut prompt, io::stdout())
+            .map_err(|e| e.to_string());
+    }
+
+    let mut lines = io::stdin().lock().lines();
+    play::play(game, &English, &mut lines, io::stdout())
+        .map_err(|e| e.to_string())
+}
+
+fn solve(args: &Args) -> Result<(), String> {
+    let path = args.option("history").ok_or("solve needs --history FILE")?;
+    let history = fs::read_to_string(path)
+        .map_err(|e| format!("couldn't read {}: {}", path, e))?;
+    let colors = match args.option("colors") {
+        Some(colors) => colors
+            .parse()
+            .map_err(|_| format!("`{}` is not a number of colors", colors))?,
+        None => 6,
+    };
+
+    solve::solve(&history, colors, io::stdout()).map_err(|e| e.to_string())
+}
+
+fn analyze(args: &Args) -> Result<(), String> {
+    let path = args.positional(0).ok_or("analyze needs a game file")?;
+    let format = args.option("format").unwrap_or("text");
+    let format = analyze::Format::from_name(format)
+        .ok_or_else(|| format!("unknown format `{}`", format))?;
+
+    let game = load_game(path)?;
+    analyze::write_report(&analysis::analyze(&game), format, io::stdout())
+        .map_err(|e| e.to_string())
+}
+
+fn generate(args: &Args) -> Result<(), String> {
+    let name = args.option("difficulty").unwrap_or("medium");
+    let difficulty = Difficulty::from_name(name)
+        .ok_or_else(|| format!("unknown difficulty `{}`", name))?;
+    let count = match args.option("count") {
+        Some(count) => count
+            .parse()
+            .map_err(|_| format!("`{}` is not a count", count))?,
+        None => 10,
+    };
+    let base = args
+        .option("seed")
+        .map(|phrase| GameBuilder::new().seed_phrase(phrase).seed.unwrap());
+
+    let text = match args.option("kind").unwrap_or("puzzles") {
+        "challenges" => gen::challenges(difficulty, base, count)
+            .iter()
+            .map(|challenge| format!("{}\n", challenge))
+            .collect::<String>(),
+        #[cfg(feature = "serde")]
+        "puzzles" => {
+            let puzzles = gen::puzzles(difficulty, base, count);
+            PuzzlePack::to_json(&puzzles).map_err(|e| e.to_string())? + "\n"
+        }
+        #[cfg(not(feature = "serde"))]
+        "puzzles" => {
+            return Err("puzzle packs need the `serde` feature".to_owned())
+        }
+        kind => return Err(format!("unknown kind `{}`", kind)),
+    };
+
+    match args.option("out") {
+        Some(path) => fs::write(path, text)
+            .map_err(|e| format!("couldn't write {}: {}", path, e)),
+        None => {
+            print!("{}", text);
+            Ok(())
+        }
+    }
+}
+
+fn bench(args: &Args) -> Result<(), String> {
+    let config = args.option("config").unwrap_or("4x6");
+    let (pegs, colors) = bench::parse_config(config)
+        .ok_or_else(|| format!("`{}` is not a config like 4x6", config))?;
+    let mut tournament = Tournament::new().config(pegs, colors);
+    if let Some(solvers) = args.option("solvers") {
+        tournament = tournament.strategies(&bench::parse_solvers(solvers)?);
+    }
+    if let Some(games) = args.option("games") {
+        let games = games
+            .parse()
+            .map_err(|_| format!("`{}` is not a number of games", games))?;
+        tournament = tournament.games(games);
+    }
+    if let Some(phrase) = args.option("seed") {
+        let seed = GameBuilder::new().seed_phrase(phrase).seed.unwrap();
+        tournament = tournament.seed(seed);
+    }
+    #[cfg(feature = "rayon")]
+    if args.option("parallel").is_some() {
+        tournament = tournament.parallel(true);
+    }
+
+    let standings = tournament.run().map_err(|e| e.to_string())?;
+    bench::write_table(&standings, io::stdout()).map_err(|e| e.to_string())
+}
+
+fn watch(args: &Args) -> Result<(), String> {
+    let strategy =
+        bench::parse_solver(args.option("solver").unwrap_or("knuth"))?;
+    let delay = match args.option("delay") {
+        Some(delay) => delay.parse().map_err(|_| {
+            format!("`{}` is not a delay in milliseconds", delay)
+        })?,
+        None => 1000,
+    };
+
+    let mut builder = GameBuilder::new();
+    if let Some(config) = args.option("config") {
+        let (pegs, colors) = bench::parse_config(config)
+            .ok_or_else(|| format!("`{}` is not a config like 4x6", config))?;
+        builder = builder.peg_count(pegs).peg_range(colors);
+    }
+    if let Some(phrase) = args.option("seed") {
+        builder = builder.seed_phrase(phrase);
+    }
+
+    let mut game = builder.build();
+    let solver = Solver::new(&game).strategy(strategy);
+    watch::watch(
+        &mut game,
+        solver,
+        Duration::from_millis(delay),
+        &English,
+        io::stdout(),
+    )
+    .map_err(|e| e.to_string())
+}
+
+fn codemaker(args: &Args) -> Result<(), String> {
+    let name = args.option("level").unwrap_or("casual");
+    let level = Level::from_name(name)
+        .ok_or_else(|| format!("unknown level `{}`", name))?;
+    let strategy =
+        bench::parse_solver(args.option("solver").unwrap_or("knuth"))?;
+    let delay = match args.option("delay") {
+        Some(delay) => delay.parse().map_err(|_| {
+            format!("`{}` is not a delay in milliseconds", delay)
+        })?,
+        None => 1000,
+    };
+
+    // A throwaway game, for the shape of the secret.
+    let shape = Game::from(Difficulty::Medium);
+    let pegs = *shape.peg_counts().start();
+    let colors = shape.alphabet().len();
+    print!("Pick a secret of {} pegs from 0 to {}: ", pegs, colors - 1);
+    io::stdout().flush().map_err(|e| e.to_string())?;
+    let line = io::stdin()
+        .lock()
+        .lines()
+        .next()
+        .ok_or("no secret given")?
+        .map_err(|e| e.to_string())?;
+    let secret = input::parse_pegs(&line)
+        .map_err(|token| English.message(&Message::NotAPeg(&token)))?;
+    if secret.len() != pegs as usize
+        || !secret.iter().all(|&peg| shape.is_guessable(peg))
+    {
+        return Err(format!(
+            "a secret needs {} pegs from 0 to {}",
+            pegs,
+            colors - 1
+        ));
+    }
+
+    let mut game = GameBuilder::from(Difficulty::Medium).pegs(&secret).build();
+    let solver = Solver::new(&game).strategy(strategy).level(level);
+    watch::watch(
+        &mut game,
+        solver,
+        Duration::from_millis(delay),
+        &English,
+        io::stdout(),
+    )
+    .map_err(|e| e.to_string())?;
+
+    match game.is_solved() {
+        true => println!("The computer cracked your code."),
+        false => println!("Your code held. You win!"),
+    }
+    Ok(())
+}
+
+fn self_test() -> Result<(), String> {
+    let passed =
+        selftest::selftest(4, 6, &selftest::CLASSIC, io::stdout().lock())
+            .map_err(|e| e.to_string())?;
+    match passed {
+        true => Ok(()),
+        false => Err("self-test failed".to_owned()),
+    }
+}
+
+// Answers each non-blank line of stdin with `respond`, flushing as it goes so
+// the other end of a pipe sees every response straight away.
+#[cfg(feature = "serde")]
+fn serve(
+    mut respond: impl FnMut(&str) -> Option<String>,
+) -> Result<(), String> {
+    let mut stdout = io::stdout().lock();
+    for line in io::stdin().lock().lines() {
+        let line = line.map_err(|e| e.to_string())?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(response) = respond(&line) {
+            writeln!(stdout, "{}", response)
+                .and_then(|_| stdout.flush())
+                .map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "serde")]
+fn rpc() -> Result<(), String> {
+    let mut handler = mastermind::rpc::Handler::new();
+    serve(|line| handler.handle(line))
+}
+
+#[cfg(not(feature = "serde"))]
+fn rpc() -> Result<(), String> {
+    Err("the rpc command needs the `serde` feature".to_owned())
+}
+
+#[cfg(feature = "serde")]
+fn json_lines() -> Result<(), String> {
+    let mut session = mastermind::pipe::Session::new();
+    serve(|line| Some(session.handle(line)))
+}
+
+#[cfg(not(feature = "serde"))]
+fn json_lines() -> Result<(), String> {
+    Err("--json needs the `serde` feature".to_owned())
+}
+
+fn versus(args: &Args) -> Result<(), String> {
+    let name = args.option("codemaker").unwrap_or("adaptive");
+    let codemaker = versus::Codemaker::from_name(name)
+        .ok_or_else(|| format!("unknown codemaker `{}`", name))?;
+    let config = args.option("config").unwrap_or("4x6");
+    let (pegs, colors) = bench::parse_config(config)
+        .ok_or_else(|| format!("`{}` is not a config like 4x6", config))?;
+    let strategies = versus::strategies(args.option("solvers"))?;
+
+    let tournament = Tournament::new()
+        .config(pegs, colors)
+        .strategies(&strategies);
+    versus::versus(&tournament, codemaker, io::stdout())
+}
+
+// Reads a finished game from a save slot file, a JSON export or a plain-text
+// transcript.
+fn load_game(path: &str) -> Result<Game, String> {
+    let text = fs::read_to_string(path)
+        .map_err(|e| format!("couldn't read {}: {}", path, e))?;
+
+    #[cfg(feature = "serde")]
+    if text.trim_start().starts_with('{') {
+        return Game::from_json(&text).map_err(|e| e.to_string());
+    }
+    if let Ok(save) = Save::decode(&text) {
+        return Ok(saved_builder(&save.variant)?.resume(&save.checkpoint));
+    }
+    Transcript::parse(&text)
+        .to_game(GameBuilder::new())
+        .map_err(|e| e.to_string())
+}
+
+fn list_saves() -> Result<(), String> {
+    let saves = saves::list()?;
+    if saves.is_empty() {
+        println!("{}", English.message(&Message::NoSavedGames));
+    }
+
+    for (slot, save) in saves {
+        println!(
+            "{:<16} {:<10} {} guesses made",
+            slot,
+            save.variant,
+            save.checkpoint.guesses.len()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loads_saves_of_difficulty_games() {
+        let mut game = GameBuilder::from(Difficulty::Hard).build();
+        game.guess(&[0; 5]).unwrap();
+        let save = Save {
+            variant:    "hard".to_owned(),
+            checkpoint: game.checkpoint(),
+        };
+        let path = std::env::temp_dir()
+            .join(format!("mastermind-load-{}.save", std::process::id()));
+        fs::write(&path, save.encode()).unwrap();
+
+        let loaded = load_game(path.to_str().unwrap());
+        fs::remove_file(&path).unwrap();
+        let loaded = loaded.unwrap();
+        assert_eq!(loaded.pegs(), game.pegs());
+        assert_eq!(loaded.guesses(), game.guesses());
+    }
+}
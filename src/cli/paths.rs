@@ -0,0 +1,196 @@
+use std::{
+    env,
+    ffi::OsString,
+    path::{Path, PathBuf},
+};
+
+const APP: &str = "mastermind";
+
+// Where the CLI keeps its files: profiles and saves in the platform's data
+// directory, and things that can safely be lost in its cache directory. Both
+// can be moved with `$MASTERMIND_DATA_DIR` and `$MASTERMIND_CACHE_DIR`.
+// Players who already have a `~/.mastermind` from an older version keep using
+// it for everything.
+#[derive(Debug, PartialEq)]
+struct Dirs {
+    data:  PathBuf,
+    cache: PathBuf,
+}
+
+impl Dirs {
+    fn from_env() -> Self {
+        Self::resolve(|name| env::var_os(name), Path::is_dir)
+    }
+
+    fn resolve<V, E>(var: V, exists: E) -> Self
+    where
+        V: Fn(&str) -> Option<OsString>,
+        E: Fn(&Path) -> bool,
+    {
+        let home = var("HOME")
+            .or_else(|| var("USERPROFILE"))
+            .map(PathBuf::from)
+            .unwrap_or_default();
+        let legacy = home.join(".mastermind");
+
+        let Self { data, cache } = if exists(&legacy) {
+            Self {
+                data:  legacy.clone(),
+                cache: legacy,
+            }
+        }
+        else {
+            Self::platform(&var, &home)
+        };
+
+        Self {
+            data:  var("MASTERMIND_DATA_DIR").map_or(data, PathBuf::from),
+            cache: var("MASTERMIND_CACHE_DIR").map_or(cache, PathBuf::from),
+        }
+    }
+
+    #[cfg(windows)]
+    fn platform(var: impl Fn(&str) -> Option<OsString>, home: &Path) -> Self {
+        let app_data = |name, fallback| {
+            var(name)
+                .map_or_else(
+                    || home.join("AppData").join(fallback),
+                    PathBuf::from,
+                )
+                .join(APP)
+        };
+
+        Self {
+            data:  app_data("APPDATA", "Roaming"),
+            cache: app_data("LOCALAPPDATA", "Local"),
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    fn platform(_: impl Fn(&str) -> Option<OsString>, home: &Path) -> Self {
+        let library = home.join("Library");
+
+        Self {
+            data:  library.join("Application Support").join(APP),
+            cache: library.join("Caches").join(APP),
+        }
+    }
+
+    // Anything else is assumed to follow the XDG base directory spec, which
+    // says relative paths in its variables are to be ignored.
+    #[cfg(not(any(windows, target_os = "macos")))]
+    fn platform(var: impl Fn(&str) -> Option<OsString>, home: &Path) -> Self {
+        let xdg = |name, fallback: &str| {
+            var(name)
+                .map(PathBuf::from)
+                .filter(|path| path.is_absolute())
+                .unwrap_or_else(|| home.join(fallback))
+                .join(APP)
+        };
+
+        Self {
+            data:  xdg("XDG_DATA_HOME", ".local/share"),
+            cache: xdg("XDG_CACHE_HOME", ".cache"),
+        }
+    }
+}
+
+fn overridden(name: &str, default: impl FnOnce() -> PathBuf) -> PathBuf {
+    env::var_os(name).map_or_else(default, PathBuf::from)
+}
+
+// Saves live in `$MASTERMIND_SAVE_DIR`, or `saves` in the data directory.
+pub fn saves() -> PathBuf {
+    overridden("MASTERMIND_SAVE_DIR", || {
+        Dirs::from_env().data.join("saves")
+    })
+}
+
+// The profile, stats included, lives in `$MASTERMIND_PROFILE`, or `profile`
+// in the data directory.
+pub fn profile() -> PathBuf {
+    overridden("MASTERMIND_PROFILE", || {
+        Dirs::from_env().data.join("profile")
+    })
+}
+
+// Personal bests live in `$MASTERMIND_LEADERBOARD`, or `leaderboard` in the
+// data directory.
+pub fn leaderboard() -> PathBuf {
+    overridden("MASTERMIND_LEADERBOARD", || {
+        Dirs::from_env().data.join("leaderboard")
+    })
+}
+
+// The code of the last shareable game lives in `$MASTERMIND_LAST_CHALLENGE`,
+// or `last-challenge` in the cache directory.
+pub fn last_challenge() -> PathBuf {
+    overridden("MASTERMIND_LAST_CHALLENGE", || {
+        Dirs::from_env().cache.join("last-challenge")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn resolve(vars: &[(&str, &str)], legacy: bool) -> Dirs {
+        let vars: HashMap<_, _> = vars.iter().copied().collect();
+        Dirs::resolve(|name| vars.get(name).map(OsString::from), |_| legacy)
+    }
+
+    #[test]
+    #[cfg(not(any(windows, target_os = "macos")))]
+    fn xdg_directories_are_used() {
+        assert_eq!(
+            resolve(&[("HOME", "/home/ada")], false),
+            Dirs {
+                data:  "/home/ada/.local/share/mastermind".into(),
+                cache: "/home/ada/.cache/mastermind".into(),
+            }
+        );
+
+        let vars = [
+            ("HOME", "/home/ada"),
+            ("XDG_DATA_HOME", "/data"),
+            ("XDG_CACHE_HOME", "relative"),
+        ];
+        assert_eq!(
+            resolve(&vars, false),
+            Dirs {
+                data:  "/data/mastermind".into(),
+                cache: "/home/ada/.cache/mastermind".into(),
+            }
+        );
+    }
+
+    #[test]
+    fn an_existing_legacy_directory_is_kept() {
+        let legacy = PathBuf::from("/home/ada/.mastermind");
+        assert_eq!(
+            resolve(&[("HOME", "/home/ada")], true),
+            Dirs {
+                data:  legacy.clone(),
+                cache: legacy,
+            }
+        );
+    }
+
+    #[test]
+    fn directories_can_be_overridden() {
+        let vars = [
+            ("HOME", "/home/ada"),
+            ("MASTERMIND_DATA_DIR", "/srv/data"),
+            ("MASTERMIND_CACHE_DIR", "/tmp/cache"),
+        ];
+        assert_eq!(
+            resolve(&vars, true),
+            Dirs {
+                data:  "/srv/data".into(),
+                cache: "/tmp/cache".into(),
+            }
+        );
+    }
+}
@@ -0,0 +1,201 @@
+use std::io::{self, Write};
+
+use mastermind::{
+    messages::{Catalog, Message},
+    FeedbackMode, Game, GuessError,
+};
+
+use super::{
+    input::{format_pegs, parse_pegs},
+    prompt::Prompt,
+};
+
+// Everything the game tells the codebreaker about the guess at `index`: the
+// usual score (or which positions are right, for positional feedback), then
+// how the secret's length compares for variable-length games, then any
+// arithmetic hints.
+pub fn feedback(game: &Game, index: usize, catalog: &dyn Catalog) -> String {
+    let mut parts = Vec::new();
+    match game.rules().feedback_mode {
+        FeedbackMode::Positional => {
+            let mask = game.mask(index).unwrap_or_default();
+            parts.push(catalog.message(&Message::Positions(&mask)));
+        }
+        _ => {
+            let (hits, near_hits) = game.hits(index).unwrap_or_default();
+            parts.push(catalog.message(&Message::Feedback { hits, near_hits }));
+        }
+    }
+    if game.is_variable_length() {
+        if let Some(ordering) = game.length_hint(index) {
+            parts.push(catalog.message(&Message::Length(ordering)));
+        }
+    }
+    for hint in game.arithmetic_hints(index).unwrap_or_default() {
+        parts.push(catalog.message(&Message::Arithmetic(hint)));
+    }
+    parts.join("; ")
+}
+
+pub fn play(
+    game: &mut Game,
+    catalog: &dyn Catalog,
+    prompt: &mut impl Prompt,
+    mut output: impl Write,
+) -> io::Result<()> {
+    for (i, guess) in game.guesses().iter().enumerate() {
+        writeln!(
+            output,
+            "{:>3}: {}  {}",
+            i + 1,
+            format_pegs(guess),
+            feedback(game, i, catalog)
+        )?;
+    }
+
+    while !game.is_over() {
+        let message = Message::Prompt {
+            guess:       game.guesses().len() + 1,
+            max_guesses: game.max_guesses(),
+        };
+        let line =
+            match prompt.read_line(&catalog.message(&message), &mut output)? {
+                Some(line) => line,
+                None => return Ok(()),
+            };
+
+        let guess = match parse_pegs(&line) {
+            Ok(guess) => guess,
+            Err(token) => {
+                writeln!(
+                    output,
+                    "{}",
+                    catalog.message(&Message::NotAPeg(&token))
+                )?;
+                continue;
+            }
+        };
+
+        // Catch pegs the game can't contain before spending a guess on them.
+        if let Some(&peg) = guess.iter().find(|&&peg| !game.is_guessable(peg)) {
+            writeln!(
+                output,
+                "{}",
+                catalog.message(&Message::NotInAlphabet(peg))
+            )?;
+            continue;
+        }
+
+        match game.guess(&guess) {
+            Ok(_) => {
+                let index = game.guesses().len() - 1;
+                writeln!(output, "  {}", feedback(game, index, catalog))?
+            }
+            Err(GuessError::NoGuessesLeft | GuessError::GameOver) => break,
+            Err(e) => writeln!(output, "{}", catalog.message(&(&e).into()))?,
+        }
+    }
+
+    let code = format_pegs(game.pegs());
+    writeln!(output, "{}", catalog.message(&ending(game, &code)))
+}
+
+// How a finished game ended, with `code` as the secret to show.
+pub fn ending<'a>(game: &Game, code: &'a str) -> Message<'a> {
+    if game.is_solved() {
+        Message::Solved {
+            guesses: game.guesses().len(),
+        }
+    }
+    else if game.is_forfeited() {
+        Message::Forfeited { code }
+    }
+    else {
+        Message::OutOfGuesses { code }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufRead;
+
+    use mastermind::{messages::English, GameBuilder};
+
+    use super::*;
+
+    fn run(game: &mut Game, input: &str) -> String {
+        let mut output = Vec::new();
+        play(game, &English, &mut input.as_bytes().lines(), &mut output)
+            .unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn plays_until_solved() {
+        let mut game = GameBuilder::new().pegs(&[1, 2, 3, 4]).build();
+        let output = run(
+            &mut game,
+            "1243\nnonsense\n123\n1239\nblue red green yellow\n1234\n",
+        );
+
+        assert!(output.contains("2 exact, 2 close"));
+        assert!(output.contains("`n` is not a peg"));
+        assert!(output.contains("wrong length"));
+        assert!(output.contains("There is no peg 9 in this game."));
+        assert!(output.contains("  0 exact, 3 close\n"));
+        assert!(output.ends_with("You cracked it in 3 guesses!\n"));
+    }
+
+    #[test]
+    fn reports_the_code_when_out_of_guesses() {
+        let mut game = GameBuilder::new()
+            .pegs(&[1, 2, 3, 4])
+            .max_guesses(1)
+            .build();
+        let output = run(&mut game, "0000\n");
+        assert!(output.ends_with("Out of guesses! The code was 1 2 3 4.\n"));
+    }
+
+    #[test]
+    fn reports_forfeited_games() {
+        let mut game = GameBuilder::new().pegs(&[1, 2, 3, 4]).build();
+        game.forfeit();
+        let output = run(&mut game, "");
+        assert_eq!(output, "You gave up. The code was 1 2 3 4.\n");
+    }
+
+    #[test]
+    fn shows_every_kind_of_feedback() {
+        let mut game = GameBuilder::try_from(mastermind::Variant::Number)
+            .unwrap()
+            .pegs(&[1, 2, 3, 4])
+            .build();
+        let output = run(&mut game, "1243\n");
+        assert!(output.contains(
+            "  2 exact, 2 close; the digits add up to an even number; the \
+             code is lower\n"
+        ));
+
+        let mut game = GameBuilder::new()
+            .pegs(&[1, 2, 3, 4])
+            .feedback_mode(FeedbackMode::Positional)
+            .build();
+        let output = run(&mut game, "1243\n5555\n");
+        assert!(output.contains("  right place: 1 2\n"));
+        assert!(output.contains("  nothing in the right place\n"));
+
+        let mut game = GameBuilder::new()
+            .peg_count_range(3, 5)
+            .pegs(&[1, 2, 3, 4])
+            .build();
+        let output = run(&mut game, "123\n");
+        assert!(output.contains("  3 exact, 0 close; the code is longer\n"));
+    }
+
+    #[test]
+    fn stops_at_end_of_input() {
+        let mut game = GameBuilder::new().pegs(&[1, 2, 3, 4]).build();
+        let output = run(&mut game, "0000\n");
+        assert!(output.ends_with("Guess 2/12: "));
+    }
+}
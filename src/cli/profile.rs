@@ -0,0 +1,131 @@
+use std::{env, fs};
+
+use mastermind::profile::Profile;
+
+use super::paths;
+
+// The saved profile, or a new one named after the user if there isn't one.
+pub fn load() -> Result<Profile, String> {
+    match fs::read_to_string(paths::profile()) {
+        Ok(text) => decode(&text),
+        Err(_) => {
+            let name = env::var("USER").unwrap_or_else(|_| "player".into());
+            Ok(Profile::new(&name))
+        }
+    }
+}
+
+pub fn store(profile: &Profile) -> Result<(), String> {
+    let path = paths::profile();
+    path.parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|_| fs::write(&path, encode(profile)))
+        .map_err(|e| format!("couldn't save profile: {}", e))
+}
+
+// The same line-based format as saves:
+//
+//     name ann
+//     variant classic
+//     stats 12 9 3 5
+//     achievements win-in-3 streak-10
+//     rating 1016
+//     daily 20377 3 5
+//
+// `stats` is games played, won, the current and the best streak. `daily` is
+// the last day a daily puzzle was played, then the current and best streaks.
+pub fn encode(profile: &Profile) -> String {
+    let stats = profile.stats;
+    let mut text = format!("name {}\n", profile.name);
+    text += &format!("variant {}\n", profile.settings.variant);
+    text += &format!(
+        "stats {} {} {} {}\n",
+        stats.played, stats.won, stats.current, stats.best
+    );
+    let achievements =
+        format!("achievements {}", profile.achievements.join(" "));
+    text += achievements.trim_end();
+    text += "\n";
+    text += &format!("rating {}\n", profile.rating);
+    if let Some(last_day) = profile.daily.last_day {
+        text += &format!(
+            "daily {} {} {}\n",
+            last_day, profile.daily.current, profile.daily.best
+        );
+    }
+    text
+}
+
+pub fn decode(text: &str) -> Result<Profile, String> {
+    let mut profile = Profile::new("");
+
+    for line in text.lines().filter(|line| !line.trim().is_empty()) {
+        let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+        let numbers = || {
+            value
+                .split_whitespace()
+                .map(|n| n.parse().map_err(|_| format!("bad number `{}`", n)))
+                .collect::<Result<Vec<u64>, _>>()
+        };
+        match key {
+            "name" => profile.name = value.trim().to_owned(),
+            "variant" => profile.settings.variant = value.trim().to_owned(),
+            "stats" => match numbers()?[..] {
+                [played, won, current, best] => {
+                    profile.stats.played = played as u32;
+                    profile.stats.won = won as u32;
+                    profile.stats.current = current as u32;
+                    profile.stats.best = best as u32;
+                }
+                _ => return Err(format!("bad stats `{}`", value)),
+            },
+            "achievements" => {
+                profile.achievements =
+                    value.split_whitespace().map(String::from).collect()
+            }
+            "rating" => {
+                profile.rating = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("bad rating `{}`", value))?
+            }
+            "daily" => match numbers()?[..] {
+                [last_day, current, best] => {
+                    profile.daily.last_day = Some(last_day);
+                    profile.daily.current = current as u32;
+                    profile.daily.best = best as u32;
+                }
+                _ => return Err(format!("bad daily streak `{}`", value)),
+            },
+            _ => return Err(format!("unexpected line `{}`", line)),
+        }
+    }
+    Ok(profile)
+}
+
+#[cfg(test)]
+mod tests {
+    use mastermind::{daily::Daily, GameBuilder};
+
+    use super::*;
+
+    #[test]
+    fn profiles_round_trip() {
+        let mut profile = Profile::new("ann");
+        assert_eq!(decode(&encode(&profile)), Ok(profile.clone()));
+
+        let mut game = GameBuilder::new().pegs(&[1, 2, 3, 4]).build();
+        game.guess(&[1, 2, 3, 4]).unwrap();
+        profile.record(&game);
+        profile.daily.record(Daily::on(20_000), true);
+        profile.update_rating(1200.0, 1.0);
+        assert_eq!(decode(&encode(&profile)), Ok(profile));
+    }
+
+    #[test]
+    fn rejects_bad_lines() {
+        assert!(decode("stats 1 2").is_err());
+        assert!(decode("rating high").is_err());
+        assert!(decode("colour blue").is_err());
+    }
+}
@@ -0,0 +1,147 @@
+use std::io::{self, BufRead, Write};
+
+#[cfg(feature = "readline")]
+pub use readline::Readline;
+
+pub trait Prompt {
+    // Shows `prompt` and reads the next line, or `None` at the end of input.
+    fn read_line(
+        &mut self,
+        prompt: &str,
+        output: &mut dyn Write,
+    ) -> io::Result<Option<String>>;
+}
+
+impl<R: BufRead> Prompt for io::Lines<R> {
+    fn read_line(
+        &mut self,
+        prompt: &str,
+        output: &mut dyn Write,
+    ) -> io::Result<Option<String>> {
+        write!(output, "{}", prompt)?;
+        output.flush()?;
+        self.next().transpose()
+    }
+}
+
+#[cfg(feature = "readline")]
+mod readline {
+    use std::io::{self, Write};
+
+    use mastermind::{render::Description, Game};
+    use rustyline::{
+        completion::Completer, error::ReadlineError, highlight::Highlighter,
+        hint::Hinter, history::DefaultHistory, validate::Validator, Context,
+        Editor, Helper,
+    };
+
+    use super::Prompt;
+
+    // An interactive prompt with line editing, a history of previous guesses,
+    // and tab completion of the game's color names.
+    pub struct Readline {
+        editor: Editor<ColorNames, DefaultHistory>,
+    }
+
+    impl Readline {
+        pub fn new(game: &Game) -> Result<Self, String> {
+            let mut editor = Editor::new().map_err(|e| e.to_string())?;
+            editor.set_helper(Some(ColorNames::new(game)));
+            Ok(Self { editor })
+        }
+    }
+
+    impl Prompt for Readline {
+        fn read_line(
+            &mut self,
+            prompt: &str,
+            _: &mut dyn Write,
+        ) -> io::Result<Option<String>> {
+            match self.editor.readline(prompt) {
+                Ok(line) => {
+                    if !line.trim().is_empty() {
+                        let _ = self.editor.add_history_entry(line.as_str());
+                    }
+                    Ok(Some(line))
+                }
+                Err(ReadlineError::Eof | ReadlineError::Interrupted) => {
+                    Ok(None)
+                }
+                Err(ReadlineError::Io(e)) => Err(e),
+                Err(e) => Err(io::Error::other(e)),
+            }
+        }
+    }
+
+    struct ColorNames(Vec<String>);
+
+    impl ColorNames {
+        fn new(game: &Game) -> Self {
+            let description = Description::default();
+            Self(
+                game.alphabet()
+                    .iter()
+                    .map(|&peg| description.name(peg))
+                    .collect(),
+            )
+        }
+
+        fn complete_word(
+            &self,
+            line: &str,
+            pos: usize,
+        ) -> (usize, Vec<String>) {
+            let start = line[..pos]
+                .rfind(|c: char| c.is_whitespace() || c == ',')
+                .map_or(0, |i| i + 1);
+            let word = line[start..pos].to_lowercase();
+
+            let candidates = self
+                .0
+                .iter()
+                .filter(|name| name.starts_with(&word))
+                .cloned()
+                .collect();
+            (start, candidates)
+        }
+    }
+
+    impl Completer for ColorNames {
+        type Candidate = String;
+
+        fn complete(
+            &self,
+            line: &str,
+            pos: usize,
+            _: &Context<'_>,
+        ) -> rustyline::Result<(usize, Vec<String>)> {
+            Ok(self.complete_word(line, pos))
+        }
+    }
+
+    impl Hinter for ColorNames {
+        type Hint = String;
+    }
+
+    impl Highlighter for ColorNames {}
+    impl Validator for ColorNames {}
+    impl Helper for ColorNames {}
+
+    #[cfg(test)]
+    mod tests {
+        use mastermind::GameBuilder;
+
+        use super::*;
+
+        #[test]
+        fn completes_color_names() {
+            let names = ColorNames::new(&GameBuilder::new().build());
+            assert_eq!(
+                names.complete_word("red, b", 6),
+                (5, vec!["blue".to_owned()])
+            );
+            assert_eq!(names.complete_word("p", 1).1, ["purple"]);
+            assert!(names.complete_word("pink", 4).1.is_empty());
+        }
+    }
+}
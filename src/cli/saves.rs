@@ -0,0 +1,273 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use mastermind::{game::Checkpoint, Game, GameBuilder};
+
+use super::{
+    input::{format_pegs, parse_pegs},
+    paths,
+};
+
+const EXTENSION: &str = "save";
+// Bump when the save format changes, and teach `Save::decode` to read the
+// older version.
+const VERSION: u32 = 3;
+
+#[derive(Debug, PartialEq)]
+pub struct Save {
+    pub variant:    String,
+    pub checkpoint: Checkpoint,
+}
+
+impl Save {
+    // A simple line-based format, one field per line:
+    //
+    //     version 3
+    //     variant classic
+    //     pegs 1 2 3 4
+    //     revealed 0
+    //     guess 0 0 1 1
+    //     forfeited
+    //
+    // Seeded games have a `seed` line in place of `pegs` and `revealed`, and
+    // only forfeited games have a `forfeited` line. Version 1 had no seeds,
+    // and version 2 didn't record forfeits.
+    pub fn encode(&self) -> String {
+        let mut text = format!("version {}\n", VERSION);
+        text += &format!("variant {}\n", self.variant);
+        match self.checkpoint.seed {
+            Some(seed) => text += &format!("seed {}\n", seed),
+            None => {
+                let pegs = format_pegs(&self.checkpoint.pegs);
+                let revealed =
+                    self.checkpoint.revealed.iter().map(|i| i.to_string());
+                text += &format!("pegs {}\n", pegs);
+                text += &format!(
+                    "revealed {}\n",
+                    revealed.collect::<Vec<_>>().join(" ")
+                );
+            }
+        }
+
+        for guess in &self.checkpoint.guesses {
+            text += &format!("guess {}\n", format_pegs(guess));
+        }
+        if self.checkpoint.forfeited {
+            text += "forfeited\n";
+        }
+        text
+    }
+
+    pub fn decode(text: &str) -> Result<Self, String> {
+        let mut variant = None;
+        let mut checkpoint = Checkpoint {
+            pegs:      Vec::new(),
+            revealed:  Vec::new(),
+            guesses:   Vec::new(),
+            hints:     Vec::new(),
+            seed:      None,
+            forfeited: false,
+        };
+
+        for line in text.lines().filter(|line| !line.trim().is_empty()) {
+            let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+            match key {
+                "version" => match value.trim().parse::<u32>() {
+                    Ok(version) if version <= VERSION => {}
+                    _ => {
+                        return Err(format!(
+                            "unsupported save version `{}`",
+                            value
+                        ))
+                    }
+                },
+                "variant" => variant = Some(value.trim().to_owned()),
+                "seed" => {
+                    checkpoint.seed = Some(
+                        value
+                            .trim()
+                            .parse()
+                            .map_err(|_| format!("bad seed `{}`", value))?,
+                    )
+                }
+                "pegs" => checkpoint.pegs = parse_pegs(value)?,
+                "revealed" => {
+                    checkpoint.revealed = value
+                        .split_whitespace()
+                        .map(|i| {
+                            i.parse()
+                                .map_err(|_| format!("bad position `{}`", i))
+                        })
+                        .collect::<Result<_, _>>()?
+                }
+                "guess" => checkpoint.guesses.push(parse_pegs(value)?),
+                "forfeited" => checkpoint.forfeited = true,
+                _ => return Err(format!("unexpected line `{}`", line)),
+            }
+        }
+
+        Ok(Self {
+            variant: variant.ok_or("save is missing its variant")?,
+            checkpoint,
+        })
+    }
+}
+
+pub struct Slot {
+    name: String,
+    path: PathBuf,
+}
+
+impl Slot {
+    pub fn new(name: &str) -> Result<Self, String> {
+        Self::in_dir(name, &paths::saves())
+    }
+
+    fn in_dir(name: &str, dir: &Path) -> Result<Self, String> {
+        let valid = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_';
+        if name.is_empty() || !name.chars().all(valid) {
+            return Err(format!("`{}` is not a valid save name", name));
+        }
+
+        Ok(Self {
+            name: name.to_owned(),
+            path: dir.join(name).with_extension(EXTENSION),
+        })
+    }
+
+    pub fn read(&self) -> Result<Save, String> {
+        let text = fs::read_to_string(&self.path)
+            .map_err(|_| format!("no saved game called `{}`", self.name))?;
+        Save::decode(&text)
+    }
+
+    fn write(path: &Path, save: &Save) {
+        let written = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(path, save.encode()));
+
+        if let Err(e) = written {
+            eprintln!("mastermind: could not save game: {}", e);
+        }
+    }
+
+    // Writes the game to this slot after every guess.
+    pub fn autosave(&self, builder: GameBuilder, variant: &str) -> GameBuilder {
+        let path = self.path.clone();
+        let variant = variant.to_owned();
+
+        builder.autosave(move |checkpoint: &Checkpoint| {
+            let save = Save {
+                variant:    variant.clone(),
+                checkpoint: checkpoint.clone(),
+            };
+            Self::write(&path, &save);
+        })
+    }
+
+    // Finished games aren't worth resuming, so their slots are freed.
+    pub fn finish(&self, game: &Game) -> Result<(), String> {
+        if game.is_over() && self.path.exists() {
+            fs::remove_file(&self.path).map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }
+}
+
+pub fn list() -> Result<Vec<(String, Save)>, String> {
+    let entries = match fs::read_dir(paths::saves()) {
+        Ok(entries) => entries,
+        Err(_) => return Ok(Vec::new()),
+    };
+
+    let mut saves = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == EXTENSION))
+        .filter_map(|path| {
+            let name = path.file_stem()?.to_string_lossy().into_owned();
+            let save = Save::decode(&fs::read_to_string(&path).ok()?).ok()?;
+            Some((name, save))
+        })
+        .collect::<Vec<_>>();
+
+    saves.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(saves)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saves_round_trip() {
+        let save = Save {
+            variant:    "number".to_owned(),
+            checkpoint: Checkpoint {
+                pegs:      vec![1, 2, 3, 4],
+                revealed:  vec![2],
+                guesses:   vec![vec![0, 0, 0, 0], vec![1, 2, 0, 0]],
+                hints:     Vec::new(),
+                seed:      None,
+                forfeited: true,
+            },
+        };
+        assert_eq!(Save::decode(&save.encode()), Ok(save));
+    }
+
+    #[test]
+    fn loads_version_1_saves() {
+        let save = "version 1\nvariant classic\npegs 1 2 3 4\nrevealed\nguess 0 1 2 3\n";
+        let save = Save::decode(save).unwrap();
+        assert_eq!(save.checkpoint.pegs, [1, 2, 3, 4]);
+        assert_eq!(save.checkpoint.guesses, [[0, 1, 2, 3]]);
+
+        assert!(Save::decode("version 4\nvariant classic\n").is_err());
+    }
+
+    #[test]
+    fn seeded_saves_round_trip() {
+        let game = GameBuilder::new().seed(12345).build();
+        let save = Save {
+            variant:    "classic".to_owned(),
+            checkpoint: game.checkpoint(),
+        };
+        assert!(save.encode().contains("\nseed 12345\n"));
+        assert!(!save.encode().contains("pegs"));
+        assert_eq!(Save::decode(&save.encode()), Ok(save));
+    }
+
+    #[test]
+    fn slot_names_are_validated() {
+        let dir = Path::new("saves");
+        assert!(Slot::in_dir("weekend", dir).is_ok());
+        assert!(Slot::in_dir("game_2-b", dir).is_ok());
+        assert!(Slot::in_dir("../escape", dir).is_err());
+        assert!(Slot::in_dir("", dir).is_err());
+    }
+
+    #[test]
+    fn slots_autosave_and_clear() {
+        let dir = std::env::temp_dir()
+            .join(format!("mastermind-saves-{}", std::process::id()));
+        let slot = Slot::in_dir("test", &dir).unwrap();
+
+        let mut game = slot
+            .autosave(GameBuilder::new().pegs(&[1, 2, 3, 4]), "classic")
+            .build();
+        game.guess(&[0, 0, 0, 0]).unwrap();
+
+        let save = slot.read().unwrap();
+        assert_eq!(save.variant, "classic");
+        assert_eq!(save.checkpoint, game.checkpoint());
+
+        game.guess(&[1, 2, 3, 4]).unwrap();
+        slot.finish(&game).unwrap();
+        assert!(slot.read().is_err());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,120 @@
+use std::io::{self, Write};
+
+use mastermind::{
+    knowledge::codes,
+    properties::{self, Property},
+    solver::Strategy,
+    tournament::Tournament,
+    Rules,
+};
+
+// What a strategy is known to manage over every secret of a configuration.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bound {
+    pub strategy: Strategy,
+    pub worst:    usize,
+    pub average:  f64,
+}
+
+// Bounds for the classic game of four pegs and six colors: Knuth's five
+// guesses at worst (4.476 on average), and what the entropy solver, which only
+// guesses candidates, manages.
+pub const CLASSIC: [Bound; 2] = [
+    Bound {
+        strategy: Strategy::Knuth,
+        worst:    5,
+        average:  4.48,
+    },
+    Bound {
+        strategy: Strategy::Entropy,
+        worst:    6,
+        average:  4.46,
+    },
+];
+
+// Checks that standard feedback has every `Property`, and that each solver
+// stays within its bound over every secret. Returns whether every check
+// passed.
+pub fn selftest(
+    peg_count: u8,
+    colors: u8,
+    bounds: &[Bound],
+    mut output: impl Write,
+) -> io::Result<bool> {
+    let mut passed = true;
+    let mut report = |output: &mut dyn Write, check: String, ok: bool| {
+        passed &= ok;
+        writeln!(output, "{:<52} {}", check, if ok { "ok" } else { "FAILED" })
+    };
+
+    let alphabet = (0..colors).collect::<Vec<_>>();
+    let all = codes(&alphabet, peg_count..=peg_count);
+    match properties::verify(&Rules::default(), &Property::ALL, &all) {
+        Ok(()) => report(&mut output, "feedback properties".into(), true)?,
+        Err(violation) => report(
+            &mut output,
+            format!(
+                "feedback properties ({:?} fails for {:?} and {:?})",
+                violation.property, violation.secret, violation.guess
+            ),
+            false,
+        )?,
+    }
+
+    let standings = Tournament::new()
+        .config(peg_count, colors)
+        .strategies(&bounds.iter().map(|b| b.strategy).collect::<Vec<_>>())
+        .run()
+        .map_err(|e| io::Error::other(e.to_string()))?;
+    for (bound, standing) in bounds.iter().zip(&standings) {
+        let name = bound.strategy.name();
+        report(
+            &mut output,
+            format!(
+                "{} worst case {} <= {}",
+                name,
+                standing.worst(),
+                bound.worst
+            ),
+            standing.worst() <= bound.worst,
+        )?;
+        report(
+            &mut output,
+            format!(
+                "{} average {:.3} <= {}",
+                name,
+                standing.average(),
+                bound.average
+            ),
+            standing.average() <= bound.average,
+        )?;
+    }
+
+    Ok(passed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_each_check() {
+        let bound = |worst, average| Bound {
+            strategy: Strategy::Knuth,
+            worst,
+            average,
+        };
+
+        let mut output = Vec::new();
+        assert!(selftest(2, 3, &[bound(4, 3.0)], &mut output).unwrap());
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output.lines().count(), 3);
+        assert!(output.lines().all(|line| line.ends_with(" ok")));
+
+        let mut output = Vec::new();
+        assert!(!selftest(2, 3, &[bound(3, 3.0)], &mut output).unwrap());
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("knuth worst case 4 <= 3"));
+        assert!(output.lines().nth(1).unwrap().ends_with("FAILED"));
+    }
+}
@@ -0,0 +1,61 @@
+use std::fs;
+
+use mastermind::{challenge::Challenge, Difficulty};
+
+use super::{paths, saves::Save};
+
+// Remembers `challenge` as the last game played, for `share` with no slot.
+pub fn remember(challenge: &Challenge) {
+    let path = paths::last_challenge();
+    let written = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|_| fs::write(&path, format!("{}\n", challenge)));
+
+    if let Err(e) = written {
+        eprintln!("mastermind: could not remember challenge: {}", e);
+    }
+}
+
+pub fn last() -> Option<Challenge> {
+    fs::read_to_string(paths::last_challenge())
+        .ok()?
+        .parse()
+        .ok()
+}
+
+// Only games started from a difficulty have a challenge code; their saves
+// name the difficulty in place of a variant.
+pub fn from_save(save: &Save) -> Option<Challenge> {
+    let difficulty = Difficulty::from_name(&save.variant)?;
+    Some(Challenge::new(difficulty, save.checkpoint.seed?))
+}
+
+#[cfg(test)]
+mod tests {
+    use mastermind::GameBuilder;
+
+    use super::*;
+
+    #[test]
+    fn saves_of_challenges_can_be_shared() {
+        let challenge = Challenge::new(Difficulty::Hard, 1234);
+        let save = Save {
+            variant:    "hard".to_owned(),
+            checkpoint: challenge.builder().build().checkpoint(),
+        };
+        assert_eq!(from_save(&save), Some(challenge));
+
+        let save = Save {
+            variant:    "classic".to_owned(),
+            checkpoint: GameBuilder::new().seed(1234).build().checkpoint(),
+        };
+        assert_eq!(from_save(&save), None);
+
+        let save = Save {
+            variant:    "hard".to_owned(),
+            checkpoint: GameBuilder::new().build().checkpoint(),
+        };
+        assert_eq!(from_save(&save), None);
+    }
+}
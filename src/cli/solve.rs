@@ -0,0 +1,92 @@
+use std::io::{self, Write};
+
+use mastermind::{solver::Solver, transcript::Transcript, Rules};
+
+use super::input::format_pegs;
+
+// Candidates beyond this many are counted rather than listed.
+const MAX_LISTED: usize = 20;
+
+pub fn solve(
+    history: &str,
+    colors: u8,
+    mut output: impl Write,
+) -> io::Result<()> {
+    let transcript = Transcript::parse(history);
+    for line in &transcript.skipped {
+        writeln!(
+            output,
+            "skipping line {}: not a guess and its feedback",
+            line
+        )?;
+    }
+    if transcript.entries.is_empty() {
+        return writeln!(output, "The history has no guesses in it.");
+    }
+
+    let candidates = transcript.candidates(colors);
+    match candidates.as_slice() {
+        [] => writeln!(output, "No secret fits this history."),
+        [secret] => {
+            writeln!(output, "The secret must be {}.", format_pegs(secret))
+        }
+        _ => {
+            writeln!(
+                output,
+                "{} secrets are still possible:",
+                candidates.len()
+            )?;
+            for candidate in candidates.iter().take(MAX_LISTED) {
+                writeln!(output, "    {}", format_pegs(candidate))?;
+            }
+            if candidates.len() > MAX_LISTED {
+                writeln!(
+                    output,
+                    "    ...and {} more",
+                    candidates.len() - MAX_LISTED
+                )?;
+            }
+
+            let guess = Solver::from_candidates(candidates)
+                .best_guess(&Rules::default())
+                .unwrap();
+            writeln!(output, "Best next guess: {}", format_pegs(&guess))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(history: &str) -> String {
+        let mut output = Vec::new();
+        solve(history, 6, &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn names_a_unique_secret() {
+        let output = run("0011 -> 1 white\n1223 -> 2 black 1 white\n\
+             1345 -> 1 black 2 white\n2134 -> 2 black 2 white");
+        assert_eq!(output, "The secret must be 1 2 3 4.\n");
+    }
+
+    #[test]
+    fn lists_candidates_and_suggests_a_guess() {
+        let output = run("Game 1\n1122 -> 2 black 2 white\n");
+        assert!(output.starts_with("skipping line 1"));
+        assert!(output.contains("4 secrets are still possible:\n"));
+        assert!(output.contains("    1 2 1 2\n"));
+        assert!(output.contains("Best next guess: "));
+
+        let output = run("0000 -> 1 black\n");
+        assert!(output.contains("...and 480 more"));
+    }
+
+    #[test]
+    fn reports_impossible_histories() {
+        assert!(run("0011 -> 5 black").contains("No secret fits"));
+        assert!(run("hello").contains("no guesses"));
+    }
+}
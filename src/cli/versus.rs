@@ -0,0 +1,109 @@
+use std::io::Write;
+
+use mastermind::{
+    adversary::{adaptive, hardest_secrets},
+    solver::Strategy,
+    tournament::Tournament,
+};
+
+use super::input::format_pegs;
+
+// Hardest secrets beyond this many are counted rather than listed.
+const MAX_LISTED: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Codemaker {
+    // Answers every guess to keep as many secrets possible as it can.
+    Adaptive,
+    // Picks whichever fixed secrets take each solver the most guesses.
+    Hardest,
+}
+
+impl Codemaker {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "adaptive" => Some(Self::Adaptive),
+            "hardest" => Some(Self::Hardest),
+            _ => None,
+        }
+    }
+}
+
+pub fn versus(
+    tournament: &Tournament,
+    codemaker: Codemaker,
+    mut output: impl Write,
+) -> Result<(), String> {
+    let (pegs, colors) = (tournament.peg_count, tournament.colors);
+    for &strategy in &tournament.strategies {
+        let line = match codemaker {
+            Codemaker::Adaptive => {
+                let game = adaptive(pegs, colors, strategy, tournament.seed)
+                    .map_err(|e| e.to_string())?;
+                format!(
+                    "{:<10} {} guesses, ending on {}",
+                    strategy.name(),
+                    game.guesses().len(),
+                    format_pegs(game.pegs())
+                )
+            }
+            Codemaker::Hardest => {
+                let (worst, secrets) = hardest_secrets(tournament, strategy)
+                    .map_err(|e| e.to_string())?;
+                let mut listed = secrets
+                    .iter()
+                    .take(MAX_LISTED)
+                    .map(|secret| format_pegs(secret))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                if secrets.len() > MAX_LISTED {
+                    listed +=
+                        &format!(" and {} more", secrets.len() - MAX_LISTED);
+                }
+                format!(
+                    "{:<10} {} guesses for {}",
+                    strategy.name(),
+                    worst,
+                    listed
+                )
+            }
+        };
+        writeln!(output, "{}", line).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+// Parses the solver names shared with `bench`.
+pub fn strategies(names: Option<&str>) -> Result<Vec<Strategy>, String> {
+    match names {
+        Some(names) => super::bench::parse_solvers(names),
+        None => Ok(Strategy::ALL.to_vec()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(codemaker: Codemaker) -> String {
+        let tournament = Tournament::new()
+            .config(3, 3)
+            .strategies(&[Strategy::Knuth, Strategy::Random]);
+        let mut output = Vec::new();
+        versus(&tournament, codemaker, &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn reports_each_solver() {
+        let output = run(Codemaker::Adaptive);
+        let lines = output.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("knuth      "));
+        assert!(lines[1].contains(" guesses, ending on "));
+
+        let output = run(Codemaker::Hardest);
+        assert!(output.lines().all(|line| line.contains(" guesses for ")));
+        assert_eq!(Codemaker::from_name("Hardest"), Some(Codemaker::Hardest));
+    }
+}
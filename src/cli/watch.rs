@@ -0,0 +1,101 @@
+use std::{
+    io::{self, Write},
+    thread,
+    time::Duration,
+};
+
+use mastermind::{
+    messages::{Catalog, Message},
+    solver::Solver,
+    Game,
+};
+
+use super::input::format_pegs;
+
+// Lets `solver` play `game` move by move, pausing for `delay` after each
+// guess and showing how many candidates it leaves.
+pub fn watch(
+    game: &mut Game,
+    mut solver: Solver,
+    delay: Duration,
+    catalog: &dyn Catalog,
+    mut output: impl Write,
+) -> io::Result<()> {
+    writeln!(
+        output,
+        "{} possible secrets to start with.",
+        solver.candidates().len()
+    )?;
+
+    while !game.is_over() {
+        let guess = solver.next_guess(game);
+        let (hits, near_hits) = match game.guess(&guess) {
+            Ok(feedback) => feedback,
+            Err(e) => {
+                writeln!(output, "{}", catalog.message(&(&e).into()))?;
+                break;
+            }
+        };
+        let before = solver.candidates().len();
+        solver.update(game);
+        let after = solver.candidates().len();
+
+        writeln!(
+            output,
+            "{:>3}: {}  {}  {} -> {} {}",
+            game.guesses().len(),
+            format_pegs(&guess),
+            catalog.message(&Message::Feedback { hits, near_hits }),
+            before,
+            after,
+            if after == 1 {
+                "candidate"
+            }
+            else {
+                "candidates"
+            }
+        )?;
+        output.flush()?;
+        if !game.is_over() {
+            thread::sleep(delay);
+        }
+    }
+
+    let message = match game.is_solved() {
+        true => Message::Solved {
+            guesses: game.guesses().len(),
+        },
+        false => Message::OutOfGuesses {
+            code: &format_pegs(game.pegs()),
+        },
+    };
+    writeln!(output, "{}", catalog.message(&message))
+}
+
+#[cfg(test)]
+mod tests {
+    use mastermind::{messages::English, GameBuilder};
+
+    use super::*;
+
+    #[test]
+    fn shows_every_move_and_the_candidates_left() {
+        let mut game = GameBuilder::new().pegs(&[1, 2, 3, 4]).build();
+        let solver = Solver::new(&game);
+        let mut output = Vec::new();
+        watch(&mut game, solver, Duration::ZERO, &English, &mut output)
+            .unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        let lines = output.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], "1296 possible secrets to start with.");
+        assert!(
+            lines[1].starts_with("  1: 0 0 1 1  0 exact, 1 close  1296 -> ")
+        );
+        assert!(lines[lines.len() - 2].ends_with(" -> 1 candidate"));
+        assert!(output.ends_with(&format!(
+            "You cracked it in {} guesses!\n",
+            game.guesses().len()
+        )));
+    }
+}
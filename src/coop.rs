@@ -0,0 +1,186 @@
+use std::ops::RangeInclusive;
+
+use crate::{knowledge::Knowledge, Game, GameBuilder, GamePlay, GuessError};
+
+// How much one player helped in a cooperative game.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Contribution {
+    pub guesses:    usize,
+    // Possible secrets ruled out by this player's guesses.
+    pub eliminated: usize,
+    pub solved:     bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum TurnError {
+    UnknownTeam(usize),
+    UnknownPlayer(usize),
+    // It's someone else's turn: the player given.
+    NotYourTurn(usize),
+    Guess(GuessError),
+}
+
+impl From<GuessError> for TurnError {
+    fn from(error: GuessError) -> Self { Self::Guess(error) }
+}
+
+// Several players taking turns at one secret, sharing the game's guess
+// budget. Each guess is recorded against whoever made it.
+pub struct Coop {
+    game:          Game,
+    players:       usize,
+    // The player who made each guess.
+    guessed_by:    Vec<usize>,
+    contributions: Vec<Contribution>,
+    // What the guesses so far reveal, kept up to date after each guess.
+    knowledge:     Knowledge,
+}
+
+impl Coop {
+    pub fn new(builder: GameBuilder, players: usize) -> Self {
+        Self::from_game(builder.build(), players)
+    }
+
+    // Players carrying on with `game`; guesses already made aren't credited
+    // to anyone.
+    pub fn from_game(game: Game, players: usize) -> Self {
+        Self {
+            knowledge: Knowledge::new(&game),
+            game,
+            players: players.max(1),
+            guessed_by: Vec::new(),
+            contributions: vec![Contribution::default(); players.max(1)],
+        }
+    }
+
+    pub fn game(&self) -> &Game { &self.game }
+    pub fn players(&self) -> usize { self.players }
+    pub fn guessed_by(&self) -> &[usize] { &self.guessed_by }
+
+    pub fn contribution(&self, player: usize) -> Option<Contribution> {
+        self.contributions.get(player).copied()
+    }
+
+    // Whose turn it is; players go round in order.
+    pub fn turn(&self) -> usize { self.guessed_by.len() % self.players }
+
+    pub fn guess(
+        &mut self,
+        player: usize,
+        guess: &[u8],
+    ) -> Result<(u8, u8), TurnError> {
+        if player >= self.players {
+            return Err(TurnError::UnknownPlayer(player));
+        }
+        if player != self.turn() {
+            return Err(TurnError::NotYourTurn(self.turn()));
+        }
+        Ok(self.record(player, guess)?)
+    }
+
+    pub fn forfeit(&mut self) { self.game.forfeit() }
+
+    // Makes a guess for `player`, whose turn it must be.
+    fn record(
+        &mut self,
+        player: usize,
+        guess: &[u8],
+    ) -> Result<(u8, u8), GuessError> {
+        let before = self.knowledge.candidates().len();
+        let feedback = self.game.guess(guess)?;
+        self.knowledge.update(&self.game);
+        let remaining = self.knowledge.candidates().len();
+
+        let contribution = &mut self.contributions[player];
+        contribution.guesses += 1;
+        contribution.eliminated += before.saturating_sub(remaining);
+        contribution.solved = self.game.is_solved();
+
+        self.guessed_by.push(player);
+        Ok(feedback)
+    }
+}
+
+// Played through `GamePlay`, each guess goes to whoever's turn it is.
+impl GamePlay for Coop {
+    fn guess(&mut self, guess: &[u8]) -> Result<(u8, u8), GuessError> {
+        self.record(self.turn(), guess)
+    }
+    fn forfeit(&mut self) { Coop::forfeit(self) }
+
+    fn guesses(&self) -> &[Vec<u8>] { self.game.guesses() }
+    fn feedback(&self, index: usize) -> Option<(u8, u8)> {
+        self.game.hits(index)
+    }
+    fn max_guesses(&self) -> Option<u8> { self.game.max_guesses() }
+    fn peg_counts(&self) -> RangeInclusive<u8> { self.game.peg_counts() }
+    fn alphabet(&self) -> &[u8] { self.game.alphabet() }
+    fn is_over(&self) -> bool { self.game.is_over() }
+    fn is_solved(&self) -> bool { self.game.is_solved() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn players_take_turns() {
+        let builder = GameBuilder::new().pegs(&[1, 2, 3, 4]).max_guesses(4);
+        let mut coop = Coop::new(builder, 3);
+
+        coop.guess(0, &[0, 0, 1, 1]).unwrap();
+        assert_eq!(
+            coop.guess(2, &[1, 1, 2, 2]),
+            Err(TurnError::NotYourTurn(1))
+        );
+        assert_eq!(
+            coop.guess(3, &[1, 1, 2, 2]),
+            Err(TurnError::UnknownPlayer(3))
+        );
+        coop.guess(1, &[1, 1, 2, 2]).unwrap();
+        coop.guess(2, &[1, 2, 3, 4]).unwrap();
+
+        assert_eq!(coop.guessed_by(), [0, 1, 2]);
+        assert_eq!(coop.turn(), 0);
+        assert!(coop.contribution(2).unwrap().solved);
+        assert_eq!(coop.contribution(0).unwrap().guesses, 1);
+    }
+
+    #[test]
+    fn contributions_count_eliminated_secrets() {
+        let builder = GameBuilder::new().pegs(&[1, 2, 3, 4]);
+        let mut coop = Coop::new(builder, 2);
+        coop.guess(0, &[0, 0, 0, 0]).unwrap();
+        coop.guess(1, &[5, 5, 5, 5]).unwrap();
+
+        // No 0s leaves 5^4 of the 6^4 codes, and no 5s then leaves 4^4.
+        assert_eq!(coop.contribution(0).unwrap().eliminated, 1296 - 625);
+        assert_eq!(coop.contribution(1).unwrap().eliminated, 625 - 256);
+    }
+
+    #[test]
+    fn trait_guesses_go_to_whoevers_turn_it_is() {
+        let builder = GameBuilder::new().pegs(&[1, 2, 3, 4]);
+        let mut coop = Coop::new(builder, 2);
+        let game: &mut dyn GamePlay = &mut coop;
+        game.guess(&[0, 0, 0, 0]).unwrap();
+        assert_eq!(game.guess(&[1, 2, 4, 3]), Ok((2, 2)));
+        game.forfeit();
+        assert!(game.is_over());
+
+        assert_eq!(coop.guessed_by(), [0, 1]);
+        assert_eq!(coop.contribution(1).unwrap().guesses, 1);
+    }
+
+    #[test]
+    fn the_guess_budget_is_shared() {
+        let builder = GameBuilder::new().pegs(&[1, 2, 3, 4]).max_guesses(2);
+        let mut coop = Coop::new(builder, 2);
+        coop.guess(0, &[0, 0, 0, 0]).unwrap();
+        coop.guess(1, &[0, 0, 0, 0]).unwrap();
+        assert_eq!(
+            coop.guess(0, &[1, 2, 3, 4]),
+            Err(TurnError::Guess(GuessError::NoGuessesLeft))
+        );
+    }
+}
@@ -0,0 +1,245 @@
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+use rand::{seq::SliceRandom, thread_rng};
+
+use crate::{Game, GuessError};
+
+// How many votes become one guess.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Tally {
+    // The guess with the most votes.
+    #[default]
+    WholeGuess,
+    // The most voted peg in each position, even if nobody voted for that
+    // exact guess.
+    PerSlot,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TieBreak {
+    // Whichever of the tied options was voted for first.
+    #[default]
+    Earliest,
+    Random,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VoteError {
+    // The guess couldn't be played in this game.
+    Invalid,
+    Over,
+}
+
+impl fmt::Display for VoteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Invalid => f.write_str("that guess can't be played here"),
+            Self::Over => f.write_str("the game is over"),
+        }
+    }
+}
+
+impl std::error::Error for VoteError {}
+
+// The winner among `options`, given in the order votes were cast.
+fn winner<T: Clone + PartialEq>(
+    options: impl IntoIterator<Item = T>,
+    tie_break: TieBreak,
+) -> Option<T> {
+    let mut counts: Vec<(T, usize)> = Vec::new();
+    for option in options {
+        match counts.iter_mut().find(|(seen, _)| *seen == option) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((option, 1)),
+        }
+    }
+
+    let most = counts.iter().map(|(_, count)| *count).max()?;
+    let tied = counts
+        .into_iter()
+        .filter(|(_, count)| *count == most)
+        .map(|(option, _)| option)
+        .collect::<Vec<_>>();
+    match tie_break {
+        TieBreak::Earliest => tied.first().cloned(),
+        TieBreak::Random => tied.choose(&mut thread_rng()).cloned(),
+    }
+}
+
+// Votes for one guess, one per voter. A voter voting again replaces their
+// earlier vote.
+#[derive(Debug, Clone, Default)]
+pub struct Poll {
+    pub tally:     Tally,
+    pub tie_break: TieBreak,
+    votes:         Vec<(String, Vec<u8>)>,
+}
+
+impl Poll {
+    pub fn new(tally: Tally, tie_break: TieBreak) -> Self {
+        Self {
+            tally,
+            tie_break,
+            votes: Vec::new(),
+        }
+    }
+
+    pub fn votes(&self) -> usize { self.votes.len() }
+
+    pub fn vote(&mut self, voter: &str, guess: &[u8]) {
+        self.votes.retain(|(earlier, _)| earlier != voter);
+        self.votes.push((voter.to_owned(), guess.to_vec()));
+    }
+
+    // The guess the crowd chose, if anyone voted.
+    pub fn result(&self) -> Option<Vec<u8>> {
+        let guesses = self.votes.iter().map(|(_, guess)| guess);
+        match self.tally {
+            Tally::WholeGuess => winner(guesses.cloned(), self.tie_break),
+            Tally::PerSlot => {
+                let len =
+                    winner(guesses.clone().map(Vec::len), self.tie_break)?;
+                let guesses = guesses.filter(|guess| guess.len() == len);
+                (0..len)
+                    .map(|i| {
+                        winner(guesses.clone().map(|g| g[i]), self.tie_break)
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+// A game played by a crowd: votes are collected for a window of time, then
+// the winning guess is played and a new poll opens.
+pub struct CrowdGame {
+    game:   Game,
+    poll:   Poll,
+    window: Duration,
+    opened: Instant,
+}
+
+impl CrowdGame {
+    pub fn new(game: Game, poll: Poll, window: Duration) -> Self {
+        Self::open_at(game, poll, window, Instant::now())
+    }
+
+    pub fn open_at(
+        game: Game,
+        poll: Poll,
+        window: Duration,
+        opened: Instant,
+    ) -> Self {
+        Self {
+            game,
+            poll,
+            window,
+            opened,
+        }
+    }
+
+    pub fn game(&self) -> &Game { &self.game }
+    pub fn poll(&self) -> &Poll { &self.poll }
+
+    // When the current poll closes.
+    pub fn closes(&self) -> Instant { self.opened + self.window }
+
+    pub fn vote(&mut self, voter: &str, guess: &[u8]) -> Result<(), VoteError> {
+        if self.game.is_over() {
+            return Err(VoteError::Over);
+        }
+        let valid = self.game.accepts_length(guess.len())
+            && guess.iter().all(|&peg| self.game.is_guessable(peg));
+        if !valid {
+            return Err(VoteError::Invalid);
+        }
+
+        self.poll.vote(voter, guess);
+        Ok(())
+    }
+
+    // Closes the poll if its window has passed, playing the crowd's guess.
+    // A window without votes just starts again.
+    pub fn tick(
+        &mut self,
+        now: Instant,
+    ) -> Option<Result<(u8, u8), GuessError>> {
+        if now < self.closes() || self.game.is_over() {
+            return None;
+        }
+
+        let guess = self.poll.result();
+        self.poll.votes.clear();
+        self.opened = now;
+        Some(self.game.guess(&guess?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GameBuilder;
+
+    #[test]
+    fn whole_guesses_need_a_plurality() {
+        let mut poll = Poll::default();
+        poll.vote("ann", &[1, 1, 2, 2]);
+        poll.vote("bob", &[3, 3, 4, 4]);
+        assert_eq!(poll.result(), Some(vec![1, 1, 2, 2]));
+
+        poll.vote("cat", &[3, 3, 4, 4]);
+        assert_eq!(poll.result(), Some(vec![3, 3, 4, 4]));
+
+        // Changing a vote replaces it.
+        poll.vote("cat", &[1, 1, 2, 2]);
+        assert_eq!(poll.votes(), 3);
+        assert_eq!(poll.result(), Some(vec![1, 1, 2, 2]));
+    }
+
+    #[test]
+    fn per_slot_tallies_take_each_position_separately() {
+        let mut poll = Poll::new(Tally::PerSlot, TieBreak::Earliest);
+        assert_eq!(poll.result(), None);
+        poll.vote("ann", &[1, 2, 3, 4]);
+        poll.vote("bob", &[1, 5, 5, 4]);
+        poll.vote("cat", &[0, 5, 3, 0]);
+        assert_eq!(poll.result(), Some(vec![1, 5, 3, 4]));
+
+        let mut poll = Poll::new(Tally::PerSlot, TieBreak::Random);
+        poll.vote("ann", &[1, 2]);
+        poll.vote("bob", &[3, 4]);
+        let result = poll.result().unwrap();
+        assert!([1, 3].contains(&result[0]) && [2, 4].contains(&result[1]));
+    }
+
+    #[test]
+    fn the_crowd_guesses_when_the_window_closes() {
+        let start = Instant::now();
+        let window = Duration::from_secs(30);
+        let game = GameBuilder::new().pegs(&[1, 2, 3, 4]).build();
+        let mut crowd =
+            CrowdGame::open_at(game, Poll::default(), window, start);
+
+        assert_eq!(crowd.vote("ann", &[1, 2, 3]), Err(VoteError::Invalid));
+        assert_eq!(crowd.vote("ann", &[1, 2, 3, 9]), Err(VoteError::Invalid));
+        crowd.vote("ann", &[1, 2, 3, 5]).unwrap();
+        crowd.vote("bob", &[1, 2, 3, 5]).unwrap();
+        crowd.vote("cat", &[0, 0, 0, 0]).unwrap();
+
+        assert_eq!(crowd.tick(start + Duration::from_secs(29)), None);
+        assert_eq!(crowd.tick(start + window), Some(Ok((3, 0))));
+        assert_eq!(crowd.poll().votes(), 0);
+        assert_eq!(crowd.closes(), start + window * 2);
+
+        // Nobody voted, so the window starts over.
+        assert_eq!(crowd.tick(start + window * 2), None);
+        assert_eq!(crowd.closes(), start + window * 3);
+
+        crowd.vote("ann", &[1, 2, 3, 4]).unwrap();
+        assert_eq!(crowd.tick(start + window * 3), Some(Ok((4, 0))));
+        assert_eq!(crowd.vote("bob", &[1, 2, 3, 4]), Err(VoteError::Over));
+    }
+}
@@ -0,0 +1,184 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{challenge::Challenge, Difficulty, Game, GameBuilder};
+
+// Daily puzzles are numbered from this day, counted in days since the Unix
+// epoch (2024-01-01).
+const FIRST_DAY: u64 = 19723;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+// The puzzle for one day. Every player gets the same secret on the same day
+// (in UTC).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Daily {
+    // Days since the Unix epoch.
+    pub day: u64,
+}
+
+impl Daily {
+    pub fn today() -> Self {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        Self::on(now.as_secs() / SECONDS_PER_DAY)
+    }
+
+    pub fn on(day: u64) -> Self { Self { day } }
+
+    // The puzzle's number, starting from 1.
+    pub fn number(&self) -> u64 { self.day.saturating_sub(FIRST_DAY) + 1 }
+
+    // The day as a `YYYY-MM-DD` date.
+    pub fn date(&self) -> String {
+        // Howard Hinnant's `civil_from_days`, shifted to start years in March
+        // so leap days fall at the end.
+        let days = self.day + 719_468;
+        let era = days / 146_097;
+        let day_of_era = days % 146_097;
+        let year_of_era = (day_of_era - day_of_era / 1460
+            + day_of_era / 36_524
+            - day_of_era / 146_096)
+            / 365;
+        let day_of_year = day_of_era
+            - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = (shifted_month + 2) % 12 + 1;
+        let year = year_of_era + era * 400 + u64::from(month <= 2);
+        format!("{:04}-{:02}-{:02}", year, month, day)
+    }
+
+    // The day's game as a challenge, so it can be shared like any other.
+    pub fn challenge(&self) -> Challenge {
+        let seed = self.day.wrapping_mul(0x9e37_79b9_7f4a_7c15) >> 24;
+        Challenge::new(Difficulty::Medium, seed)
+    }
+
+    pub fn builder(&self) -> GameBuilder { self.challenge().builder() }
+
+    // A spoiler-free summary of a finished game: a square per peg of each
+    // guess, green for hits and yellow for near hits.
+    //
+    //     Mastermind #123 4/10
+    //     🟩🟨⬛⬛
+    //     ...
+    pub fn share(&self, game: &Game) -> String {
+        let guesses = match game.is_solved() {
+            true => game.guesses().len().to_string(),
+            false => "X".to_owned(),
+        };
+        let max_guesses = game
+            .max_guesses()
+            .map_or("-".to_owned(), |max| max.to_string());
+
+        let mut text = format!(
+            "Mastermind #{} {}/{}\n",
+            self.number(),
+            guesses,
+            max_guesses
+        );
+        for (i, guess) in game.guesses().iter().enumerate() {
+            let (hits, near_hits) = game.hits(i).unwrap_or_default();
+            let misses = guess.len() - (hits + near_hits) as usize;
+            text += &"🟩".repeat(hits as usize);
+            text += &"🟨".repeat(near_hits as usize);
+            text += &"⬛".repeat(misses);
+            text += "\n";
+        }
+        text
+    }
+}
+
+// Consecutive days with a won daily puzzle.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DailyStreak {
+    // The last day a daily puzzle was played, in days since the Unix epoch.
+    pub last_day: Option<u64>,
+    pub current:  u32,
+    pub best:     u32,
+}
+
+impl DailyStreak {
+    pub fn has_played(&self, daily: Daily) -> bool {
+        self.last_day == Some(daily.day)
+    }
+
+    // The streak as of `daily`: a missed day ends it even before the next
+    // game is recorded.
+    pub fn current_on(&self, daily: Daily) -> u32 {
+        match self.last_day {
+            Some(last) if last + 1 >= daily.day => self.current,
+            _ => 0,
+        }
+    }
+
+    pub fn record(&mut self, daily: Daily, won: bool) {
+        if self.has_played(daily) {
+            return;
+        }
+
+        self.current = match won {
+            true => self.current_on(daily) + 1,
+            false => 0,
+        };
+        self.best = self.best.max(self.current);
+        self.last_day = Some(daily.day);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_have_dates_and_numbers() {
+        assert_eq!(Daily::on(0).date(), "1970-01-01");
+        assert_eq!(Daily::on(FIRST_DAY).date(), "2024-01-01");
+        assert_eq!(Daily::on(FIRST_DAY + 59).date(), "2024-02-29");
+        assert_eq!(Daily::on(FIRST_DAY + 366).date(), "2025-01-01");
+        assert_eq!(Daily::on(FIRST_DAY).number(), 1);
+        assert_eq!(Daily::on(FIRST_DAY + 9).number(), 10);
+    }
+
+    #[test]
+    fn everyone_gets_the_same_secret() {
+        let daily = Daily::on(20_000);
+        assert_eq!(
+            daily.builder().build().pegs(),
+            daily.builder().build().pegs()
+        );
+        assert_ne!(daily.challenge(), Daily::on(20_001).challenge());
+    }
+
+    #[test]
+    fn share_hides_the_guesses() {
+        let daily = Daily::on(FIRST_DAY + 4);
+        let mut game = GameBuilder::new().pegs(&[1, 2, 3, 4]).build();
+        game.guess(&[1, 3, 0, 0]).unwrap();
+        game.guess(&[1, 2, 3, 4]).unwrap();
+        assert_eq!(
+            daily.share(&game),
+            "Mastermind #5 2/12\n🟩🟨⬛⬛\n🟩🟩🟩🟩\n"
+        );
+    }
+
+    #[test]
+    fn streaks_need_consecutive_wins() {
+        let mut streak = DailyStreak::default();
+        streak.record(Daily::on(10), true);
+        streak.record(Daily::on(11), true);
+        streak.record(Daily::on(11), false);
+        assert_eq!((streak.current, streak.best), (2, 2));
+        assert!(streak.has_played(Daily::on(11)));
+
+        assert_eq!(streak.current_on(Daily::on(12)), 2);
+        assert_eq!(streak.current_on(Daily::on(13)), 0);
+        streak.record(Daily::on(13), true);
+        assert_eq!((streak.current, streak.best), (1, 2));
+
+        streak.record(Daily::on(14), false);
+        assert_eq!((streak.current, streak.best), (0, 2));
+    }
+}
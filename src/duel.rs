@@ -0,0 +1,178 @@
+use std::{
+    cmp::Ordering,
+    time::{Duration, Instant},
+};
+
+use crate::{Game, GameBuilder, GuessError};
+
+// How a duel is won.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Race {
+    // The first player to crack the code wins at once.
+    #[default]
+    FirstToSolve,
+    // Both players finish. Solving beats not solving, then fewer guesses
+    // win, then the faster time.
+    FewestGuesses,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Winner(usize),
+    Draw,
+}
+
+// What a player may see of their opponent's game: how far along it is, but
+// not the guesses themselves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    pub guesses:  usize,
+    pub solved:   bool,
+    pub finished: Option<Duration>,
+}
+
+// Two players racing to crack the same secret, each on their own board.
+pub struct Duel {
+    games:    [Game; 2],
+    finished: [Option<Duration>; 2],
+    race:     Race,
+    started:  Instant,
+}
+
+impl Duel {
+    pub fn new(builder: GameBuilder, race: Race) -> Self {
+        Self::start_at(builder, race, Instant::now())
+    }
+
+    pub fn start_at(
+        builder: GameBuilder,
+        race: Race,
+        started: Instant,
+    ) -> Self {
+        let game = builder.build();
+        Self {
+            games: [game.truncated(0), game],
+            finished: [None; 2],
+            race,
+            started,
+        }
+    }
+
+    pub fn game(&self, player: usize) -> &Game { &self.games[player] }
+
+    pub fn progress(&self, player: usize) -> Progress {
+        Progress {
+            guesses:  self.games[player].guesses().len(),
+            solved:   self.games[player].is_solved(),
+            finished: self.finished[player],
+        }
+    }
+
+    pub fn guess(
+        &mut self,
+        player: usize,
+        guess: &[u8],
+    ) -> Result<(u8, u8), GuessError> {
+        self.guess_at(player, guess, Instant::now())
+    }
+
+    pub fn guess_at(
+        &mut self,
+        player: usize,
+        guess: &[u8],
+        at: Instant,
+    ) -> Result<(u8, u8), GuessError> {
+        if self.outcome().is_some() {
+            return Err(GuessError::GameOver);
+        }
+
+        let feedback = self.games[player].guess(guess)?;
+        if self.games[player].is_over() {
+            self.finished[player] =
+                Some(at.saturating_duration_since(self.started));
+        }
+        Ok(feedback)
+    }
+
+    // The result, once the duel is decided.
+    pub fn outcome(&self) -> Option<Outcome> {
+        if self.race == Race::FirstToSolve {
+            if let Some(winner) = (0..2).find(|&p| self.games[p].is_solved()) {
+                return Some(Outcome::Winner(winner));
+            }
+        }
+        if !self.games.iter().all(Game::is_over) {
+            return None;
+        }
+
+        // Better results sort first. Time only separates players who both
+        // solved it.
+        let rank = |player: usize| {
+            let game = &self.games[player];
+            let time = self.finished[player].filter(|_| game.is_solved());
+            (!game.is_solved(), game.guesses().len(), time)
+        };
+        Some(match rank(0).cmp(&rank(1)) {
+            Ordering::Less => Outcome::Winner(0),
+            Ordering::Greater => Outcome::Winner(1),
+            Ordering::Equal => Outcome::Draw,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn duel(race: Race) -> (Duel, Instant) {
+        let start = Instant::now();
+        let builder = GameBuilder::new().pegs(&[1, 2, 3, 4]).max_guesses(3);
+        (Duel::start_at(builder, race, start), start)
+    }
+
+    #[test]
+    fn first_to_solve_wins_at_once() {
+        let (mut duel, start) = duel(Race::FirstToSolve);
+        assert_eq!(duel.game(0).pegs(), duel.game(1).pegs());
+
+        duel.guess_at(0, &[0, 0, 0, 0], start).unwrap();
+        duel.guess_at(1, &[1, 2, 3, 4], start + Duration::from_secs(9))
+            .unwrap();
+        assert_eq!(duel.outcome(), Some(Outcome::Winner(1)));
+        assert_eq!(
+            duel.progress(1),
+            Progress {
+                guesses:  1,
+                solved:   true,
+                finished: Some(Duration::from_secs(9)),
+            }
+        );
+        assert_eq!(duel.guess(0, &[1, 2, 3, 4]), Err(GuessError::GameOver));
+    }
+
+    #[test]
+    fn fewest_guesses_then_fastest_wins() {
+        let (mut duel, start) = duel(Race::FewestGuesses);
+        duel.guess_at(0, &[0, 0, 0, 0], start).unwrap();
+        duel.guess_at(0, &[1, 2, 3, 4], start + Duration::from_secs(5))
+            .unwrap();
+        assert_eq!(duel.outcome(), None);
+
+        duel.guess_at(1, &[0, 1, 0, 0], start).unwrap();
+        duel.guess_at(1, &[1, 2, 3, 4], start + Duration::from_secs(4))
+            .unwrap();
+        assert_eq!(duel.outcome(), Some(Outcome::Winner(1)));
+    }
+
+    #[test]
+    fn nobody_solving_is_a_draw() {
+        let (mut duel, start) = duel(Race::FewestGuesses);
+        for player in 0..2 {
+            for i in 0..3 {
+                let at = start + Duration::from_secs(player * 10 + i);
+                duel.guess_at(player as usize, &[0, 0, 0, 0], at).unwrap();
+            }
+        }
+        assert_eq!(duel.outcome(), Some(Outcome::Draw));
+    }
+}
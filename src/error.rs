@@ -0,0 +1,113 @@
+use std::{fmt, io};
+
+#[cfg(feature = "rhai")]
+use crate::game::ScriptError;
+#[cfg(feature = "serde")]
+use crate::schema::SchemaError;
+use crate::{
+    challenge::ChallengeError, coop::TurnError, crowd::VoteError,
+    game::TemplateError, input::InputError, transcript::ImportError,
+    BuildError, GuessError,
+};
+
+// Any error the crate can produce, so applications can `?` their way through
+// several subsystems with a single error type. New variants may be added, but
+// existing ones are never renamed or removed.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    Build(BuildError),
+    Guess(GuessError),
+    Input(InputError),
+    Template(TemplateError),
+    Challenge(ChallengeError),
+    Import(ImportError),
+    Turn(TurnError),
+    Vote(VoteError),
+    #[cfg(feature = "serde")]
+    Schema(SchemaError),
+    #[cfg(feature = "rhai")]
+    Script(ScriptError),
+    Io(io::Error),
+}
+
+impl Error {
+    fn inner(&self) -> &(dyn std::error::Error + 'static) {
+        match self {
+            Self::Build(e) => e,
+            Self::Guess(e) => e,
+            Self::Input(e) => e,
+            Self::Template(e) => e,
+            Self::Challenge(e) => e,
+            Self::Import(e) => e,
+            Self::Turn(e) => e,
+            Self::Vote(e) => e,
+            #[cfg(feature = "serde")]
+            Self::Schema(e) => e,
+            #[cfg(feature = "rhai")]
+            Self::Script(e) => e,
+            Self::Io(e) => e,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.inner(), f)
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(self.inner())
+    }
+}
+
+macro_rules! from {
+    ($($variant:ident($error:ty)),* $(,)?) => {$(
+        impl From<$error> for Error {
+            fn from(e: $error) -> Self { Self::$variant(e) }
+        }
+    )*};
+}
+
+from! {
+    Build(BuildError),
+    Guess(GuessError),
+    Input(InputError),
+    Template(TemplateError),
+    Challenge(ChallengeError),
+    Import(ImportError),
+    Turn(TurnError),
+    Vote(VoteError),
+    Io(io::Error),
+}
+
+#[cfg(feature = "serde")]
+from!(Schema(SchemaError));
+#[cfg(feature = "rhai")]
+from!(Script(ScriptError));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{challenge::Challenge, GameBuilder};
+
+    fn play(code: &str, guess: &[u8]) -> Result<(u8, u8), Error> {
+        let challenge = code.parse::<Challenge>()?;
+        let mut game = challenge.builder().try_build()?;
+        Ok(game.guess(guess)?)
+    }
+
+    #[test]
+    fn subsystem_errors_convert() {
+        assert!(play("M-1", &[0, 0, 0, 0]).is_ok());
+        assert!(matches!(play("?", &[]), Err(Error::Challenge(_))));
+        assert!(matches!(play("M-1", &[0]), Err(Error::Guess(_))));
+
+        let build = GameBuilder::new().pegs(&[9]).try_build().err().unwrap();
+        let error = Error::from(build.clone());
+        assert_eq!(error.to_string(), build.to_string());
+        assert!(std::error::Error::source(&error).is_some());
+    }
+}
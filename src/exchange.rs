@@ -0,0 +1,266 @@
+// Plain JSON forms of games and puzzles, for swapping content with tools and
+// websites that don't use this crate. Both use the usual `schema` envelope:
+//
+//     {"kind": "game", "version": 1, "data": {
+//         "colors": 6,
+//         "allow_empty": false,
+//         "secret": [1, 2, 3, 4],
+//         "max_guesses": 12,
+//         "guesses": [{"guess": [0, 0, 1, 1], "hits": 0, "near_hits": 1}]
+//     }}
+//
+//     {"kind": "puzzle", "version": 1, "data": {
+//         "colors": 6,
+//         "allow_empty": false,
+//         "secret": [1, 1, 2, 2],
+//         "guesses": [{"guess": [1, 1, 1, 1], "hits": 2, "near_hits": 0}],
+//         "guesses_needed": 3
+//     }}
+//
+// A puzzle pack is a list of puzzles, each in the `data` form above:
+//
+//     {"kind": "puzzle-pack", "version": 1, "data": {"puzzles": [...]}}
+//
+// Pegs are numbered from 0, and an empty hole is 255. `max_guesses` is null
+// for unlimited games, and `allow_empty` may be left out. Only standard rules
+// are described; a game using other rules is exported as if it were standard.
+// Fields may be added in later versions, but never renamed or removed.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    puzzle::Puzzle,
+    schema::{self, Schema, SchemaError},
+    BoardView, Game, GameBuilder, EMPTY,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GuessRecord {
+    pub guess:     Vec<u8>,
+    pub hits:      u8,
+    pub near_hits: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameRecord {
+    pub colors:      u8,
+    #[serde(default)]
+    pub allow_empty: bool,
+    pub secret:      Vec<u8>,
+    pub max_guesses: Option<u8>,
+    pub guesses:     Vec<GuessRecord>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PuzzleRecord {
+    pub colors:         u8,
+    #[serde(default)]
+    pub allow_empty:    bool,
+    pub secret:         Vec<u8>,
+    pub guesses:        Vec<GuessRecord>,
+    pub guesses_needed: u8,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PuzzlePack {
+    pub puzzles: Vec<PuzzleRecord>,
+}
+
+impl Schema for GameRecord {
+    const KIND: &'static str = "game";
+    const VERSION: u32 = 1;
+}
+
+impl Schema for PuzzleRecord {
+    const KIND: &'static str = "puzzle";
+    const VERSION: u32 = 1;
+}
+
+impl Schema for PuzzlePack {
+    const KIND: &'static str = "puzzle-pack";
+    const VERSION: u32 = 1;
+}
+
+impl PuzzlePack {
+    pub fn to_json(puzzles: &[Puzzle]) -> Result<String, SchemaError> {
+        schema::to_string(&Self {
+            puzzles: puzzles.iter().map(PuzzleRecord::from).collect(),
+        })
+    }
+
+    pub fn from_json(text: &str) -> Result<Vec<Puzzle>, SchemaError> {
+        schema::from_str::<Self>(text)?
+            .puzzles
+            .into_iter()
+            .map(Puzzle::try_from)
+            .collect()
+    }
+}
+
+fn guess_records(board: BoardView) -> Vec<GuessRecord> {
+    (0..board.guesses().len())
+        .map(|i| {
+            let (hits, near_hits) = board.hits(i).unwrap();
+            GuessRecord {
+                guess: board.guesses()[i].clone(),
+                hits,
+                near_hits,
+            }
+        })
+        .collect()
+}
+
+// Rebuilds a game from its secret, then replays `guesses`, checking that each
+// one gets the recorded feedback.
+pub(crate) fn replay(
+    builder: GameBuilder,
+    guesses: &[GuessRecord],
+) -> Result<Game, SchemaError> {
+    let mut game = builder.try_build().map_err(SchemaError::Build)?;
+    for (i, record) in guesses.iter().enumerate() {
+        match game.guess(&record.guess) {
+            Ok(feedback) if feedback == (record.hits, record.near_hits) => (),
+            _ => return Err(SchemaError::InvalidHistory(i)),
+        }
+    }
+    Ok(game)
+}
+
+pub(crate) fn builder(
+    colors: u8,
+    allow_empty: bool,
+    secret: &[u8],
+) -> GameBuilder {
+    GameBuilder::new()
+        .peg_range(colors)
+        .allow_empty(allow_empty)
+        .peg_count(secret.len() as u8)
+        .pegs(secret)
+}
+
+impl From<&Game> for GameRecord {
+    fn from(game: &Game) -> Self {
+        let alphabet = game.alphabet();
+        Self {
+            colors:      alphabet.iter().filter(|&&p| p != EMPTY).count() as u8,
+            allow_empty: alphabet.contains(&EMPTY),
+            secret:      game.pegs().to_owned(),
+            max_guesses: game.max_guesses(),
+            guesses:     guess_records(game.view()),
+        }
+    }
+}
+
+impl Game {
+    pub fn to_json(&self) -> Result<String, SchemaError> {
+        schema::to_string(&GameRecord::from(self))
+    }
+
+    pub fn from_json(text: &str) -> Result<Game, SchemaError> {
+        let record = schema::from_str::<GameRecord>(text)?;
+        let builder =
+            builder(record.colors, record.allow_empty, &record.secret);
+        let builder = match record.max_guesses {
+            Some(max) => builder.max_guesses(max),
+            None => builder.unlimited_guesses(true),
+        };
+        replay(builder, &record.guesses)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loads_game_v1() {
+        let saved = r#"{"kind":"game","version":1,"data":{
+            "colors":6,"secret":[1,2,3,4],"max_guesses":12,
+            "guesses":[{"guess":[0,0,1,1],"hits":0,"near_hits":1}]
+        }}"#;
+        let game = Game::from_json(saved).unwrap();
+        assert_eq!(game.pegs(), [1, 2, 3, 4]);
+        assert_eq!(game.alphabet(), [0, 1, 2, 3, 4, 5]);
+        assert_eq!(game.guesses(), [[0, 0, 1, 1]]);
+        assert_eq!(game.max_guesses(), Some(12));
+    }
+
+    #[test]
+    fn loads_puzzle_v1() {
+        let saved = r#"{"kind":"puzzle","version":1,"data":{
+            "colors":6,"allow_empty":false,"secret":[1,1,2,2],
+            "guesses":[
+                {"guess":[1,1,1,1],"hits":2,"near_hits":0},
+                {"guess":[2,2,2,2],"hits":2,"near_hits":0}
+            ],
+            "guesses_needed":3
+        }}"#;
+        let puzzle = Puzzle::from_json(saved).unwrap();
+        assert_eq!(puzzle.guesses_needed(), 3);
+        assert_eq!(puzzle.board().guesses().len(), 2);
+    }
+
+    #[test]
+    fn games_round_trip() {
+        let mut game = GameBuilder::new()
+            .pegs(&[EMPTY, 3, 3, 0])
+            .allow_empty(true)
+            .unlimited_guesses(true)
+            .build();
+        game.guess(&[3, 3, EMPTY, 1]).unwrap();
+        game.guess(&[EMPTY, 3, 3, 0]).unwrap();
+
+        let loaded = Game::from_json(&game.to_json().unwrap()).unwrap();
+        assert_eq!(GameRecord::from(&loaded), GameRecord::from(&game));
+        assert!(loaded.is_solved());
+    }
+
+    #[test]
+    fn puzzles_round_trip() {
+        let mut game = GameBuilder::new().pegs(&[1, 1, 2, 2]).build();
+        game.guess(&[1, 1, 1, 1]).unwrap();
+        game.guess(&[2, 2, 2, 2]).unwrap();
+        let puzzle = Puzzle::from_position(&game).unwrap();
+
+        let loaded = Puzzle::from_json(&puzzle.to_json().unwrap()).unwrap();
+        assert_eq!(loaded.to_json().unwrap(), puzzle.to_json().unwrap());
+    }
+
+    #[test]
+    fn puzzle_packs_round_trip() {
+        let saved = r#"{"kind":"puzzle-pack","version":1,"data":{"puzzles":[
+            {"colors":6,"secret":[1,1,2,2],"guesses_needed":3,"guesses":[
+                {"guess":[1,1,1,1],"hits":2,"near_hits":0},
+                {"guess":[2,2,2,2],"hits":2,"near_hits":0}
+            ]},
+            {"colors":6,"secret":[0,1,2,3],"guesses_needed":1,"guesses":[]}
+        ]}}"#;
+        let puzzles = PuzzlePack::from_json(saved).unwrap();
+        assert_eq!(puzzles.len(), 2);
+        assert_eq!(puzzles[0].guesses_needed(), 3);
+
+        let text = PuzzlePack::to_json(&puzzles).unwrap();
+        let loaded = PuzzlePack::from_json(&text).unwrap();
+        assert_eq!(loaded[1].board().guesses().len(), 0);
+    }
+
+    #[test]
+    fn inconsistent_histories_are_rejected() {
+        let saved = r#"{"kind":"game","version":1,"data":{
+            "colors":6,"secret":[1,2,3,4],"max_guesses":null,
+            "guesses":[
+                {"guess":[0,0,1,1],"hits":0,"near_hits":1},
+                {"guess":[1,2,3,5],"hits":2,"near_hits":0}
+            ]
+        }}"#;
+        assert!(matches!(
+            Game::from_json(saved),
+            Err(SchemaError::InvalidHistory(1))
+        ));
+
+        let saved = r#"{"kind":"game","version":1,"data":{
+            "colors":3,"secret":[1,2,3,4],"max_guesses":null,"guesses":[]
+        }}"#;
+        assert!(matches!(Game::from_json(saved), Err(SchemaError::Build(_))));
+    }
+}
@@ -0,0 +1,100 @@
+// `Arbitrary` implementations for fuzzing and property tests. Everything
+// generated is valid: configurations always build, and games only contain
+// guesses they would have accepted.
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::{
+    challenge::Challenge, Difficulty, FeedbackMode, Game, GameBuilder,
+};
+
+impl<'a> Arbitrary<'a> for Difficulty {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        u.choose(&Difficulty::ALL).copied()
+    }
+}
+
+impl<'a> Arbitrary<'a> for Challenge {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Challenge::new(u.arbitrary()?, u.arbitrary()?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for FeedbackMode {
+    // Custom feedback can't be generated, so only the built-in modes are.
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=2)? {
+            0 => FeedbackMode::Standard,
+            1 => FeedbackMode::CountOnly,
+            _ => FeedbackMode::Positional,
+        })
+    }
+}
+
+// A seeded configuration with 1-6 pegs of 2-10 colors.
+impl<'a> Arbitrary<'a> for GameBuilder {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let builder = GameBuilder::new()
+            .peg_count(u.int_in_range(1..=6)?)
+            .peg_range(u.int_in_range(2..=10)?)
+            .feedback_mode(u.arbitrary()?)
+            .allow_empty(u.arbitrary()?)
+            .seed(u.arbitrary()?);
+
+        Ok(match u.int_in_range(0..=20)? {
+            0 => builder.unlimited_guesses(true),
+            max => builder.max_guesses(max),
+        })
+    }
+}
+
+// A game part way through (or at the end of) a history of valid guesses.
+impl<'a> Arbitrary<'a> for Game {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut game = GameBuilder::arbitrary(u)?.build();
+        while !game.is_over() && u.arbitrary()? {
+            let len = u.int_in_range(game.peg_counts())?;
+            let guess = (0..len)
+                .map(|_| u.choose(game.alphabet()).copied())
+                .collect::<Result<Vec<_>>>()?;
+            game.guess(&guess).unwrap();
+        }
+        Ok(game)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bytes(seed: u8) -> Vec<u8> {
+        (0..512u32)
+            .map(|i| (i as u8).wrapping_mul(seed) ^ seed)
+            .collect()
+    }
+
+    #[test]
+    fn arbitrary_games_are_valid() {
+        for seed in 0..50 {
+            let bytes = bytes(seed);
+            let game = Game::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+            for guess in game.guesses() {
+                assert!(game.peg_counts().contains(&(guess.len() as u8)));
+                assert!(guess.iter().all(|peg| game.alphabet().contains(peg)));
+            }
+            if let Some(max) = game.max_guesses() {
+                assert!(game.guesses().len() <= max as usize);
+            }
+        }
+    }
+
+    #[test]
+    fn arbitrary_challenges_round_trip() {
+        for seed in 0..20 {
+            let bytes = bytes(seed);
+            let mut u = Unstructured::new(&bytes);
+            let challenge = Challenge::arbitrary(&mut u).unwrap();
+            assert_eq!(challenge.code().parse(), Ok(challenge));
+        }
+    }
+}
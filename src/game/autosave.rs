@@ -0,0 +1,145 @@
+use super::{Game, Hint};
+
+// Everything needed to resume a game exactly where it left off, given a
+// builder with the same configuration. Seeded games record their seed instead
+// of the secret, leaving `pegs` and `revealed` empty.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Checkpoint {
+    pub pegs:      Vec<u8>,
+    pub revealed:  Vec<usize>,
+    pub guesses:   Vec<Vec<u8>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub hints:     Vec<Hint>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub seed:      Option<u64>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub forfeited: bool,
+}
+
+impl From<&Game> for Checkpoint {
+    fn from(game: &Game) -> Self {
+        let seeded = game.seed.is_some();
+        Self {
+            pegs:      if seeded {
+                Vec::new()
+            }
+            else {
+                game.pegs.clone()
+            },
+            revealed:  if seeded {
+                Vec::new()
+            }
+            else {
+                game.revealed.clone()
+            },
+            guesses:   game.guesses.clone(),
+            hints:     game.hints.clone(),
+            seed:      game.seed,
+            forfeited: game.forfeited,
+        }
+    }
+}
+
+// A persistence sink that receives a checkpoint after every change to a game.
+pub trait Autosave {
+    fn checkpoint(&mut self, checkpoint: &Checkpoint);
+}
+
+impl<F: FnMut(&Checkpoint)> Autosave for F {
+    fn checkpoint(&mut self, checkpoint: &Checkpoint) { self(checkpoint) }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::GameBuilder;
+
+    #[test]
+    fn checkpoints_are_written_after_every_guess() {
+        let saved = Arc::new(Mutex::new(Vec::new()));
+        let sink = saved.clone();
+
+        let mut game = GameBuilder::new()
+            .pegs(&[1, 2, 3, 4])
+            .max_guesses(2)
+            .autosave(move |checkpoint: &Checkpoint| {
+                sink.lock().unwrap().push(checkpoint.clone())
+            })
+            .build();
+        game.guess(&[0, 0, 0, 0]).unwrap();
+        game.guess(&[1, 1, 1, 1]).unwrap();
+        game.guess(&[2, 2, 2, 2]).unwrap_err();
+
+        let saved = saved.lock().unwrap();
+        assert_eq!(saved.len(), 2);
+        assert_eq!(saved[1].guesses, [[0, 0, 0, 0], [1, 1, 1, 1]]);
+        assert_eq!(saved[1].pegs, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn games_resume_from_checkpoints() {
+        let mut game = GameBuilder::new().build();
+        game.guess(&[0, 1, 2, 3]).unwrap();
+        game.guess(&[3, 2, 1, 0]).unwrap();
+        game.hint(crate::game::HintKind::RevealPosition).unwrap();
+        let checkpoint = Checkpoint::from(&game);
+
+        let resumed = GameBuilder::new().resume(&checkpoint);
+        assert_eq!(resumed.pegs(), game.pegs());
+        assert_eq!(resumed.guesses(), game.guesses());
+        assert_eq!(resumed.hits(1), game.hits(1));
+        assert_eq!(resumed.hints(), game.hints());
+        assert_eq!(Checkpoint::from(&resumed), checkpoint);
+    }
+
+    #[test]
+    fn forfeits_are_checkpointed() {
+        let saved = Arc::new(Mutex::new(None));
+        let sink = saved.clone();
+
+        let mut game = GameBuilder::new()
+            .autosave(move |checkpoint: &Checkpoint| {
+                *sink.lock().unwrap() = Some(checkpoint.clone())
+            })
+            .build();
+        game.guess(&[0, 1, 2, 3]).unwrap();
+        game.forfeit();
+
+        let checkpoint = saved.lock().unwrap().clone().unwrap();
+        assert!(checkpoint.forfeited);
+        let mut resumed = GameBuilder::new().resume(&checkpoint);
+        let secret = resumed.pegs().to_vec();
+        assert!(resumed.is_over());
+        assert_eq!(resumed.guess(&secret), Err(crate::GuessError::GameOver));
+    }
+
+    #[test]
+    fn seeded_games_checkpoint_their_seed() {
+        let builder = || {
+            GameBuilder::new().handicap(crate::Handicap {
+                bonus_guesses: 0,
+                revealed_pegs: 1,
+            })
+        };
+        let mut game = builder().seed_phrase("friday night").build();
+        game.guess(&[0, 1, 2, 3]).unwrap();
+
+        let checkpoint = game.checkpoint();
+        assert!(checkpoint.pegs.is_empty());
+        assert!(checkpoint.revealed.is_empty());
+        assert_eq!(checkpoint.seed, game.seed());
+
+        let resumed = builder().resume(&checkpoint);
+        assert_eq!(resumed.pegs(), game.pegs());
+        assert_eq!(resumed.revealed(), game.revealed());
+        assert_eq!(resumed.hits(0), game.hits(0));
+        assert_eq!(resumed.checkpoint(), checkpoint);
+
+        // Explicit pegs override the seed, so the game isn't seeded.
+        let game = GameBuilder::new().seed(1).pegs(&[1, 2, 3, 4]).build();
+        assert_eq!(game.seed(), None);
+    }
+}
@@ -1,20 +1,84 @@
-use rand::prelude::{thread_rng, Rng};
+use std::{ops::RangeInclusive, sync::Arc, time::Instant};
 
-use super::{Game, Variant};
+use rand::{
+    distributions::{Distribution, WeightedIndex},
+    prelude::{thread_rng, Rng, SeedableRng},
+    seq::index,
+    RngCore,
+};
+use rand_chacha::ChaCha8Rng;
+
+use super::{
+    fits_length, Autosave, Checkpoint, FeedbackMode, Game, Handicap,
+    HintChannel, Invariants, Joker, Rules, SecretConstraint, Telemetry,
+    Template, EMPTY,
+};
+use crate::solver::Solver;
+
+// Generated secrets are redrawn until the filter accepts them, giving up after
+// this many attempts.
+const MAX_SECRET_ATTEMPTS: usize = 10_000;
+
+pub type SecretFilter = Arc<dyn Fn(&[u8]) -> bool + Send + Sync>;
+
+// Configurations `try_build` refuses, because they would panic or give a game
+// that can't meaningfully be played.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildError {
+    // A peg range of 0 or 1, leaving nothing to deduce.
+    TooFewColors(u8),
+    NoPegs,
+    NoGuesses,
+    ConflictingPegCounts { count: u8, range: (u8, u8) },
+    EmptyPegCountRange(u8, u8),
+    SecretWrongLength(usize),
+    SecretNotInAlphabet(u8),
+    // Color weights must be one finite, non-negative weight per color in the
+    // alphabet, and can't all be zero.
+    InvalidColorWeights,
+    // No secret satisfying the filter and constraints could be found.
+    UnsatisfiableSecret,
+    // A joker in the guess that is also a color, which would turn that color
+    // into a wildcard.
+    JokerInAlphabet(u8),
+    // A custom `Variant` that isn't in the registry.
+    UnknownVariant(String),
+}
 
 #[derive(Default)]
 pub struct GameBuilder {
     pub pegs:              Option<Vec<u8>>,
     pub peg_range:         Option<u8>,
     pub peg_count:         Option<u8>,
+    pub peg_count_range:   Option<(u8, u8)>,
     pub max_guesses:       Option<u8>,
     pub unlimited_guesses: bool,
+    pub feedback_mode:     FeedbackMode,
+    pub secret_filter:     Option<SecretFilter>,
+    pub color_weights:     Option<Vec<f32>>,
+    pub constraints:       Vec<SecretConstraint>,
+    pub template:          Option<Template>,
+    pub winnable:          bool,
+    pub hint_budget:       Option<u32>,
+    pub joker:             Option<Joker>,
+    pub allow_empty:       bool,
+    pub hint_channels:     Vec<HintChannel>,
+    pub handicap:          Handicap,
+    pub autosave:          Option<Box<dyn Autosave + Send>>,
+    pub telemetry:         Option<Box<dyn Telemetry + Send>>,
+    pub seed:              Option<u64>,
+    pub check_invariants:  bool,
 }
 
 impl GameBuilder {
     pub fn new() -> Self { Self::default() }
     pub fn build(self) -> Game { self.into() }
 
+    pub fn try_build(self) -> Result<Game, BuildError> {
+        self.validate()?;
+        self.build_game()
+    }
+
     pub fn pegs(mut self, pegs: &[u8]) -> Self {
         self.pegs = pegs.to_owned().into();
         self
@@ -30,6 +94,13 @@ impl GameBuilder {
         self
     }
 
+    // Makes the secret's length unknown, somewhere between `min` and `max`
+    // inclusive. Guesses may then be any length in that range.
+    pub fn peg_count_range(mut self, min: u8, max: u8) -> Self {
+        self.peg_count_range = Some((min, max));
+        self
+    }
+
     pub fn max_guesses(mut self, guesses: u8) -> Self {
         self.max_guesses = Some(guesses);
         self
@@ -40,50 +111,383 @@ impl GameBuilder {
         self
     }
 
-    fn calculate_pegs(&self) -> Vec<u8> {
-        let peg_count = self.peg_count.unwrap_or(4);
+    pub fn feedback_mode(mut self, mode: FeedbackMode) -> Self {
+        self.feedback_mode = mode;
+        self
+    }
+
+    pub fn secret_filter<F>(mut self, filter: F) -> Self
+    where
+        F: Fn(&[u8]) -> bool + Send + Sync + 'static,
+    {
+        self.secret_filter = Some(Arc::new(filter));
+        self
+    }
+
+    pub fn constraint(mut self, constraint: SecretConstraint) -> Self {
+        self.constraints.push(constraint);
+        self
+    }
+
+    // Generates secrets in the shape of `template`. Unless a peg count is also
+    // given, secrets are as long as the template.
+    pub fn template(mut self, template: Template) -> Self {
+        self.template = Some(template);
+        self
+    }
+
+    // Only generates secrets the solver can crack within `max_guesses`, so
+    // every game can be won with good enough play.
+    pub fn winnable(mut self, winnable: bool) -> Self {
+        self.winnable = winnable;
+        self
+    }
+
+    // How likely each color in the alphabet is to appear in a generated
+    // secret, relative to the others. Weights are given in alphabet order.
+    pub fn color_weights(mut self, weights: &[f32]) -> Self {
+        self.color_weights = Some(weights.to_owned());
+        self
+    }
+
+    pub fn joker(mut self, joker: Joker) -> Self {
+        self.joker = Some(joker);
+        self
+    }
+
+    pub fn allow_empty(mut self, allow_empty: bool) -> Self {
+        self.allow_empty = allow_empty;
+        self
+    }
+
+    pub fn hint_channel(mut self, channel: HintChannel) -> Self {
+        if !self.hint_channels.contains(&channel) {
+            self.hint_channels.push(channel);
+        }
+        self
+    }
+
+    pub fn handicap(mut self, handicap: Handicap) -> Self {
+        self.handicap = handicap;
+        self
+    }
+
+    // Caps the total cost of the hints the codebreaker can ask for.
+    pub fn hint_budget(mut self, budget: u32) -> Self {
+        self.hint_budget = Some(budget);
+        self
+    }
+
+    pub fn autosave(mut self, sink: impl Autosave + Send + 'static) -> Self {
+        self.autosave = Some(Box::new(sink));
+        self
+    }
+
+    // Opts in to reporting a summary of the game to `sink` when it ends.
+    pub fn telemetry(mut self, sink: impl Telemetry + Send + 'static) -> Self {
+        self.telemetry = Some(Box::new(sink));
+        self
+    }
+
+    // Checks the game's internal consistency after every change, panicking on
+    // the first problem. Always on with the `invariants` feature.
+    pub fn check_invariants(mut self, check: bool) -> Self {
+        self.check_invariants = check;
+        self
+    }
+
+    // Makes the secret (and any handicap reveals) reproducible: builders with
+    // the same seed and configuration always build the same game.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    // Seeds the game from a phrase, so players can share a challenge
+    // ("friday night") without giving away the secret. Case and extra
+    // whitespace don't matter.
+    pub fn seed_phrase(self, phrase: &str) -> Self {
+        let phrase = phrase.split_whitespace().collect::<Vec<_>>().join(" ");
+        self.seed(phrase_seed(&phrase.to_lowercase()))
+    }
+
+    // Builds a game that carries on from `checkpoint`. The builder should
+    // be configured the same way as the one the checkpointed game came from.
+    // Seeded checkpoints have their secret regenerated from the seed rather
+    // than read from the checkpoint.
+    pub fn resume(self, checkpoint: &Checkpoint) -> Game {
+        let mut game = match checkpoint.seed {
+            Some(seed) => Self { pegs: None, ..self }.seed(seed).build(),
+            None => {
+                let mut game = self.pegs(&checkpoint.pegs).build();
+                game.revealed = checkpoint.revealed.clone();
+                game
+            }
+        };
+        game.hints = checkpoint.hints.clone();
+        game.guesses = checkpoint.guesses.clone();
+        game.forfeited = checkpoint.forfeited;
+        game.check_invariants();
+        game
+    }
+
+    fn validate(&self) -> Result<(), BuildError> {
+        let peg_counts = match (self.peg_count, self.peg_count_range) {
+            (Some(count), Some(range)) => {
+                return Err(BuildError::ConflictingPegCounts { count, range })
+            }
+            (_, Some((min, max))) if min > max => {
+                return Err(BuildError::EmptyPegCountRange(min, max))
+            }
+            _ => self.peg_counts(),
+        };
+
+        if *peg_counts.start() == 0 {
+            return Err(BuildError::NoPegs);
+        }
+        if self.max_guesses == Some(0) && !self.unlimited_guesses {
+            return Err(BuildError::NoGuesses);
+        }
+
+        if let Some(weights) = &self.color_weights {
+            let valid = weights.len() == self.alphabet().len()
+                && weights.iter().all(|w| w.is_finite() && *w >= 0.0)
+                && weights.iter().any(|w| *w > 0.0);
+            if !valid {
+                return Err(BuildError::InvalidColorWeights);
+            }
+        }
+        if let Some(Joker::InGuess(joker)) = self.joker {
+            if self.alphabet().contains(&joker) {
+                return Err(BuildError::JokerInAlphabet(joker));
+            }
+        }
+
+        let Some(pegs) = &self.pegs
+        else {
+            if let Some(range @ (0 | 1)) = self.peg_range {
+                return Err(BuildError::TooFewColors(range));
+            }
+            if let Some(template) = &self.template {
+                let alphabet = self.alphabet();
+                if let Some(peg) =
+                    template.fixed_pegs().find(|peg| !alphabet.contains(peg))
+                {
+                    return Err(BuildError::SecretNotInAlphabet(peg));
+                }
+            }
+            let (colors, max_len) = (self.alphabet().len(), *peg_counts.end());
+            return match self
+                .constraints
+                .iter()
+                .any(|c| c.is_impossible(colors, max_len))
+            {
+                true => Err(BuildError::UnsatisfiableSecret),
+                false => Ok(()),
+            };
+        };
+
+        if !fits_length(&peg_counts, pegs.len()) {
+            return Err(BuildError::SecretWrongLength(pegs.len()));
+        }
+        let alphabet = self.alphabet();
+        match pegs.iter().find(|peg| !alphabet.contains(peg)) {
+            Some(peg) => Err(BuildError::SecretNotInAlphabet(*peg)),
+            None => Ok(()),
+        }
+    }
+
+    // Every value a generated secret may contain.
+    fn alphabet(&self) -> Vec<u8> {
+        let mut alphabet = (0..self.peg_range.unwrap_or(6)).collect::<Vec<_>>();
+
+        if let Some(Joker::InSecret(joker)) = self.joker {
+            if !alphabet.contains(&joker) {
+                alphabet.push(joker);
+            }
+        }
+        if self.allow_empty {
+            alphabet.push(EMPTY);
+        }
+        alphabet
+    }
+
+    fn peg_counts(&self) -> RangeInclusive<u8> {
+        match (self.peg_count, self.peg_count_range) {
+            (Some(count), Some(range)) => panic!(
+                "Trying to build a Game with peg_count {} and peg_count_range \
+                 {:?}",
+                count, range
+            ),
+            (_, Some((min, max))) => min..=max,
+            (count, None) => {
+                // Too long a template is reported by `validate`.
+                let template = self
+                    .template
+                    .as_ref()
+                    .map(|t| u8::try_from(t.len()).unwrap_or(u8::MAX));
+                let count = count.or(template).unwrap_or(4);
+                count..=count
+            }
+        }
+    }
+
+    fn rng(&self) -> Box<dyn RngCore> {
+        match self.seed {
+            Some(seed) => Box::new(ChaCha8Rng::seed_from_u64(seed)),
+            None => Box::new(thread_rng()),
+        }
+    }
+
+    fn calculate_pegs(
+        &self,
+        rng: &mut dyn RngCore,
+    ) -> Result<Vec<u8>, BuildError> {
+        let peg_counts = self.peg_counts();
 
         if let Some(pegs) = &self.pegs {
-            if pegs.len() != peg_count as usize {
+            if !fits_length(&peg_counts, pegs.len()) {
                 panic!(
-                    "Trying to build a Game with pegs {:?} and peg_count {}",
-                    pegs, peg_count
+                    "Trying to build a Game with pegs {:?} and peg_count {:?}",
+                    pegs, peg_counts
                 );
             }
-            pegs.clone()
+            Ok(pegs.clone())
         }
         else {
-            let mut rng = thread_rng();
+            let alphabet = self.alphabet();
+            let weights = self.color_weights.as_ref().map(|weights| {
+                WeightedIndex::new(weights).expect("Invalid color weights")
+            });
+            let template = match &self.template {
+                Some(template) if !fits_length(&peg_counts, template.len()) => {
+                    return Err(BuildError::SecretWrongLength(template.len()))
+                }
+                Some(template) if !template.fits(&alphabet) => {
+                    return Err(BuildError::UnsatisfiableSecret)
+                }
+                template => template,
+            };
+            let mut generate = || {
+                if let Some(template) = template {
+                    return template.generate(&alphabet, rng);
+                }
+                (0..rng.gen_range(peg_counts.clone()))
+                    .map(|_| match &weights {
+                        Some(weights) => alphabet[weights.sample(rng)],
+                        None => alphabet[rng.gen_range(0..alphabet.len())],
+                    })
+                    .collect::<Vec<_>>()
+            };
 
-            (0..peg_count)
-                .map(|_| {
-                    let rand: u8 = rng.gen();
-                    rand % self.peg_range.unwrap_or(6)
-                })
-                .collect::<Vec<_>>()
+            let accepts = |pegs: &[u8]| {
+                self.secret_filter
+                    .as_ref()
+                    .is_none_or(|filter| filter(pegs))
+                    && self.constraints.iter().all(|c| c.accepts(pegs))
+                    && (!self.winnable || self.is_winnable(pegs))
+            };
+
+            (0..MAX_SECRET_ATTEMPTS)
+                .map(|_| generate())
+                .find(|pegs| accepts(pegs))
+                .ok_or(BuildError::UnsatisfiableSecret)
         }
     }
-}
 
-impl From<GameBuilder> for Game {
-    fn from(builder: GameBuilder) -> Self {
-        let max_guesses = {
-            if !builder.unlimited_guesses {
-                builder.max_guesses.or(Some(12))
-            }
-            else {
-                None
-            }
+    fn guess_limit(&self) -> Option<u8> {
+        if !self.unlimited_guesses {
+            self.max_guesses
+                .or(Some(12))
+                .map(|max| max.saturating_add(self.handicap.bonus_guesses))
+        }
+        else {
+            None
+        }
+    }
+
+    fn is_winnable(&self, pegs: &[u8]) -> bool {
+        let mut game = Game {
+            pegs:        pegs.to_owned(),
+            revealed:    Vec::new(),
+            hints:       Vec::new(),
+            handicap:    self.handicap,
+            guesses:     Vec::new(),
+            forfeited:   false,
+            max_guesses: self.guess_limit(),
+            hint_budget: None,
+            rules:       Rules {
+                feedback_mode: self.feedback_mode.clone(),
+                joker:         self.joker,
+                allow_empty:   self.allow_empty,
+                hint_channels: self.hint_channels.clone(),
+            },
+            peg_counts:  self.peg_counts(),
+            alphabet:    self.alphabet(),
+            seed:        None,
+            autosave:    None,
+            telemetry:   None,
+            started:     Instant::now(),
+            invariants:  None,
+            spectators:  Default::default(),
         };
+        Solver::new(&game).solve(&mut game).is_ok()
+    }
+
+    fn build_game(self) -> Result<Game, BuildError> {
+        let max_guesses = self.guess_limit();
+
+        let mut rng = self.rng();
+        let pegs = self.calculate_pegs(&mut rng)?;
+        let reveal = (self.handicap.revealed_pegs as usize).min(pegs.len());
+        let mut revealed =
+            index::sample(&mut rng, pegs.len(), reveal).into_vec();
+        revealed.sort_unstable();
 
-        Self {
-            pegs: builder.calculate_pegs(),
+        Ok(Game {
+            pegs,
+            revealed,
+            hints: Vec::new(),
+            handicap: self.handicap,
             guesses: Vec::new(),
+            forfeited: false,
             max_guesses,
-        }
+            hint_budget: self.hint_budget,
+            peg_counts: self.peg_counts(),
+            alphabet: self.alphabet(),
+            seed: self.seed.filter(|_| self.pegs.is_none()),
+            autosave: self.autosave,
+            telemetry: self.telemetry,
+            started: Instant::now(),
+            invariants: (self.check_invariants || cfg!(feature = "invariants"))
+                .then(Invariants::default),
+            spectators: Default::default(),
+            rules: Rules {
+                feedback_mode: self.feedback_mode,
+                joker:         self.joker,
+                allow_empty:   self.allow_empty,
+                hint_channels: self.hint_channels,
+            },
+        })
+    }
+}
+
+impl From<GameBuilder> for Game {
+    fn from(builder: GameBuilder) -> Self {
+        builder
+            .build_game()
+            .unwrap_or_else(|error| panic!("{}", error))
     }
 }
 
+// FNV-1a, which unlike the standard library's hasher is guaranteed to give
+// the same seed on every platform and Rust version.
+fn phrase_seed(phrase: &str) -> u64 {
+    phrase.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -96,6 +500,26 @@ mod tests {
         })
     }
 
+    #[test]
+    fn peg_count_range_is_respected() {
+        let lengths = (0..100)
+            .map(|_| GameBuilder::new().peg_count_range(3, 5).build())
+            .map(|game| game.pegs().len())
+            .collect::<Vec<_>>();
+
+        assert!(lengths.iter().all(|len| (3..=5).contains(len)));
+        (3..=5).for_each(|len| assert!(lengths.contains(&len)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_peg_count_and_peg_count_range() {
+        GameBuilder::new()
+            .peg_count(4)
+            .peg_count_range(3, 5)
+            .build();
+    }
+
     #[test]
     fn peg_range_is_respected() {
         // This has a small chance of failing even with correct implementations,
@@ -136,12 +560,338 @@ mod tests {
         assert_eq!(game.max_guesses.unwrap(), 12);
     }
 
+    #[test]
+    fn handicap_is_respected() {
+        let game = GameBuilder::new()
+            .max_guesses(8)
+            .handicap(Handicap {
+                bonus_guesses: 2,
+                revealed_pegs: 1,
+            })
+            .build();
+        assert_eq!(game.max_guesses.unwrap(), 10);
+        assert_eq!(game.revealed().len(), 1);
+
+        let (position, peg) = game.revealed()[0];
+        assert_eq!(game.pegs()[position], peg);
+
+        let game = GameBuilder::new()
+            .handicap(Handicap {
+                bonus_guesses: 0,
+                revealed_pegs: 9,
+            })
+            .build();
+        assert_eq!(game.revealed().len(), 4);
+    }
+
     #[test]
     fn unlimited_guesses_is_respected() {
         let game = GameBuilder::new().unlimited_guesses(true).build();
         assert!(game.max_guesses.is_none());
     }
 
+    #[test]
+    fn feedback_mode_is_respected() {
+        let game = GameBuilder::new().build();
+        assert_eq!(game.rules().feedback_mode, FeedbackMode::Standard);
+
+        let game = GameBuilder::new()
+            .feedback_mode(FeedbackMode::CountOnly)
+            .build();
+        assert_eq!(game.rules().feedback_mode, FeedbackMode::CountOnly);
+    }
+
+    #[test]
+    fn joker_is_respected() {
+        let game = GameBuilder::new().joker(Joker::InGuess(6)).build();
+        assert_eq!(game.rules().joker, Some(Joker::InGuess(6)));
+        assert!(game.pegs().iter().all(|peg| *peg < 6));
+
+        // A joker in the secret should be able to appear in generated pegs.
+        let game = GameBuilder::new()
+            .peg_count(255)
+            .joker(Joker::InSecret(6))
+            .build();
+        assert!(game.pegs().iter().all(|peg| *peg <= 6));
+        assert!(game.pegs().contains(&6));
+    }
+
+    #[test]
+    fn guess_jokers_must_not_be_colors() {
+        let error = |joker, allow_empty| {
+            GameBuilder::new()
+                .joker(joker)
+                .allow_empty(allow_empty)
+                .try_build()
+                .err()
+        };
+        assert_eq!(
+            error(Joker::InGuess(5), false),
+            Some(BuildError::JokerInAlphabet(5))
+        );
+        assert_eq!(
+            error(Joker::InGuess(EMPTY), true),
+            Some(BuildError::JokerInAlphabet(EMPTY))
+        );
+        assert_eq!(error(Joker::InGuess(EMPTY), false), None);
+        assert_eq!(error(Joker::InSecret(5), false), None);
+    }
+
+    #[test]
+    fn alphabet_is_recorded() {
+        let game = GameBuilder::new().peg_range(3).build();
+        assert_eq!(game.alphabet(), [0, 1, 2]);
+
+        let game = GameBuilder::new()
+            .peg_range(3)
+            .joker(Joker::InSecret(7))
+            .allow_empty(true)
+            .build();
+        assert_eq!(game.alphabet(), [0, 1, 2, 7, EMPTY]);
+    }
+
+    #[test]
+    fn allow_empty_is_respected() {
+        let game = GameBuilder::new().peg_count(255).build();
+        assert!(!game.rules().allow_empty);
+        assert!(!game.pegs().contains(&EMPTY));
+
+        let game = GameBuilder::new().peg_count(255).allow_empty(true).build();
+        assert!(game.rules().allow_empty);
+        assert!(game.pegs().contains(&EMPTY));
+        assert!(game.pegs().iter().all(|peg| *peg < 6 || *peg == EMPTY));
+    }
+
+    #[test]
+    fn secret_filter_is_respected() {
+        for _ in 0..20 {
+            let game = GameBuilder::new()
+                .secret_filter(|pegs| pegs.iter().all(|peg| peg % 2 == 0))
+                .build();
+            assert!(game.pegs().iter().all(|peg| peg % 2 == 0));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_unsatisfiable_secret_filter() {
+        GameBuilder::new().secret_filter(|_| false).build();
+    }
+
+    #[test]
+    fn seeds_are_reproducible() {
+        let build = |seed| {
+            GameBuilder::new()
+                .peg_count(8)
+                .handicap(Handicap {
+                    bonus_guesses: 0,
+                    revealed_pegs: 2,
+                })
+                .seed(seed)
+                .build()
+        };
+        assert_eq!(build(7).pegs(), build(7).pegs());
+        assert_eq!(build(7).revealed(), build(7).revealed());
+        assert_ne!(build(7).pegs(), build(8).pegs());
+    }
+
+    #[test]
+    fn seed_phrases_ignore_case_and_spacing() {
+        let build = |phrase| {
+            GameBuilder::new().peg_count(8).seed_phrase(phrase).build()
+        };
+        assert_eq!(
+            build("friday night").pegs(),
+            build("  Friday   NIGHT ").pegs()
+        );
+        assert_ne!(build("friday night").pegs(), build("saturday").pegs());
+        assert_eq!(phrase_seed(""), 0xcbf29ce484222325);
+        assert_eq!(phrase_seed("a"), 0xaf63dc4c8601ec8c);
+    }
+
+    #[test]
+    fn color_weights_are_respected() {
+        let game = GameBuilder::new()
+            .peg_count(255)
+            .peg_range(3)
+            .color_weights(&[1.0, 0.0, 3.0])
+            .seed(1)
+            .build();
+        let count = |color| game.pegs().iter().filter(|p| **p == color).count();
+
+        assert_eq!(count(1), 0);
+        // Expected counts are about 64 and 191.
+        assert!((40..90).contains(&count(0)));
+        assert!((165..215).contains(&count(2)));
+    }
+
+    #[test]
+    fn invalid_color_weights_are_rejected() {
+        let error = |weights: &[f32]| {
+            GameBuilder::new()
+                .peg_range(3)
+                .color_weights(weights)
+                .try_build()
+                .err()
+        };
+        let invalid = Some(BuildError::InvalidColorWeights);
+
+        assert_eq!(error(&[1.0, 1.0]), invalid);
+        assert_eq!(error(&[1.0, -1.0, 1.0]), invalid);
+        assert_eq!(error(&[1.0, f32::NAN, 1.0]), invalid);
+        assert_eq!(error(&[0.0, 0.0, 0.0]), invalid);
+        assert_eq!(error(&[0.0, 0.5, 2.0]), None);
+    }
+
+    #[test]
+    fn constraints_are_respected() {
+        for _ in 0..20 {
+            let game = GameBuilder::new()
+                .peg_count(6)
+                .constraint(SecretConstraint::NoAdjacentRepeats)
+                .constraint(SecretConstraint::MinDistinctColors(5))
+                .build();
+            let pegs = game.pegs();
+            assert!(pegs.windows(2).all(|w| w[0] != w[1]));
+        }
+    }
+
+    #[test]
+    fn unsatisfiable_constraints_are_errors() {
+        let error = GameBuilder::new()
+            .constraint(SecretConstraint::MinDistinctColors(5))
+            .try_build()
+            .err();
+        assert_eq!(error, Some(BuildError::UnsatisfiableSecret));
+
+        let error = GameBuilder::new()
+            .constraint(SecretConstraint::custom(|pegs| pegs.len() > 4))
+            .try_build()
+            .err();
+        assert_eq!(error, Some(BuildError::UnsatisfiableSecret));
+    }
+
+    #[test]
+    fn template_is_respected() {
+        let template = "A?AB".parse::<Template>().unwrap();
+        for _ in 0..20 {
+            let game = GameBuilder::new().template(template.clone()).build();
+            assert!(template.matches(game.pegs()));
+        }
+
+        let game = GameBuilder::new()
+            .template("AB?1BA".parse().unwrap())
+            .build();
+        assert_eq!(game.peg_counts(), 6..=6);
+    }
+
+    #[test]
+    fn invalid_templates_are_errors() {
+        let error = |builder: GameBuilder, template: &str| {
+            builder
+                .template(template.parse().unwrap())
+                .try_build()
+                .err()
+        };
+        assert_eq!(
+            error(GameBuilder::new().peg_count(4), "AAB"),
+            Some(BuildError::SecretWrongLength(3))
+        );
+        assert_eq!(
+            error(GameBuilder::new().peg_count(4), &"A".repeat(260)),
+            Some(BuildError::SecretWrongLength(260))
+        );
+        assert_eq!(
+            error(GameBuilder::new().peg_range(2), "ABC"),
+            Some(BuildError::UnsatisfiableSecret)
+        );
+        assert_eq!(
+            error(GameBuilder::new(), "A7?"),
+            Some(BuildError::SecretNotInAlphabet(7))
+        );
+    }
+
+    #[test]
+    fn winnable_games_are_winnable() {
+        let game = GameBuilder::new().max_guesses(5).winnable(true).build();
+        let mut probe =
+            GameBuilder::new().pegs(game.pegs()).max_guesses(5).build();
+        assert!(Solver::new(&probe).solve(&mut probe).is_ok());
+
+        // With one guess, only the solver's opening guess can be won.
+        let game = GameBuilder::new()
+            .peg_range(2)
+            .peg_count(2)
+            .max_guesses(1)
+            .winnable(true)
+            .build();
+        assert_eq!(game.pegs(), [0, 0]);
+    }
+
+    #[test]
+    fn try_build_rejects_degenerate_configurations() {
+        let error = |builder: GameBuilder| builder.try_build().err();
+
+        assert_eq!(
+            error(GameBuilder::new().peg_range(0)),
+            Some(BuildError::TooFewColors(0))
+        );
+        assert_eq!(
+            error(GameBuilder::new().peg_range(1)),
+            Some(BuildError::TooFewColors(1))
+        );
+        assert_eq!(
+            error(GameBuilder::new().peg_count(0)),
+            Some(BuildError::NoPegs)
+        );
+        assert_eq!(
+            error(GameBuilder::new().peg_count_range(0, 3)),
+            Some(BuildError::NoPegs)
+        );
+        assert_eq!(
+            error(GameBuilder::new().peg_count_range(5, 3)),
+            Some(BuildError::EmptyPegCountRange(5, 3))
+        );
+        assert_eq!(
+            error(GameBuilder::new().max_guesses(0)),
+            Some(BuildError::NoGuesses)
+        );
+        assert_eq!(
+            error(GameBuilder::new().peg_count(4).peg_count_range(3, 5)),
+            Some(BuildError::ConflictingPegCounts {
+                count: 4,
+                range: (3, 5),
+            })
+        );
+        assert_eq!(
+            error(GameBuilder::new().pegs(&[1, 2, 3])),
+            Some(BuildError::SecretWrongLength(3))
+        );
+        assert_eq!(
+            error(GameBuilder::new().pegs(&[1; 260]).peg_count(4)),
+            Some(BuildError::SecretWrongLength(260))
+        );
+        assert_eq!(
+            error(GameBuilder::new().pegs(&[1, 2, 3, 7])),
+            Some(BuildError::SecretNotInAlphabet(7))
+        );
+    }
+
+    #[test]
+    fn try_build_accepts_sensible_configurations() {
+        assert!(GameBuilder::new().try_build().is_ok());
+        assert!(GameBuilder::new()
+            .max_guesses(0)
+            .unlimited_guesses(true)
+            .try_build()
+            .is_ok());
+        assert!(GameBuilder::new()
+            .pegs(&[0, EMPTY, 0, 0])
+            .allow_empty(true)
+            .try_build()
+            .is_ok());
+    }
+
     #[test]
     #[should_panic]
     fn panics_for_wrong_number_of_pins() {
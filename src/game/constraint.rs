@@ -0,0 +1,85 @@
+use std::fmt;
+
+use super::SecretFilter;
+
+// A rule every generated secret must follow. Secrets are redrawn until they
+// satisfy all of a builder's constraints.
+#[derive(Clone)]
+pub enum SecretConstraint {
+    NoAdjacentRepeats,
+    MinDistinctColors(u8),
+    Custom(SecretFilter),
+}
+
+impl SecretConstraint {
+    pub fn custom<F>(f: F) -> Self
+    where
+        F: Fn(&[u8]) -> bool + Send + Sync + 'static,
+    {
+        Self::Custom(std::sync::Arc::new(f))
+    }
+
+    pub fn accepts(&self, pegs: &[u8]) -> bool {
+        match self {
+            Self::NoAdjacentRepeats => pegs.windows(2).all(|w| w[0] != w[1]),
+            Self::MinDistinctColors(k) => {
+                let mut colors = pegs.to_owned();
+                colors.sort_unstable();
+                colors.dedup();
+                colors.len() >= *k as usize
+            }
+            Self::Custom(f) => f(pegs),
+        }
+    }
+
+    // Whether no secret could ever satisfy this constraint, given the
+    // alphabet size and the longest secret allowed.
+    pub(super) fn is_impossible(&self, colors: usize, max_len: u8) -> bool {
+        match self {
+            Self::NoAdjacentRepeats => colors < 2 && max_len > 1,
+            Self::MinDistinctColors(k) => *k as usize > colors || *k > max_len,
+            Self::Custom(_) => false,
+        }
+    }
+}
+
+impl fmt::Debug for SecretConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoAdjacentRepeats => f.write_str("NoAdjacentRepeats"),
+            Self::MinDistinctColors(k) => {
+                f.debug_tuple("MinDistinctColors").field(k).finish()
+            }
+            Self::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constraints_accept_matching_secrets() {
+        let no_repeats = SecretConstraint::NoAdjacentRepeats;
+        assert!(no_repeats.accepts(&[1, 2, 1, 2]));
+        assert!(!no_repeats.accepts(&[1, 2, 2, 3]));
+
+        let distinct = SecretConstraint::MinDistinctColors(3);
+        assert!(distinct.accepts(&[1, 2, 1, 3]));
+        assert!(!distinct.accepts(&[1, 2, 1, 2]));
+
+        let even = SecretConstraint::custom(|pegs| pegs[0] % 2 == 0);
+        assert!(even.accepts(&[2, 1]));
+        assert!(!even.accepts(&[1, 2]));
+    }
+
+    #[test]
+    fn impossible_constraints_are_detected() {
+        assert!(SecretConstraint::NoAdjacentRepeats.is_impossible(1, 4));
+        assert!(!SecretConstraint::NoAdjacentRepeats.is_impossible(1, 1));
+        assert!(SecretConstraint::MinDistinctColors(5).is_impossible(6, 4));
+        assert!(SecretConstraint::MinDistinctColors(5).is_impossible(4, 6));
+        assert!(!SecretConstraint::MinDistinctColors(4).is_impossible(6, 4));
+    }
+}
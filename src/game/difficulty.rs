@@ -0,0 +1,95 @@
+use super::{Game, GameBuilder, SecretConstraint};
+
+// A simple difficulty picker for frontends. Unlike a `Variant`, which changes
+// how the game is played, a difficulty only tunes a classic game: how big the
+// board is, how many guesses and hints the codebreaker gets, and whether the
+// secret can repeat colors.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Medium,
+    Hard,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Self::Easy, Self::Medium, Self::Hard];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "easy" => Some(Self::Easy),
+            "medium" => Some(Self::Medium),
+            "hard" => Some(Self::Hard),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Easy => "easy",
+            Self::Medium => "medium",
+            Self::Hard => "hard",
+        }
+    }
+}
+
+impl From<Difficulty> for GameBuilder {
+    fn from(difficulty: Difficulty) -> Self {
+        match difficulty {
+            Difficulty::Easy => GameBuilder::new()
+                .peg_count(4)
+                .peg_range(6)
+                .max_guesses(12)
+                .hint_budget(6)
+                .constraint(SecretConstraint::MinDistinctColors(4)),
+            Difficulty::Medium => GameBuilder::new()
+                .peg_count(4)
+                .peg_range(6)
+                .max_guesses(10)
+                .hint_budget(3),
+            Difficulty::Hard => GameBuilder::new()
+                .peg_count(5)
+                .peg_range(8)
+                .max_guesses(10)
+                .hint_budget(0),
+        }
+    }
+}
+
+impl From<Difficulty> for Game {
+    fn from(difficulty: Difficulty) -> Self {
+        GameBuilder::from(difficulty).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::HintKind;
+
+    #[test]
+    fn names_round_trip() {
+        for difficulty in Difficulty::ALL {
+            assert_eq!(
+                Difficulty::from_name(difficulty.name()),
+                Some(difficulty)
+            );
+        }
+        assert_eq!(Difficulty::from_name("HARD"), Some(Difficulty::Hard));
+        assert_eq!(Difficulty::from_name("nightmare"), None);
+    }
+
+    #[test]
+    fn presets_get_harder() {
+        let easy = Game::from(Difficulty::Easy);
+        let mut colors = easy.pegs().to_vec();
+        colors.sort_unstable();
+        colors.dedup();
+        assert_eq!(colors.len(), 4);
+
+        let mut hard = Game::from(Difficulty::Hard);
+        assert_eq!(hard.pegs().len(), 5);
+        assert!(hard.max_guesses() < easy.max_guesses());
+        assert!(hard.hint(HintKind::EliminateColor).is_none());
+    }
+}
@@ -0,0 +1,138 @@
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+#[cfg(feature = "stream")]
+use futures_channel::mpsc::{unbounded, UnboundedSender};
+#[cfg(feature = "stream")]
+use futures_core::Stream;
+
+use super::{Game, Hint};
+
+// What spectators see of a game. Nothing here gives away the secret until the
+// game is over.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GameEvent {
+    Guess {
+        index:     usize,
+        guess:     Vec<u8>,
+        hits:      u8,
+        near_hits: u8,
+    },
+    Hint(Hint),
+    // The game went back to an earlier snapshot, leaving this many guesses.
+    Restored {
+        guesses: usize,
+    },
+    Over {
+        solved: bool,
+        secret: Vec<u8>,
+    },
+}
+
+enum Spectator {
+    Channel(Sender<GameEvent>),
+    #[cfg(feature = "stream")]
+    Stream(UnboundedSender<GameEvent>),
+}
+
+#[derive(Default)]
+pub(super) struct Spectators(Vec<Spectator>);
+
+impl Spectators {
+    // Sends to every spectator, forgetting any that have hung up.
+    pub(super) fn send(&mut self, event: GameEvent) {
+        self.0.retain(|spectator| match spectator {
+            Spectator::Channel(sender) => sender.send(event.clone()).is_ok(),
+            #[cfg(feature = "stream")]
+            Spectator::Stream(sender) => {
+                sender.unbounded_send(event.clone()).is_ok()
+            }
+        });
+    }
+}
+
+impl Game {
+    // A read-only feed of everything that happens in the game from now on.
+    // Any number of spectators can follow the same game.
+    pub fn spectate(&mut self) -> Receiver<GameEvent> {
+        let (sender, receiver) = channel();
+        self.spectators.0.push(Spectator::Channel(sender));
+        receiver
+    }
+
+    // Like `spectate`, but as an async stream for use in async runtimes.
+    #[cfg(feature = "stream")]
+    pub fn events(&mut self) -> impl Stream<Item = GameEvent> + Send + Unpin {
+        let (sender, receiver) = unbounded();
+        self.spectators.0.push(Spectator::Stream(sender));
+        receiver
+    }
+
+    pub(super) fn broadcast(&mut self, event: GameEvent) {
+        self.spectators.send(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{game::HintKind, GameBuilder};
+
+    #[test]
+    fn spectators_follow_the_game() {
+        let mut game = GameBuilder::new().pegs(&[1, 2, 3, 4]).build();
+        let early = game.spectate();
+
+        game.guess(&[0, 0, 1, 1]).unwrap();
+        let snapshot = game.snapshot();
+        let late = game.spectate();
+        game.hint(HintKind::RevealPosition).unwrap();
+        game.restore(&snapshot);
+        game.guess(&[1, 2, 3, 4]).unwrap();
+
+        let events = early.try_iter().collect::<Vec<_>>();
+        assert_eq!(events.len(), 5);
+        assert_eq!(
+            events[0],
+            GameEvent::Guess {
+                index:     0,
+                guess:     vec![0, 0, 1, 1],
+                hits:      0,
+                near_hits: 1,
+            }
+        );
+        assert!(matches!(events[1], GameEvent::Hint(Hint::Position { .. })));
+        assert_eq!(events[2], GameEvent::Restored { guesses: 1 });
+        assert_eq!(
+            events[4],
+            GameEvent::Over {
+                solved: true,
+                secret: vec![1, 2, 3, 4],
+            }
+        );
+
+        assert_eq!(late.try_iter().count(), 4);
+    }
+
+    #[cfg(feature = "stream")]
+    #[test]
+    fn events_stream_asynchronously() {
+        use futures::{executor::block_on, StreamExt};
+
+        let mut game = GameBuilder::new().pegs(&[1, 2, 3, 4]).build();
+        let events = game.events();
+        game.guess(&[1, 2, 3, 4]).unwrap();
+        drop(game);
+
+        let events = block_on(events.collect::<Vec<_>>());
+        assert_eq!(events.len(), 2);
+        assert!(matches!(events[1], GameEvent::Over { solved: true, .. }));
+    }
+
+    #[test]
+    fn disconnected_spectators_are_dropped() {
+        let mut game = GameBuilder::new().pegs(&[1, 2, 3, 4]).build();
+        drop(game.spectate());
+        game.guess(&[0, 0, 0, 0]).unwrap();
+        assert!(game.spectators.0.is_empty());
+    }
+}
@@ -0,0 +1,63 @@
+use std::ops::RangeInclusive;
+
+use super::{Game, GuessError};
+
+// The interface every single-board game shares, kept object-safe so frontends
+// can hold a `Box<dyn GamePlay>` and drive any kind of game the same way.
+// Games with a board per player, like `Duel` and `Relay`, aren't one board:
+// they hand out their boards as `Game`s and `Coop`s instead.
+pub trait GamePlay {
+    fn guess(&mut self, guess: &[u8]) -> Result<(u8, u8), GuessError>;
+    fn forfeit(&mut self);
+
+    fn guesses(&self) -> &[Vec<u8>];
+    // The feedback given for the guess at `index`.
+    fn feedback(&self, index: usize) -> Option<(u8, u8)>;
+    fn max_guesses(&self) -> Option<u8>;
+    fn peg_counts(&self) -> RangeInclusive<u8>;
+    fn alphabet(&self) -> &[u8];
+    fn is_over(&self) -> bool;
+    fn is_solved(&self) -> bool;
+}
+
+impl GamePlay for Game {
+    fn guess(&mut self, guess: &[u8]) -> Result<(u8, u8), GuessError> {
+        Game::guess(self, guess)
+    }
+    fn forfeit(&mut self) { Game::forfeit(self) }
+
+    fn guesses(&self) -> &[Vec<u8>] { Game::guesses(self) }
+    fn feedback(&self, index: usize) -> Option<(u8, u8)> { self.hits(index) }
+    fn max_guesses(&self) -> Option<u8> { Game::max_guesses(self) }
+    fn peg_counts(&self) -> RangeInclusive<u8> { Game::peg_counts(self) }
+    fn alphabet(&self) -> &[u8] { Game::alphabet(self) }
+    fn is_over(&self) -> bool { Game::is_over(self) }
+    fn is_solved(&self) -> bool { Game::is_solved(self) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GameBuilder, Variant};
+
+    #[test]
+    fn games_can_be_played_through_the_trait() {
+        let mut games: Vec<Box<dyn GamePlay>> = vec![
+            Box::new(GameBuilder::new().pegs(&[1, 2, 3, 4]).build()),
+            Box::new(
+                GameBuilder::try_from(Variant::Number)
+                    .unwrap()
+                    .pegs(&[1, 2, 3, 4])
+                    .build(),
+            ),
+        ];
+
+        for game in &mut games {
+            assert_eq!(game.guess(&[1, 2, 4, 3]), Ok((2, 2)));
+            assert_eq!(game.feedback(0), Some((2, 2)));
+            game.forfeit();
+            assert!(game.is_over());
+            assert!(!game.is_solved());
+        }
+    }
+}
@@ -0,0 +1,234 @@
+use rand::{prelude::thread_rng, seq::SliceRandom, RngCore};
+
+use super::{Game, GameEvent};
+use crate::streams::Streams;
+
+// The kinds of hint a codebreaker can ask for, from most to least revealing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HintKind {
+    RevealPosition,
+    EliminateColor,
+    ConfirmColorPresent,
+    ParityOfColorCount,
+}
+
+impl HintKind {
+    pub const ALL: [HintKind; 4] = [
+        HintKind::RevealPosition,
+        HintKind::EliminateColor,
+        HintKind::ConfirmColorPresent,
+        HintKind::ParityOfColorCount,
+    ];
+
+    // A suggested price for the hint, in guesses. Scoring functions are free
+    // to weigh hints differently; see `scoring::Round::hints`.
+    pub fn cost(&self) -> u8 {
+        match self {
+            Self::RevealPosition => 3,
+            Self::EliminateColor => 1,
+            Self::ConfirmColorPresent => 1,
+            Self::ParityOfColorCount => 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Hint {
+    Position { position: usize, peg: u8 },
+    Absent(u8),
+    Present(u8),
+    ColorCountEven { peg: u8, even: bool },
+}
+
+impl Hint {
+    pub fn kind(&self) -> HintKind {
+        match self {
+            Self::Position { .. } => HintKind::RevealPosition,
+            Self::Absent(_) => HintKind::EliminateColor,
+            Self::Present(_) => HintKind::ConfirmColorPresent,
+            Self::ColorCountEven { .. } => HintKind::ParityOfColorCount,
+        }
+    }
+}
+
+impl Game {
+    // Gives a hint of the requested kind that the codebreaker doesn't already
+    // have, or `None` if every such hint has been given or the hint would go
+    // over the game's hint budget.
+    pub fn hint(&mut self, kind: HintKind) -> Option<Hint> {
+        let cost = self.hint_cost() + kind.cost() as u32;
+        if self.hint_budget.is_some_and(|budget| cost > budget) {
+            return None;
+        }
+        let hint = *self.possible_hints(kind).choose(&mut self.hint_rng())?;
+        self.hints.push(hint);
+        self.save();
+        self.check_invariants();
+        self.broadcast(GameEvent::Hint(hint));
+        Some(hint)
+    }
+
+    pub fn hints(&self) -> &[Hint] { &self.hints }
+
+    // The most the codebreaker may spend on hints, if it's limited.
+    pub fn hint_budget(&self) -> Option<u32> { self.hint_budget }
+
+    // The total cost of every hint given so far.
+    pub fn hint_cost(&self) -> u32 {
+        self.hints
+            .iter()
+            .map(|hint| hint.kind().cost() as u32)
+            .sum()
+    }
+
+    // Seeded games draw each hint from its own stream of the seed, so the
+    // same challenge gives the same hints in the same order. Stream 0 is the
+    // one the secret was generated from.
+    fn hint_rng(&self) -> Box<dyn RngCore> {
+        match self.seed {
+            Some(seed) => {
+                let index = self.hints.len() as u64 + 1;
+                Box::new(Streams::new(seed).stream(index))
+            }
+            None => Box::new(thread_rng()),
+        }
+    }
+
+    fn possible_hints(&self, kind: HintKind) -> Vec<Hint> {
+        let count = |peg| self.pegs.iter().filter(|&&p| p == peg).count();
+
+        let hints = match kind {
+            HintKind::RevealPosition => (0..self.pegs.len())
+                .filter(|position| !self.revealed.contains(position))
+                .map(|position| Hint::Position {
+                    position,
+                    peg: self.pegs[position],
+                })
+                .collect(),
+            HintKind::EliminateColor => self
+                .alphabet
+                .iter()
+                .filter(|&&peg| count(peg) == 0)
+                .map(|&peg| Hint::Absent(peg))
+                .collect(),
+            HintKind::ConfirmColorPresent => self
+                .alphabet
+                .iter()
+                .filter(|&&peg| count(peg) > 0)
+                .map(|&peg| Hint::Present(peg))
+                .collect(),
+            HintKind::ParityOfColorCount => self
+                .alphabet
+                .iter()
+                .map(|&peg| Hint::ColorCountEven {
+                    peg,
+                    even: count(peg) % 2 == 0,
+                })
+                .collect::<Vec<_>>(),
+        };
+
+        hints
+            .into_iter()
+            .filter(|hint| !self.hints.contains(hint))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GameBuilder;
+
+    #[test]
+    fn hints_are_true_and_not_repeated() {
+        let mut game = GameBuilder::new().pegs(&[0, 0, 1, 2]).build();
+
+        for _ in 0..3 {
+            match game.hint(HintKind::EliminateColor) {
+                Some(Hint::Absent(peg)) => assert!((3..6).contains(&peg)),
+                hint => panic!("unexpected hint {:?}", hint),
+            }
+        }
+        assert_eq!(game.hint(HintKind::EliminateColor), None);
+
+        for _ in 0..4 {
+            match game.hint(HintKind::RevealPosition) {
+                Some(Hint::Position { position, peg }) => {
+                    assert_eq!(game.pegs()[position], peg)
+                }
+                hint => panic!("unexpected hint {:?}", hint),
+            }
+        }
+        assert_eq!(game.hint(HintKind::RevealPosition), None);
+        assert_eq!(game.hints().len(), 7);
+        assert_eq!(game.hint_cost(), 3 + 4 * 3);
+    }
+
+    #[test]
+    fn seeded_games_give_the_same_hints() {
+        let hints = || {
+            let mut game = GameBuilder::new().peg_range(8).seed(42).build();
+            [HintKind::EliminateColor, HintKind::RevealPosition]
+                .repeat(3)
+                .into_iter()
+                .map(|kind| game.hint(kind))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(hints(), hints());
+    }
+
+    #[test]
+    fn color_hints() {
+        let mut game =
+            GameBuilder::new().pegs(&[0, 0, 0, 0]).peg_range(1).build();
+        assert_eq!(
+            game.hint(HintKind::ConfirmColorPresent),
+            Some(Hint::Present(0))
+        );
+        assert_eq!(
+            game.hint(HintKind::ParityOfColorCount),
+            Some(Hint::ColorCountEven {
+                peg:  0,
+                even: true,
+            })
+        );
+        assert_eq!(game.hint(HintKind::ParityOfColorCount), None);
+    }
+
+    #[test]
+    fn hint_budget_is_respected() {
+        let mut game = GameBuilder::new()
+            .pegs(&[0, 1, 2, 3])
+            .hint_budget(4)
+            .build();
+        assert_eq!(game.hint_budget(), Some(4));
+
+        assert!(game.hint(HintKind::RevealPosition).is_some());
+        assert!(game.hint(HintKind::ParityOfColorCount).is_none());
+        assert!(game.hint(HintKind::EliminateColor).is_some());
+        assert!(game.hint(HintKind::EliminateColor).is_none());
+        assert_eq!(game.hint_cost(), 4);
+    }
+
+    #[test]
+    fn handicap_reveals_are_not_hinted_again() {
+        let mut game = GameBuilder::new()
+            .pegs(&[1, 2, 3, 4])
+            .handicap(crate::Handicap {
+                bonus_guesses: 0,
+                revealed_pegs: 3,
+            })
+            .build();
+        let hidden =
+            (0..4).find(|i| !game.revealed().iter().any(|r| r.0 == *i));
+        assert_eq!(
+            game.hint(HintKind::RevealPosition),
+            Some(Hint::Position {
+                position: hidden.unwrap(),
+                peg:      game.pegs()[hidden.unwrap()],
+            })
+        );
+    }
+}
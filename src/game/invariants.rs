@@ -0,0 +1,178 @@
+use super::{FeedbackMode, Game, Joker};
+use crate::knowledge;
+
+// Games with more possible codes than this skip the candidate count check,
+// which would otherwise dominate every move.
+const MAX_COUNTED_CODES: usize = 4096;
+
+// Consistency checks run after every change to a game, enabled per game with
+// `GameBuilder::check_invariants` or for every game with the `invariants`
+// feature. A failed check means a bug in the engine (or in custom feedback),
+// so it panics rather than returning an error.
+#[derive(Debug, Clone, Default)]
+pub(super) struct Invariants {
+    // How many codes were still possible at the last check.
+    candidates: Option<usize>,
+    // Each guess checked so far, with the feedback it had when first seen.
+    feedback:   Vec<(Vec<u8>, (u8, u8))>,
+}
+
+impl Game {
+    pub(super) fn check_invariants(&mut self) {
+        let Some(mut invariants) = self.invariants.take()
+        else {
+            return;
+        };
+
+        if let Some(max) = self.max_guesses {
+            assert!(
+                self.guesses.len() <= max as usize,
+                "{} guesses made, but only {} allowed",
+                self.guesses.len(),
+                max
+            );
+        }
+        for &position in &self.revealed {
+            assert!(position < self.pegs.len(), "revealed a missing peg");
+        }
+
+        // Only guesses still on the board keep their recorded feedback.
+        let kept = invariants
+            .feedback
+            .iter()
+            .zip(&self.guesses)
+            .take_while(|((seen, _), guess)| seen == *guess)
+            .count();
+        invariants.feedback.truncate(kept);
+
+        for (i, guess) in self.guesses.iter().enumerate() {
+            assert!(
+                self.accepts_length(guess.len()),
+                "guess {} has the wrong length",
+                i + 1
+            );
+
+            let feedback = self.hits(i).unwrap();
+            match invariants.feedback.get(i) {
+                Some(&(_, given)) => assert_eq!(
+                    feedback,
+                    given,
+                    "feedback for guess {} changed when re-scored",
+                    i + 1
+                ),
+                None => invariants.feedback.push((guess.clone(), feedback)),
+            }
+            if !matches!(self.rules.feedback_mode, FeedbackMode::Custom(_)) {
+                let (hits, near_hits) = feedback;
+                assert!(
+                    (hits + near_hits) as usize <= guess.len(),
+                    "feedback for guess {} is more than its pegs",
+                    i + 1
+                );
+                // Jokers in the guess can earn full marks without solving.
+                let wildcard = matches!(
+                    self.rules.joker,
+                    Some(Joker::InGuess(joker)) if guess.contains(&joker)
+                );
+                let full_marks = hits as usize == self.pegs.len()
+                    && guess.len() == self.pegs.len();
+                assert_eq!(
+                    self.rules.is_solved_by(&self.pegs, guess),
+                    full_marks && !wildcard,
+                    "guess {} scores full marks but isn't a solve, or the \
+                     other way around",
+                    i + 1
+                );
+            }
+        }
+
+        assert!(
+            knowledge::is_consistent(&*self, &self.pegs),
+            "the secret contradicts the board"
+        );
+
+        let codes = self.peg_counts.clone().map(|len| {
+            self.alphabet
+                .len()
+                .checked_pow(len as u32)
+                .unwrap_or(usize::MAX)
+        });
+        if codes.fold(0, usize::saturating_add) <= MAX_COUNTED_CODES {
+            let candidates = knowledge::all_codes(&*self)
+                .iter()
+                .filter(|code| knowledge::is_consistent(&*self, code))
+                .count();
+            if let Some(before) = invariants.candidates {
+                assert!(
+                    candidates <= before,
+                    "possible codes went from {} to {}",
+                    before,
+                    candidates
+                );
+            }
+            invariants.candidates = Some(candidates);
+        }
+
+        self.invariants = Some(invariants);
+    }
+
+    // Forgets what earlier checks saw, for when the board legitimately goes
+    // back to knowing less.
+    pub(super) fn reset_invariants(&mut self) {
+        if let Some(invariants) = &mut self.invariants {
+            invariants.candidates = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicU8, Ordering},
+        Arc,
+    };
+
+    use super::*;
+    use crate::{
+        game::{FeedbackFn, HintKind},
+        GameBuilder,
+    };
+
+    #[test]
+    fn checked_games_play_normally() {
+        let mut game = GameBuilder::new()
+            .peg_count(3)
+            .peg_range(4)
+            .pegs(&[1, 2, 3])
+            .check_invariants(true)
+            .build();
+        game.guess(&[0, 0, 1]).unwrap();
+        let snapshot = game.snapshot();
+        game.hint(HintKind::EliminateColor).unwrap();
+        game.guess(&[1, 1, 2]).unwrap();
+        game.restore(&snapshot);
+        game.guess(&[1, 2, 3]).unwrap();
+        assert!(game.is_solved());
+        assert!(game.invariants.is_some());
+    }
+
+    #[test]
+    #[should_panic(expected = "changed when re-scored")]
+    fn inconsistent_feedback_is_caught() {
+        let hits = Arc::new(AtomicU8::new(0));
+        let drifting = {
+            let hits = hits.clone();
+            FeedbackFn::new(move |_, _| (hits.load(Ordering::Relaxed), 0))
+        };
+        let mut game = GameBuilder::new()
+            .feedback_mode(FeedbackMode::Custom(drifting))
+            .check_invariants(true)
+            .build();
+        game.guess(&[0, 0, 0, 0]).unwrap();
+
+        // Every code scores the same, so each check alone is consistent;
+        // only the first guess's feedback changing gives it away.
+        hits.store(1, Ordering::Relaxed);
+        game.guess(&[1, 1, 1, 1]).unwrap();
+    }
+}
@@ -1,61 +1,265 @@
+mod autosave;
 mod builder;
+mod constraint;
+mod difficulty;
+mod events;
+mod gameplay;
+mod hint;
+mod invariants;
+mod number;
+pub mod registry;
+mod rules;
+#[cfg(feature = "rhai")]
+mod script;
+mod telemetry;
+mod template;
 mod variant;
+mod view;
 
-pub use builder::GameBuilder;
+use std::{cmp::Ordering, ops::RangeInclusive, time::Instant};
+
+pub use autosave::{Autosave, Checkpoint};
+pub use builder::{BuildError, GameBuilder, SecretFilter};
+pub use constraint::SecretConstraint;
+pub use difficulty::Difficulty;
+pub use events::GameEvent;
+use events::Spectators;
+pub use gameplay::GamePlay;
+pub use hint::{Hint, HintKind};
+use invariants::Invariants;
+pub use number::{ArithmeticHint, HintChannel};
+pub use rules::{FeedbackFn, FeedbackMode, Joker, Rules, EMPTY};
+#[cfg(feature = "rhai")]
+pub use script::{Script, ScriptError};
+pub use telemetry::{Outcome, Summary, Telemetry};
+pub use template::{Template, TemplateError};
 pub use variant::Variant;
+pub use view::BoardView;
+
+// Handicaps for the codebreaker, for balancing games between players of
+// different skill.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Handicap {
+    pub bonus_guesses: u8,
+    pub revealed_pegs: u8,
+}
 
 pub struct Game {
     pegs:        Vec<u8>,
+    revealed:    Vec<usize>,
+    hints:       Vec<Hint>,
+    handicap:    Handicap,
     guesses:     Vec<Vec<u8>>,
+    forfeited:   bool,
     max_guesses: Option<u8>,
+    hint_budget: Option<u32>,
+    rules:       Rules,
+    peg_counts:  RangeInclusive<u8>,
+    alphabet:    Vec<u8>,
+    seed:        Option<u64>,
+    autosave:    Option<Box<dyn Autosave + Send>>,
+    telemetry:   Option<Box<dyn Telemetry + Send>>,
+    started:     Instant,
+    invariants:  Option<Invariants>,
+    spectators:  Spectators,
 }
 
 impl Game {
     pub fn pegs(&self) -> &[u8] { &self.pegs }
     pub fn guesses(&self) -> &[Vec<u8>] { &self.guesses }
+    pub fn rules(&self) -> &Rules { &self.rules }
+    pub fn handicap(&self) -> Handicap { self.handicap }
+    pub fn max_guesses(&self) -> Option<u8> { self.max_guesses }
+    // The seed the secret was generated from, if the game was seeded.
+    pub fn seed(&self) -> Option<u64> { self.seed }
+
+    pub fn is_forfeited(&self) -> bool { self.forfeited }
+
+    pub fn is_over(&self) -> bool {
+        self.is_solved()
+            || self.forfeited
+            || self
+                .max_guesses
+                .is_some_and(|max| self.guesses.len() >= max as usize)
+    }
+
+    // The secret pegs given away at the start of the game, as
+    // `(position, peg)` pairs.
+    pub fn revealed(&self) -> Vec<(usize, u8)> {
+        self.revealed
+            .iter()
+            .map(|&position| (position, self.pegs[position]))
+            .collect()
+    }
+
+    // The lengths the secret could have. For fixed-length games this contains
+    // a single value.
+    pub fn peg_counts(&self) -> RangeInclusive<u8> { self.peg_counts.clone() }
+    // Every value the secret may contain.
+    pub fn alphabet(&self) -> &[u8] { &self.alphabet }
+    // Whether `peg` may appear in a guess: anything in the alphabet, or a
+    // guess-side joker.
+    pub fn is_guessable(&self, peg: u8) -> bool {
+        self.alphabet.contains(&peg)
+            || self.rules.joker == Some(Joker::InGuess(peg))
+    }
+    pub fn is_variable_length(&self) -> bool {
+        self.peg_counts.start() != self.peg_counts.end()
+    }
+    // Whether a guess of `len` pegs is the right length.
+    pub fn accepts_length(&self, len: usize) -> bool {
+        fits_length(&self.peg_counts, len)
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.guesses
+            .last()
+            .is_some_and(|guess| self.rules.is_solved_by(&self.pegs, guess))
+    }
 
     pub fn guess(&mut self, guess: &[u8]) -> Result<(u8, u8), GuessError> {
+        if self.forfeited || self.is_solved() {
+            return Err(GuessError::GameOver);
+        }
         if let Some(max_guesses) = self.max_guesses {
             if self.guesses.len() == max_guesses as usize {
                 return Err(GuessError::NoGuessesLeft);
             }
         }
 
+        if !self.accepts_length(guess.len()) {
+            return Err(GuessError::WrongLength);
+        }
+
         self.guesses.push(guess.to_owned());
-        Ok(self.hits(self.guesses.len() - 1).unwrap())
+        self.save();
+        self.check_invariants();
+
+        let index = self.guesses.len() - 1;
+        let (hits, near_hits) = self.hits(index).unwrap();
+        self.broadcast(GameEvent::Guess {
+            index,
+            guess: guess.to_owned(),
+            hits,
+            near_hits,
+        });
+        if self.is_over() {
+            self.broadcast(GameEvent::Over {
+                solved: self.is_solved(),
+                secret: self.pegs.clone(),
+            });
+            self.report();
+        }
+        Ok((hits, near_hits))
     }
 
     pub fn hits(&self, index: usize) -> Option<(u8, u8)> {
-        self.guesses.get(index).map(|guess_pegs| {
-            let mut hits = 0;
-            let mut near_hits = 0;
-
-            let guess = guess_pegs.clone();
-            let real = self.pegs.clone();
-
-            let mut guess = guess.iter().map(|p| Some(p)).collect::<Vec<_>>();
-            let mut real = real.iter().map(|p| Some(p)).collect::<Vec<_>>();
-
-            for (i, real_peg) in real.iter_mut().enumerate() {
-                if guess[i] == *real_peg {
-                    guess[i] = None;
-                    *real_peg = None;
-                    hits += 1;
-                }
-            }
+        self.guesses
+            .get(index)
+            .map(|guess| self.rules.score(&self.pegs, guess))
+    }
+
+    pub fn mask(&self, index: usize) -> Option<Vec<bool>> {
+        self.guesses
+            .get(index)
+            .map(|guess| self.rules.mask(&self.pegs, guess))
+    }
 
-            for (i, real_peg) in real.iter_mut().enumerate() {
-                for (j, guess_peg) in guess.iter_mut().enumerate() {
-                    if real_peg.is_some() && *real_peg == *guess_peg && i != j {
-                        *guess_peg = None;
-                        *real_peg = None;
-                        near_hits += 1;
-                    }
-                }
+    pub fn arithmetic_hints(
+        &self,
+        index: usize,
+    ) -> Option<Vec<ArithmeticHint>> {
+        self.guesses
+            .get(index)
+            .map(|guess| self.rules.arithmetic_hints(&self.pegs, guess))
+    }
+
+    pub fn checkpoint(&self) -> Checkpoint { self.into() }
+
+    // Gives up, ending the game unsolved.
+    pub fn forfeit(&mut self) {
+        if self.is_over() {
+            return;
+        }
+        self.forfeited = true;
+        self.save();
+        self.check_invariants();
+        self.broadcast(GameEvent::Over {
+            solved: false,
+            secret: self.pegs.clone(),
+        });
+        self.report();
+    }
+
+    // Captures the game's progress so it can be returned to later, e.g. after
+    // exploring a "what if" line of guesses.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            guesses:   self.guesses.clone(),
+            hints:     self.hints.clone(),
+            forfeited: self.forfeited,
+        }
+    }
+
+    // Returns to a snapshot taken from this game.
+    pub fn restore(&mut self, snapshot: &Snapshot) {
+        self.guesses.clone_from(&snapshot.guesses);
+        self.hints.clone_from(&snapshot.hints);
+        self.forfeited = snapshot.forfeited;
+        self.save();
+        self.reset_invariants();
+        self.check_invariants();
+        self.broadcast(GameEvent::Restored {
+            guesses: self.guesses.len(),
+        });
+    }
+
+    // A copy of the game as it stood after its first `guesses` guesses,
+    // without the autosave or telemetry sinks.
+    pub(crate) fn truncated(&self, guesses: usize) -> Game {
+        Game {
+            pegs:        self.pegs.clone(),
+            revealed:    self.revealed.clone(),
+            hints:       self.hints.clone(),
+            handicap:    self.handicap,
+            guesses:     self.guesses[..guesses.min(self.guesses.len())]
+                .to_vec(),
+            forfeited:   self.forfeited && guesses >= self.guesses.len(),
+            max_guesses: self.max_guesses,
+            hint_budget: self.hint_budget,
+            rules:       self.rules.clone(),
+            peg_counts:  self.peg_counts.clone(),
+            alphabet:    self.alphabet.clone(),
+            seed:        self.seed,
+            autosave:    None,
+            telemetry:   None,
+            started:     self.started,
+            invariants:  self.invariants.clone(),
+            spectators:  Spectators::default(),
+        }
+    }
+
+    fn save(&mut self) {
+        if let Some(mut autosave) = self.autosave.take() {
+            autosave.checkpoint(&self.checkpoint());
+            self.autosave = Some(autosave);
+        }
+    }
+
+    fn report(&mut self) {
+        if let Some(summary) = Summary::of(self) {
+            if let Some(telemetry) = self.telemetry.as_mut() {
+                telemetry.record(&summary);
             }
+        }
+    }
 
-            (hits, near_hits)
-        })
+    // Whether the secret is longer (`Greater`), shorter (`Less`) or the same
+    // length as the guess at `index`.
+    pub fn length_hint(&self, index: usize) -> Option<Ordering> {
+        self.guesses
+            .get(index)
+            .map(|guess| self.pegs.len().cmp(&guess.len()))
     }
 }
 
@@ -63,9 +267,26 @@ impl Default for Game {
     fn default() -> Self { GameBuilder::default().into() }
 }
 
+// The parts of a game that change as it's played.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    guesses:   Vec<Vec<u8>>,
+    hints:     Vec<Hint>,
+    forfeited: bool,
+}
+
+// Whether `len` is one of `peg_counts`. Lengths are compared as `usize`, so a
+// code of 256 or more pegs can't wrap around into range.
+pub(crate) fn fits_length(peg_counts: &RangeInclusive<u8>, len: usize) -> bool {
+    u8::try_from(len).is_ok_and(|len| peg_counts.contains(&len))
+}
+
 #[derive(Debug, PartialEq)]
 pub enum GuessError {
     NoGuessesLeft,
+    WrongLength,
+    // The game was already solved or forfeited.
+    GameOver,
 }
 
 #[cfg(test)]
@@ -94,6 +315,23 @@ mod tests {
         assert!(game.guess(&[1, 2, 3, 4]).is_err());
     }
 
+    #[test]
+    fn game_is_over_when_solved_or_out_of_guesses() {
+        let mut game = GameBuilder::new()
+            .pegs(&[1, 2, 3, 4])
+            .max_guesses(2)
+            .build();
+        assert!(!game.is_over());
+        game.guess(&[1, 1, 1, 1]).unwrap();
+        assert!(!game.is_over());
+        game.guess(&[2, 2, 2, 2]).unwrap();
+        assert!(game.is_over());
+
+        let mut game = GameBuilder::new().pegs(&[1, 2, 3, 4]).build();
+        game.guess(&[1, 2, 3, 4]).unwrap();
+        assert!(game.is_over());
+    }
+
     #[test]
     fn hits_returns_accurate_hits() {
         let mut game = GameBuilder::new().pegs(&[1, 1, 2, 2]).build();
@@ -108,4 +346,157 @@ mod tests {
         assert_eq!(game.guess(&[0, 5, 1, 0]), Ok((1, 1)));
         assert_eq!(game.guess(&[3, 5, 1, 0]), Ok((1, 2)));
     }
+
+    #[test]
+    fn is_solved_after_correct_guess() {
+        let mut game = GameBuilder::new().pegs(&[1, 2, 3, 4]).build();
+        assert!(!game.is_solved());
+        game.guess(&[4, 3, 2, 1]).unwrap();
+        assert!(!game.is_solved());
+        game.guess(&[1, 2, 3, 4]).unwrap();
+        assert!(game.is_solved());
+    }
+
+    #[test]
+    fn jokers_are_scored() {
+        let mut game = GameBuilder::new()
+            .pegs(&[1, 5, 6, 3])
+            .joker(Joker::InGuess(7))
+            .build();
+        assert_eq!(game.guess(&[7, 7, 3, 0]), Ok((2, 1)));
+        assert!(!game.is_solved());
+
+        // Jokers in the guess score full marks, but only the real secret
+        // solves the game.
+        assert_eq!(game.guess(&[7, 7, 7, 7]), Ok((4, 0)));
+        assert!(!game.is_solved());
+        assert_eq!(game.guess(&[1, 5, 6, 3]), Ok((4, 0)));
+        assert!(game.is_solved());
+        assert!(game.is_over());
+    }
+
+    #[test]
+    fn guessable_pegs_include_guess_jokers() {
+        let game = GameBuilder::new().joker(Joker::InGuess(9)).build();
+        assert!(game.is_guessable(5));
+        assert!(game.is_guessable(9));
+        assert!(!game.is_guessable(6));
+        assert!(!game.is_guessable(EMPTY));
+    }
+
+    #[test]
+    fn solved_games_take_no_more_guesses() {
+        let mut game = GameBuilder::new().pegs(&[1, 2, 3, 4]).build();
+        let events = game.spectate();
+        game.guess(&[1, 2, 3, 4]).unwrap();
+
+        assert_eq!(game.guess(&[0, 0, 0, 0]), Err(GuessError::GameOver));
+        assert!(game.is_solved());
+        assert_eq!(game.guesses().len(), 1);
+        assert_eq!(events.try_iter().count(), 2);
+    }
+
+    #[test]
+    fn forfeiting_ends_the_game() {
+        let mut game = GameBuilder::new().pegs(&[1, 2, 3, 4]).build();
+        let events = game.spectate();
+        game.guess(&[0, 0, 0, 0]).unwrap();
+        game.forfeit();
+        game.forfeit();
+
+        assert!(game.is_over());
+        assert!(!game.is_solved());
+        assert_eq!(game.guess(&[1, 2, 3, 4]), Err(GuessError::GameOver));
+        assert_eq!(events.try_iter().count(), 2);
+    }
+
+    #[test]
+    fn snapshots_restore_progress() {
+        let mut game = GameBuilder::new()
+            .pegs(&[1, 2, 3, 4])
+            .max_guesses(3)
+            .build();
+        game.guess(&[0, 0, 0, 0]).unwrap();
+        let snapshot = game.snapshot();
+
+        game.guess(&[1, 1, 1, 1]).unwrap();
+        game.hint(HintKind::EliminateColor).unwrap();
+        game.guess(&[1, 2, 3, 4]).unwrap();
+        assert!(game.is_over());
+
+        game.restore(&snapshot);
+        assert_eq!(game.guesses(), [[0, 0, 0, 0]]);
+        assert!(game.hints().is_empty());
+        assert!(!game.is_over());
+        assert_eq!(game.guess(&[2, 2, 2, 2]), Ok((1, 0)));
+    }
+
+    #[test]
+    fn guesses_must_have_a_valid_length() {
+        let mut game = GameBuilder::new().pegs(&[1, 2, 3, 4]).build();
+        assert_eq!(game.guess(&[1, 2, 3]), Err(GuessError::WrongLength));
+        assert_eq!(game.guess(&[1, 2, 3, 4, 5]), Err(GuessError::WrongLength));
+        assert!(game.guesses().is_empty());
+    }
+
+    #[test]
+    fn variable_length_games_hint_at_length() {
+        let mut game = GameBuilder::new()
+            .peg_count_range(3, 5)
+            .pegs(&[1, 2, 3, 4])
+            .build();
+        assert!(game.is_variable_length());
+
+        assert_eq!(game.guess(&[1, 2]), Err(GuessError::WrongLength));
+        assert_eq!(game.guess(&[1; 259]), Err(GuessError::WrongLength));
+        assert_eq!(game.guess(&[1, 2, 3]), Ok((3, 0)));
+        assert_eq!(game.length_hint(0), Some(Ordering::Greater));
+        assert_eq!(game.guess(&[4, 3, 2, 1, 0]), Ok((0, 4)));
+        assert_eq!(game.length_hint(1), Some(Ordering::Less));
+        assert_eq!(game.guess(&[1, 2, 3, 4]), Ok((4, 0)));
+        assert_eq!(game.length_hint(2), Some(Ordering::Equal));
+    }
+
+    #[test]
+    fn arithmetic_hints_follow_the_channels() {
+        let mut game = GameBuilder::new().pegs(&[4, 0, 2, 1]).build();
+        game.guess(&[1, 2, 3, 4]).unwrap();
+        assert_eq!(game.arithmetic_hints(0), Some(vec![]));
+
+        let mut game = GameBuilder::new()
+            .pegs(&[4, 0, 2, 1])
+            .hint_channel(HintChannel::HigherLower)
+            .hint_channel(HintChannel::DigitSumParity)
+            .build();
+        game.guess(&[1, 2, 3, 4]).unwrap();
+        assert_eq!(
+            game.arithmetic_hints(0),
+            Some(vec![
+                ArithmeticHint::Magnitude(Ordering::Greater),
+                ArithmeticHint::DigitSumEven(false),
+            ])
+        );
+        assert_eq!(game.arithmetic_hints(1), None);
+    }
+
+    #[test]
+    fn positional_game_reports_masks() {
+        let mut game = GameBuilder::new()
+            .pegs(&[1, 5, 6, 3])
+            .feedback_mode(FeedbackMode::Positional)
+            .build();
+        assert_eq!(game.guess(&[3, 5, 1, 3]), Ok((2, 0)));
+        assert_eq!(game.mask(0), Some(vec![false, true, false, true]));
+        assert_eq!(game.mask(1), None);
+    }
+
+    #[test]
+    fn count_only_game_hides_near_hits() {
+        let mut game = GameBuilder::new()
+            .pegs(&[1, 5, 6, 3])
+            .feedback_mode(FeedbackMode::CountOnly)
+            .build();
+        assert_eq!(game.guess(&[3, 5, 1, 0]), Ok((1, 0)));
+        assert_eq!(game.hits(0), Some((1, 0)));
+    }
 }
@@ -0,0 +1,82 @@
+use std::cmp::Ordering;
+
+// Extra information channels used by "Number Mastermind", where the secret is
+// read as a number with one digit per peg.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HintChannel {
+    DigitSumParity,
+    HigherLower,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArithmeticHint {
+    DigitSumEven(bool),
+    // How the secret compares to the guess.
+    Magnitude(Ordering),
+}
+
+impl HintChannel {
+    pub fn hint(&self, secret: &[u8], guess: &[u8]) -> ArithmeticHint {
+        match self {
+            Self::DigitSumParity => {
+                let sum = secret.iter().map(|&peg| peg as u32).sum::<u32>();
+                ArithmeticHint::DigitSumEven(sum % 2 == 0)
+            }
+            Self::HigherLower => {
+                ArithmeticHint::Magnitude(compare_numbers(secret, guess))
+            }
+        }
+    }
+}
+
+// Compares two digit sequences (most significant first) as numbers, without
+// needing to know their base.
+fn compare_numbers(a: &[u8], b: &[u8]) -> Ordering {
+    let significant = |digits: &[u8]| {
+        let start = digits.iter().position(|&d| d != 0).unwrap_or(digits.len());
+        digits[start..].to_vec()
+    };
+    let (a, b) = (significant(a), significant(b));
+
+    a.len().cmp(&b.len()).then_with(|| a.cmp(&b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digit_sum_parity() {
+        let channel = HintChannel::DigitSumParity;
+        assert_eq!(
+            channel.hint(&[1, 2, 3, 4], &[0, 0, 0, 0]),
+            ArithmeticHint::DigitSumEven(true)
+        );
+        assert_eq!(
+            channel.hint(&[1, 2, 3, 5], &[0, 0, 0, 0]),
+            ArithmeticHint::DigitSumEven(false)
+        );
+    }
+
+    #[test]
+    fn higher_lower() {
+        use Ordering::*;
+
+        let hint = |secret: &[u8], guess: &[u8]| {
+            HintChannel::HigherLower.hint(secret, guess)
+        };
+        assert_eq!(
+            hint(&[1, 2, 3, 4], &[1, 2, 3, 3]),
+            ArithmeticHint::Magnitude(Greater)
+        );
+        assert_eq!(
+            hint(&[0, 9, 9, 9], &[1, 0, 0, 0]),
+            ArithmeticHint::Magnitude(Less)
+        );
+        assert_eq!(hint(&[0, 4, 2], &[4, 2]), ArithmeticHint::Magnitude(Equal));
+        assert_eq!(
+            hint(&[1, 0, 0], &[0, 0, 9, 9]),
+            ArithmeticHint::Magnitude(Greater)
+        );
+    }
+}
@@ -0,0 +1,73 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, OnceLock, RwLock},
+};
+
+use super::GameBuilder;
+
+type BuilderFn = Arc<dyn Fn() -> GameBuilder + Send + Sync>;
+
+fn registry() -> &'static RwLock<HashMap<String, BuilderFn>> {
+    static REGISTRY: OnceLock<RwLock<HashMap<String, BuilderFn>>> =
+        OnceLock::new();
+    REGISTRY.get_or_init(Default::default)
+}
+
+// Registers a named variant, replacing any previous variant with that name.
+pub fn register<F>(name: &str, builder: F)
+where
+    F: Fn() -> GameBuilder + Send + Sync + 'static,
+{
+    registry()
+        .write()
+        .unwrap()
+        .insert(name.to_lowercase(), Arc::new(builder));
+}
+
+pub fn is_registered(name: &str) -> bool {
+    registry()
+        .read()
+        .unwrap()
+        .contains_key(&name.to_lowercase())
+}
+
+pub fn registered_names() -> Vec<String> {
+    let mut names = registry()
+        .read()
+        .unwrap()
+        .keys()
+        .cloned()
+        .collect::<Vec<_>>();
+    names.sort();
+    names
+}
+
+pub(crate) fn builder(name: &str) -> Option<GameBuilder> {
+    let builder = registry()
+        .read()
+        .unwrap()
+        .get(&name.to_lowercase())
+        .cloned();
+    builder.map(|builder| builder())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registered_variants_can_be_built() {
+        register("Registry Test", || GameBuilder::new().peg_count(6));
+        assert!(is_registered("registry test"));
+        assert!(registered_names().contains(&"registry test".to_owned()));
+
+        let game = builder("REGISTRY TEST").unwrap().build();
+        assert_eq!(game.pegs().len(), 6);
+    }
+
+    #[test]
+    fn unknown_variants_are_not_found() {
+        assert!(!is_registered("no such variant"));
+        assert!(builder("no such variant").is_none());
+    }
+}
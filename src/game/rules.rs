@@ -0,0 +1,291 @@
+use std::{fmt, sync::Arc};
+
+use super::{ArithmeticHint, HintChannel};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum FeedbackMode {
+    #[default]
+    Standard,
+    // Only exact matches are reported; near hits are always zero.
+    CountOnly,
+    // Wordle-style feedback: whether each position is exactly right. Aggregate
+    // scores only report exact hits; see `Rules::mask`.
+    Positional,
+    Custom(FeedbackFn),
+}
+
+type ScoreFn = dyn Fn(&[u8], &[u8]) -> (u8, u8) + Send + Sync;
+
+// A user-supplied scoring function, called as `f(secret, guess)`.
+#[derive(Clone)]
+pub struct FeedbackFn(Arc<ScoreFn>);
+
+impl FeedbackFn {
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(&[u8], &[u8]) -> (u8, u8) + Send + Sync + 'static,
+    {
+        Self(Arc::new(f))
+    }
+}
+
+impl fmt::Debug for FeedbackFn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("FeedbackFn(..)")
+    }
+}
+
+impl PartialEq for FeedbackFn {
+    fn eq(&self, other: &Self) -> bool { Arc::ptr_eq(&self.0, &other.0) }
+}
+
+impl Eq for FeedbackFn {}
+
+// The peg value used for an empty hole, when a game allows them. Empty holes
+// are scored like any other color.
+pub const EMPTY: u8 = u8::MAX;
+
+// A peg value that matches any color. A joker always counts as an exact hit
+// for the position it's in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Joker {
+    InGuess(u8),
+    InSecret(u8),
+}
+
+impl Joker {
+    fn matches(&self, real: Option<&u8>, guess: Option<&u8>) -> bool {
+        match self {
+            Self::InGuess(joker) => guess == Some(joker) && real.is_some(),
+            Self::InSecret(joker) => real == Some(joker) && guess.is_some(),
+        }
+    }
+}
+
+// Codes up to this long are scored without allocating.
+const STACK_PEGS: usize = 16;
+
+fn working_copy<'a>(
+    pegs: &[u8],
+    stack: &'a mut [Option<u8>; STACK_PEGS],
+    heap: &'a mut Vec<Option<u8>>,
+) -> &'a mut [Option<u8>] {
+    if pegs.len() <= STACK_PEGS {
+        let copy = &mut stack[..pegs.len()];
+        copy.iter_mut()
+            .zip(pegs)
+            .for_each(|(slot, &peg)| *slot = Some(peg));
+        copy
+    }
+    else {
+        heap.extend(pegs.iter().copied().map(Some));
+        heap
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Rules {
+    pub feedback_mode: FeedbackMode,
+    pub joker:         Option<Joker>,
+    pub allow_empty:   bool,
+    pub hint_channels: Vec<HintChannel>,
+}
+
+impl Rules {
+    pub fn arithmetic_hints(
+        &self,
+        secret: &[u8],
+        guess: &[u8],
+    ) -> Vec<ArithmeticHint> {
+        self.hint_channels
+            .iter()
+            .map(|channel| channel.hint(secret, guess))
+            .collect()
+    }
+
+    pub fn score(&self, secret: &[u8], guess: &[u8]) -> (u8, u8) {
+        if let FeedbackMode::Custom(FeedbackFn(f)) = &self.feedback_mode {
+            return f(secret, guess);
+        }
+
+        let mut hits = 0;
+        let mut near_hits = 0;
+
+        // Scoring is the solvers' inner loop, so the working copies of the
+        // pegs live on the stack unless a code is unusually long.
+        let (mut guess_stack, mut real_stack) =
+            ([None; STACK_PEGS], [None; STACK_PEGS]);
+        let (mut guess_heap, mut real_heap) = (Vec::new(), Vec::new());
+        let guess = working_copy(guess, &mut guess_stack, &mut guess_heap);
+        let real = working_copy(secret, &mut real_stack, &mut real_heap);
+
+        // Guesses and secrets may differ in length in variable-length games,
+        // in which case only the overlapping positions can be exact hits.
+        for (real_peg, guess_peg) in real.iter_mut().zip(guess.iter_mut()) {
+            let joker = self.joker.is_some_and(|joker| {
+                joker.matches(real_peg.as_ref(), guess_peg.as_ref())
+            });
+
+            if *guess_peg == *real_peg || joker {
+                *guess_peg = None;
+                *real_peg = None;
+                hits += 1;
+            }
+        }
+
+        if matches!(
+            self.feedback_mode,
+            FeedbackMode::CountOnly | FeedbackMode::Positional
+        ) {
+            return (hits, 0);
+        }
+
+        for (i, real_peg) in real.iter_mut().enumerate() {
+            for (j, guess_peg) in guess.iter_mut().enumerate() {
+                if real_peg.is_some() && *real_peg == *guess_peg && i != j {
+                    *guess_peg = None;
+                    *real_peg = None;
+                    near_hits += 1;
+                }
+            }
+        }
+
+        (hits, near_hits)
+    }
+
+    // Whether `guess` cracks `secret`. A guess that differs from the secret
+    // only where a joker in the secret matched scores full marks, so it
+    // counts too. Jokers in the guess never complete a solve: otherwise a
+    // guess of nothing but jokers would win every game.
+    pub fn is_solved_by(&self, secret: &[u8], guess: &[u8]) -> bool {
+        if guess == secret {
+            return true;
+        }
+        matches!(self.joker, Some(Joker::InSecret(_)))
+            && !matches!(self.feedback_mode, FeedbackMode::Custom(_))
+            && guess.len() == secret.len()
+            && self.mask(secret, guess).into_iter().all(|hit| hit)
+    }
+
+    pub fn mask(&self, secret: &[u8], guess: &[u8]) -> Vec<bool> {
+        guess
+            .iter()
+            .enumerate()
+            .map(|(i, guess_peg)| {
+                let real_peg = secret.get(i);
+                let joker = self.joker.is_some_and(|joker| {
+                    joker.matches(real_peg, Some(guess_peg))
+                });
+
+                real_peg == Some(guess_peg) || joker
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn standard_reports_near_hits() {
+        let rules = Rules::default();
+        assert_eq!(rules.score(&[1, 1, 2, 2], &[1, 2, 3, 1]), (1, 2));
+        assert_eq!(rules.score(&[1, 5, 6, 3], &[3, 5, 1, 0]), (1, 2));
+    }
+
+    #[test]
+    fn count_only_hides_near_hits() {
+        let rules = Rules {
+            feedback_mode: FeedbackMode::CountOnly,
+            ..Rules::default()
+        };
+        assert_eq!(rules.score(&[1, 1, 2, 2], &[1, 2, 3, 1]), (1, 0));
+        assert_eq!(rules.score(&[1, 1, 2, 2], &[2, 2, 1, 1]), (0, 0));
+        assert_eq!(rules.score(&[1, 1, 2, 2], &[1, 1, 2, 2]), (4, 0));
+    }
+
+    #[test]
+    fn joker_in_guess_matches_anything() {
+        let rules = Rules {
+            joker: Some(Joker::InGuess(9)),
+            ..Rules::default()
+        };
+        assert_eq!(rules.score(&[1, 2, 3, 4], &[9, 9, 9, 9]), (4, 0));
+        assert!(!rules.is_solved_by(&[1, 2, 3, 4], &[9, 9, 9, 9]));
+        assert!(!rules.is_solved_by(&[1, 2, 3, 4], &[9, 2, 3, 4]));
+        assert!(rules.is_solved_by(&[1, 2, 3, 4], &[1, 2, 3, 4]));
+        assert_eq!(rules.score(&[1, 2, 3, 4], &[9, 3, 0, 0]), (1, 1));
+        assert!(!rules.is_solved_by(&[1, 2, 3, 4], &[9, 3, 0, 0]));
+        assert_eq!(rules.score(&[9, 2, 3, 4], &[1, 9, 0, 0]), (1, 0));
+    }
+
+    #[test]
+    fn joker_in_secret_matches_anything() {
+        let rules = Rules {
+            joker: Some(Joker::InSecret(9)),
+            ..Rules::default()
+        };
+        assert_eq!(rules.score(&[9, 2, 3, 4], &[0, 0, 0, 0]), (1, 0));
+        assert!(rules.is_solved_by(&[9, 2, 3, 4], &[0, 2, 3, 4]));
+        assert_eq!(rules.score(&[9, 2, 3, 4], &[0, 0, 2, 0]), (1, 1));
+        assert_eq!(rules.score(&[1, 2, 3, 4], &[9, 9, 9, 9]), (0, 0));
+    }
+
+    #[test]
+    fn empty_is_scored_as_a_color() {
+        let rules = Rules {
+            allow_empty: true,
+            ..Rules::default()
+        };
+        assert_eq!(
+            rules.score(&[EMPTY, 1, EMPTY, 2], &[EMPTY, EMPTY, 0, 0]),
+            (1, 1)
+        );
+    }
+
+    #[test]
+    fn mismatched_lengths_are_scored() {
+        let rules = Rules::default();
+        assert_eq!(rules.score(&[1, 2, 3], &[1, 3, 2, 4, 5]), (1, 2));
+        assert_eq!(rules.score(&[1, 2, 3, 4, 5], &[5, 2]), (1, 1));
+    }
+
+    #[test]
+    fn long_codes_are_scored() {
+        let rules = Rules::default();
+        let secret = (0..20).collect::<Vec<_>>();
+        let mut guess = secret.clone();
+        guess.swap(0, 19);
+        assert_eq!(rules.score(&secret, &guess), (18, 2));
+        assert_eq!(rules.score(&secret, &guess[..10]), (9, 1));
+    }
+
+    #[test]
+    fn positional_feedback() {
+        let rules = Rules {
+            feedback_mode: FeedbackMode::Positional,
+            joker: Some(Joker::InGuess(9)),
+            ..Rules::default()
+        };
+        assert_eq!(rules.score(&[1, 2, 3, 4], &[1, 3, 2, 9]), (2, 0));
+        assert_eq!(
+            rules.mask(&[1, 2, 3, 4], &[1, 3, 2, 9]),
+            vec![true, false, false, true]
+        );
+        assert_eq!(rules.mask(&[1, 2], &[1, 2, 3]), vec![true, true, false]);
+    }
+
+    #[test]
+    fn custom_feedback_is_used() {
+        let reversed = FeedbackFn::new(|secret, guess| {
+            let (hits, near_hits) = Rules::default().score(secret, guess);
+            (near_hits, hits)
+        });
+        let rules = Rules {
+            feedback_mode: FeedbackMode::Custom(reversed),
+            ..Rules::default()
+        };
+        assert_eq!(rules.score(&[1, 1, 2, 2], &[1, 2, 3, 1]), (2, 1));
+    }
+}
@@ -0,0 +1,258 @@
+use std::{fmt, fs, io, path::Path, sync::Arc};
+
+use rhai::{Array, Dynamic, Engine, EvalAltResult, ParseError, Scope, AST};
+
+use super::{FeedbackFn, FeedbackMode, GameBuilder};
+
+// Roughly how many steps a script may take per call: plenty to score codes
+// of any sensible length.
+const MAX_OPERATIONS: u64 = 100_000;
+
+// A variant defined in a rhai script. The script must define
+// `score(secret, guess)`, returning `[hits, near_hits]`, and may define
+// `valid_secret(secret)` to constrain which secrets are generated.
+//
+// Scripts run with limits on how much work they do, so a runaway script fails
+// instead of hanging the game. A `score` that fails while a game is played
+// counts as no hits at all, and a `valid_secret` that fails rejects the
+// secret; `try_score` reports the error instead.
+//
+//     fn score(secret, guess) {
+//         let hits = 0;
+//         for i in 0..secret.len() {
+//             if secret[i] == guess[i] { hits += 1; }
+//         }
+//         [hits, 0]
+//     }
+#[derive(Clone)]
+pub struct Script {
+    engine: Arc<Engine>,
+    ast:    Arc<AST>,
+}
+
+#[derive(Debug)]
+pub enum ScriptError {
+    Io(io::Error),
+    Parse(ParseError),
+    MissingFunction(&'static str),
+    // The named function failed, or ran past the engine's limits.
+    Runtime(&'static str, Box<EvalAltResult>),
+    // The named function returned something other than what it should.
+    BadReturn(&'static str),
+}
+
+impl fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "could not read script: {}", e),
+            Self::Parse(e) => write!(f, "could not parse script: {}", e),
+            Self::MissingFunction(name) => {
+                write!(f, "script does not define `{}`", name)
+            }
+            Self::Runtime(name, e) => write!(f, "`{}` failed: {}", name, e),
+            Self::BadReturn("score") => {
+                f.write_str("`score` must return [hits, near_hits]")
+            }
+            Self::BadReturn(name) => {
+                write!(f, "`{}` must return true or false", name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ScriptError {}
+
+impl Script {
+    pub fn compile(source: &str) -> Result<Self, ScriptError> {
+        let mut engine = Engine::new();
+        engine
+            .set_max_operations(MAX_OPERATIONS)
+            .set_max_call_levels(32)
+            .set_max_expr_depths(64, 32)
+            .set_max_string_size(1024)
+            .set_max_array_size(1024)
+            .set_max_map_size(1024);
+        let ast = engine.compile(source).map_err(ScriptError::Parse)?;
+
+        let script = Self {
+            engine: Arc::new(engine),
+            ast:    Arc::new(ast),
+        };
+
+        if !script.defines("score", 2) {
+            return Err(ScriptError::MissingFunction("score"));
+        }
+
+        // A trial run catches scripts that can't work at all before they're
+        // used in a game.
+        let (secret, guess) = ([0, 1, 2, 3], [3, 2, 1, 1]);
+        script.try_score(&secret, &guess)?;
+        script.try_accepts(&secret)?;
+        Ok(script)
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ScriptError> {
+        Self::compile(&fs::read_to_string(path).map_err(ScriptError::Io)?)
+    }
+
+    pub fn score(&self, secret: &[u8], guess: &[u8]) -> (u8, u8) {
+        self.try_score(secret, guess).unwrap_or_default()
+    }
+
+    // The script's score, with counts clamped to the length of the guess.
+    pub fn try_score(
+        &self,
+        secret: &[u8],
+        guess: &[u8],
+    ) -> Result<(u8, u8), ScriptError> {
+        let result = self.call("score", (to_array(secret), to_array(guess)))?;
+        let counts = result
+            .into_typed_array::<i64>()
+            .ok()
+            .filter(|counts| counts.len() == 2)
+            .ok_or(ScriptError::BadReturn("score"))?;
+
+        let max = guess.len().min(u8::MAX as usize) as i64;
+        let count = |i: usize| counts[i].clamp(0, max) as u8;
+        Ok((count(0), count(1)))
+    }
+
+    pub fn accepts(&self, secret: &[u8]) -> bool {
+        self.try_accepts(secret).unwrap_or(false)
+    }
+
+    pub fn try_accepts(&self, secret: &[u8]) -> Result<bool, ScriptError> {
+        if !self.defines("valid_secret", 1) {
+            return Ok(true);
+        }
+
+        self.call("valid_secret", (to_array(secret),))?
+            .as_bool()
+            .map_err(|_| ScriptError::BadReturn("valid_secret"))
+    }
+
+    pub fn feedback(&self) -> FeedbackFn {
+        let script = self.clone();
+        FeedbackFn::new(move |secret, guess| script.score(secret, guess))
+    }
+
+    pub fn apply(&self, builder: GameBuilder) -> GameBuilder {
+        let script = self.clone();
+        builder
+            .feedback_mode(FeedbackMode::Custom(self.feedback()))
+            .secret_filter(move |secret| script.accepts(secret))
+    }
+
+    fn defines(&self, name: &str, params: usize) -> bool {
+        self.ast
+            .iter_functions()
+            .any(|f| f.name == name && f.params.len() == params)
+    }
+
+    fn call(
+        &self,
+        name: &'static str,
+        args: impl rhai::FuncArgs,
+    ) -> Result<Dynamic, ScriptError> {
+        self.engine
+            .call_fn(&mut Scope::new(), &self.ast, name, args)
+            .map_err(|e| ScriptError::Runtime(name, e))
+    }
+}
+
+fn to_array(pegs: &[u8]) -> Array {
+    pegs.iter().map(|&peg| Dynamic::from(peg as i64)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXACT_ONLY: &str = r#"
+        fn score(secret, guess) {
+            let hits = 0;
+            for i in 0..secret.len() {
+                if secret[i] == guess[i] { hits += 1; }
+            }
+            [hits, 0]
+        }
+
+        fn valid_secret(secret) {
+            secret[0] != secret[1]
+        }
+    "#;
+
+    #[test]
+    fn script_scores_guesses() {
+        let script = Script::compile(EXACT_ONLY).unwrap();
+        assert_eq!(script.score(&[1, 2, 3, 4], &[1, 3, 2, 4]), (2, 0));
+    }
+
+    #[test]
+    fn script_constrains_secrets() {
+        let script = Script::compile(EXACT_ONLY).unwrap();
+        assert!(!script.accepts(&[1, 1, 2, 3]));
+
+        for _ in 0..20 {
+            let mut game = script.apply(GameBuilder::new()).build();
+            let pegs = game.pegs().to_vec();
+            assert_ne!(pegs[0], pegs[1]);
+
+            let swapped = [pegs[1], pegs[0], pegs[2], pegs[3]];
+            assert_eq!(game.guess(&swapped), Ok((2, 0)));
+        }
+    }
+
+    #[test]
+    fn broken_scripts_are_errors() {
+        let error = |source: &str| Script::compile(source).err();
+        assert!(matches!(
+            error("fn score(secret, guess) { loop {} }"),
+            Some(ScriptError::Runtime("score", _))
+        ));
+        assert!(matches!(
+            error("fn score(secret, guess) { secret[99] }"),
+            Some(ScriptError::Runtime("score", _))
+        ));
+        assert!(matches!(
+            error("fn score(secret, guess) { \"two\" }"),
+            Some(ScriptError::BadReturn("score"))
+        ));
+        assert!(matches!(
+            error("fn score(s, g) { [1, 2, 3] }"),
+            Some(ScriptError::BadReturn("score"))
+        ));
+        assert!(matches!(
+            error("fn score(s, g) { [0, 0] } fn valid_secret(s) { 1 }"),
+            Some(ScriptError::BadReturn("valid_secret"))
+        ));
+    }
+
+    #[test]
+    fn scores_are_clamped_and_failures_are_misses() {
+        let script = Script::compile(
+            "fn score(secret, guess) { [secret.len() * 9, -1] }",
+        )
+        .unwrap();
+        assert_eq!(script.score(&[1, 2, 3, 4], &[1, 2, 3, 4]), (4, 0));
+
+        let script = Script::compile(
+            "fn score(secret, guess) { if secret[0] == 5 { loop {} } [0, 0] }",
+        )
+        .unwrap();
+        assert!(script.try_score(&[5, 0, 0, 0], &[0; 4]).is_err());
+        assert_eq!(script.score(&[5, 0, 0, 0], &[0; 4]), (0, 0));
+    }
+
+    #[test]
+    fn score_is_required() {
+        assert!(matches!(
+            Script::compile("fn valid_secret(secret) { true }"),
+            Err(ScriptError::MissingFunction("score"))
+        ));
+        assert!(matches!(
+            Script::compile("fn score(secret, guess) {"),
+            Err(ScriptError::Parse(_))
+        ));
+    }
+}
@@ -0,0 +1,124 @@
+use std::{ops::RangeInclusive, time::Duration};
+
+use super::Game;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Outcome {
+    Solved,
+    OutOfGuesses,
+    Forfeited,
+}
+
+// What a telemetry sink learns about a finished game: its configuration and
+// how it went, but never the secret or the guesses themselves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Summary {
+    pub peg_counts:  RangeInclusive<u8>,
+    pub colors:      usize,
+    pub max_guesses: Option<u8>,
+    pub guesses:     usize,
+    pub hints:       usize,
+    pub duration:    Duration,
+    pub outcome:     Outcome,
+}
+
+impl Summary {
+    // None until the game is over.
+    pub fn of(game: &Game) -> Option<Self> {
+        let outcome = if game.is_solved() {
+            Outcome::Solved
+        }
+        else if game.forfeited {
+            Outcome::Forfeited
+        }
+        else if game.is_over() {
+            Outcome::OutOfGuesses
+        }
+        else {
+            return None;
+        };
+
+        Some(Self {
+            peg_counts: game.peg_counts(),
+            colors: game.alphabet().len(),
+            max_guesses: game.max_guesses(),
+            guesses: game.guesses().len(),
+            hints: game.hints.len(),
+            duration: game.started.elapsed(),
+            outcome,
+        })
+    }
+}
+
+// An analytics sink that receives a summary whenever a game ends. Games only
+// report to a sink if one is given to the builder.
+pub trait Telemetry {
+    fn record(&mut self, summary: &Summary);
+}
+
+impl<F: FnMut(&Summary)> Telemetry for F {
+    fn record(&mut self, summary: &Summary) { self(summary) }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::GameBuilder;
+
+    fn recorded(
+        builder: GameBuilder,
+        play: impl FnOnce(&mut Game),
+    ) -> Vec<Summary> {
+        let recorded = Arc::new(Mutex::new(Vec::new()));
+        let sink = recorded.clone();
+        let mut game = builder
+            .telemetry(move |summary: &Summary| {
+                sink.lock().unwrap().push(summary.clone())
+            })
+            .build();
+        play(&mut game);
+        drop(game);
+        Arc::try_unwrap(recorded).unwrap().into_inner().unwrap()
+    }
+
+    #[test]
+    fn finished_games_are_summarized() {
+        let builder = GameBuilder::new().pegs(&[1, 2, 3, 4]).max_guesses(3);
+        let summaries = recorded(builder, |game| {
+            game.guess(&[0, 0, 0, 0]).unwrap();
+            assert!(Summary::of(game).is_none());
+            game.guess(&[1, 2, 3, 4]).unwrap();
+        });
+
+        assert_eq!(summaries.len(), 1);
+        let summary = &summaries[0];
+        assert_eq!(summary.outcome, Outcome::Solved);
+        assert_eq!(summary.peg_counts, 4..=4);
+        assert_eq!(summary.colors, 6);
+        assert_eq!(summary.max_guesses, Some(3));
+        assert_eq!(summary.guesses, 2);
+    }
+
+    #[test]
+    fn losses_are_summarized() {
+        let builder = GameBuilder::new().pegs(&[1, 2, 3, 4]).max_guesses(1);
+        let summaries = recorded(builder, |game| {
+            game.guess(&[0, 0, 0, 0]).unwrap();
+        });
+        assert_eq!(summaries[0].outcome, Outcome::OutOfGuesses);
+
+        let summaries = recorded(GameBuilder::new(), |game| {
+            game.forfeit();
+            game.forfeit();
+        });
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].outcome, Outcome::Forfeited);
+        assert_eq!(summaries[0].guesses, 0);
+
+        assert!(recorded(GameBuilder::new(), |_| {}).is_empty());
+    }
+}
@@ -0,0 +1,184 @@
+use std::{fmt, str::FromStr};
+
+use rand::{seq::SliceRandom, Rng, RngCore};
+
+// The shape of a secret, written one character per peg: a digit is that exact
+// peg, `?` is any peg, and letters are placeholders. Positions sharing a
+// letter get the same peg, and different letters get different pegs, so
+// `A?AB` gives secrets like `3 0 3 5`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    slots: Vec<Slot>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Slot {
+    Any,
+    Fixed(u8),
+    Letter(u8),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateError {
+    Empty,
+    InvalidChar(char),
+}
+
+impl Template {
+    pub fn len(&self) -> usize { self.slots.len() }
+    pub fn is_empty(&self) -> bool { self.slots.is_empty() }
+
+    // The distinct letters used, in the order they first appear.
+    fn letters(&self) -> Vec<u8> {
+        let mut letters = Vec::new();
+        for slot in &self.slots {
+            if let Slot::Letter(letter) = slot {
+                if !letters.contains(letter) {
+                    letters.push(*letter);
+                }
+            }
+        }
+        letters
+    }
+
+    pub(super) fn fixed_pegs(&self) -> impl Iterator<Item = u8> + '_ {
+        self.slots.iter().filter_map(|slot| match slot {
+            Slot::Fixed(peg) => Some(*peg),
+            _ => None,
+        })
+    }
+
+    // Whether `alphabet` has enough pegs to give each letter its own.
+    pub(super) fn fits(&self, alphabet: &[u8]) -> bool {
+        self.letters().len() <= alphabet.len()
+    }
+
+    pub fn matches(&self, pegs: &[u8]) -> bool {
+        if pegs.len() != self.slots.len() {
+            return false;
+        }
+
+        let mut assigned: Vec<(u8, u8)> = Vec::new();
+        for (slot, peg) in self.slots.iter().zip(pegs) {
+            match *slot {
+                Slot::Any => (),
+                Slot::Fixed(fixed) if fixed != *peg => return false,
+                Slot::Fixed(_) => (),
+                Slot::Letter(letter) => {
+                    match assigned
+                        .iter()
+                        .find(|(l, p)| *l == letter || p == peg)
+                    {
+                        Some(&(l, p)) if l != letter || p != *peg => {
+                            return false
+                        }
+                        Some(_) => (),
+                        None => assigned.push((letter, *peg)),
+                    }
+                }
+            }
+        }
+        true
+    }
+
+    // Fills in the template from `alphabet`, which must fit it.
+    pub fn generate(&self, alphabet: &[u8], rng: &mut dyn RngCore) -> Vec<u8> {
+        let letters = self.letters();
+        let pegs = alphabet
+            .choose_multiple(rng, letters.len())
+            .copied()
+            .collect::<Vec<_>>();
+
+        self.slots
+            .iter()
+            .map(|slot| match *slot {
+                Slot::Any => alphabet[rng.gen_range(0..alphabet.len())],
+                Slot::Fixed(peg) => peg,
+                Slot::Letter(letter) => {
+                    let i = letters.iter().position(|l| *l == letter).unwrap();
+                    pegs[i]
+                }
+            })
+            .collect()
+    }
+}
+
+impl FromStr for Template {
+    type Err = TemplateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let slots = s
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| match c {
+                '?' => Ok(Slot::Any),
+                '0'..='9' => Ok(Slot::Fixed(c as u8 - b'0')),
+                'A'..='Z' | 'a'..='z' => {
+                    Ok(Slot::Letter(c.to_ascii_uppercase() as u8))
+                }
+                _ => Err(TemplateError::InvalidChar(c)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        match slots.is_empty() {
+            true => Err(TemplateError::Empty),
+            false => Ok(Self { slots }),
+        }
+    }
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("empty template"),
+            Self::InvalidChar(c) => write!(f, "`{}` is not a template slot", c),
+        }
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use super::*;
+
+    #[test]
+    fn templates_are_parsed() {
+        let template = "A?AB".parse::<Template>().unwrap();
+        assert_eq!(template.len(), 4);
+        assert_eq!(template.letters(), [b'A', b'B']);
+        assert_eq!("a 1 a".parse::<Template>().unwrap().len(), 3);
+
+        assert_eq!("".parse::<Template>(), Err(TemplateError::Empty));
+        assert_eq!(
+            "AB-A".parse::<Template>(),
+            Err(TemplateError::InvalidChar('-'))
+        );
+    }
+
+    #[test]
+    fn matches_checks_letters_and_fixed_pegs() {
+        let template = "A?AB".parse::<Template>().unwrap();
+        assert!(template.matches(&[3, 0, 3, 5]));
+        assert!(template.matches(&[3, 3, 3, 5]));
+        assert!(!template.matches(&[3, 0, 4, 5]));
+        assert!(!template.matches(&[3, 0, 3, 3]));
+        assert!(!template.matches(&[3, 0, 3]));
+
+        let template = "1AA".parse::<Template>().unwrap();
+        assert!(template.matches(&[1, 2, 2]));
+        assert!(!template.matches(&[2, 2, 2]));
+    }
+
+    #[test]
+    fn generated_secrets_match() {
+        let template = "AB?BA2".parse::<Template>().unwrap();
+        let alphabet = [0, 1, 2, 3, 4, 5];
+        for _ in 0..50 {
+            let pegs = template.generate(&alphabet, &mut thread_rng());
+            assert!(template.matches(&pegs), "{:?}", pegs);
+        }
+    }
+}
@@ -1,30 +1,69 @@
-use rand::prelude::{thread_rng, Rng};
+use super::{registry, BuildError, Game, GameBuilder, HintChannel};
 
-use super::{Game, GameBuilder};
-
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Default, PartialEq)]
 pub enum Variant {
+    #[default]
     Classic,
+    // Five pegs of eight colors, as in Super Mastermind.
     Advanced,
+    // Digits 0-9, with arithmetic hints alongside the usual feedback.
+    Number,
+    Custom(String),
 }
 
-impl Default for Variant {
-    fn default() -> Self { Self::Classic }
+impl Variant {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "classic" => Some(Self::Classic),
+            "advanced" => Some(Self::Advanced),
+            "number" => Some(Self::Number),
+            name if registry::is_registered(name) => {
+                Some(Self::Custom(name.to_owned()))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Classic => "classic",
+            Self::Advanced => "advanced",
+            Self::Number => "number",
+            Self::Custom(name) => name,
+        }
+    }
 }
 
-impl From<Variant> for GameBuilder {
-    fn from(variant: Variant) -> Self {
+// Fails only for a custom variant that isn't registered.
+impl TryFrom<Variant> for GameBuilder {
+    type Error = BuildError;
+
+    fn try_from(variant: Variant) -> Result<Self, BuildError> {
         use Variant::*;
 
         match variant {
-            Classic => GameBuilder::default(),
-            _ => unimplemented!(),
+            Classic => Ok(GameBuilder::default()),
+            Advanced => Ok(GameBuilder::default()
+                .peg_count(5)
+                .peg_range(8)
+                .max_guesses(12)),
+            Number => Ok(GameBuilder::default()
+                .peg_range(10)
+                .hint_channel(HintChannel::DigitSumParity)
+                .hint_channel(HintChannel::HigherLower)),
+            Custom(name) => {
+                registry::builder(&name).ok_or(BuildError::UnknownVariant(name))
+            }
         }
     }
 }
 
-impl From<Variant> for Game {
-    fn from(variant: Variant) -> Self { GameBuilder::from(variant).into() }
+impl TryFrom<Variant> for Game {
+    type Error = BuildError;
+
+    fn try_from(variant: Variant) -> Result<Self, BuildError> {
+        GameBuilder::try_from(variant)?.try_build()
+    }
 }
 
 #[cfg(test)]
@@ -35,4 +74,54 @@ mod tests {
     fn default_is_classic() {
         assert_eq!(Variant::default(), Variant::Classic);
     }
+
+    #[test]
+    fn names_round_trip() {
+        registry::register("variant-test", || GameBuilder::new().peg_count(3));
+
+        for variant in [
+            Variant::Classic,
+            Variant::Advanced,
+            Variant::Number,
+            Variant::Custom("variant-test".to_owned()),
+        ] {
+            assert_eq!(Variant::from_name(variant.name()), Some(variant));
+        }
+        assert_eq!(Variant::from_name("Classic"), Some(Variant::Classic));
+        assert_eq!(Variant::from_name("unknown"), None);
+    }
+
+    #[test]
+    fn custom_variants_use_the_registry() {
+        registry::register("five-pegs", || GameBuilder::new().peg_count(5));
+
+        let variant = Variant::from_name("five-pegs").unwrap();
+        let game = Game::try_from(variant).unwrap();
+        assert_eq!(game.pegs().len(), 5);
+    }
+
+    #[test]
+    fn number_variant_gives_arithmetic_hints() {
+        let mut game = Game::try_from(Variant::Number).unwrap();
+        assert_eq!(game.rules().hint_channels.len(), 2);
+
+        game.guess(&[9, 9, 9, 9]).unwrap();
+        assert_eq!(game.arithmetic_hints(0).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn advanced_variant_is_bigger() {
+        let game = Game::try_from(Variant::Advanced).unwrap();
+        assert_eq!(game.pegs().len(), 5);
+        assert_eq!(game.alphabet().len(), 8);
+    }
+
+    #[test]
+    fn unregistered_custom_variant_is_an_error() {
+        let name = "never registered".to_owned();
+        assert_eq!(
+            Game::try_from(Variant::Custom(name.clone())).err(),
+            Some(BuildError::UnknownVariant(name))
+        );
+    }
 }
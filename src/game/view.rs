@@ -0,0 +1,80 @@
+use std::{cmp::Ordering, ops::RangeInclusive};
+
+use super::{ArithmeticHint, Game, Handicap, Hint, Rules};
+
+// A read-only view of a game for renderers, analysers and spectators. It shows
+// everything the codebreaker can see, but can't change the game and only
+// gives away the secret once the game is over.
+#[derive(Clone, Copy)]
+pub struct BoardView<'a> {
+    game: &'a Game,
+}
+
+impl<'a> BoardView<'a> {
+    pub fn guesses(&self) -> &'a [Vec<u8>] { self.game.guesses() }
+    pub fn rules(&self) -> &'a Rules { self.game.rules() }
+    pub fn handicap(&self) -> Handicap { self.game.handicap() }
+    pub fn max_guesses(&self) -> Option<u8> { self.game.max_guesses() }
+    pub fn peg_counts(&self) -> RangeInclusive<u8> { self.game.peg_counts() }
+    pub fn alphabet(&self) -> &'a [u8] { self.game.alphabet() }
+    pub fn revealed(&self) -> Vec<(usize, u8)> { self.game.revealed() }
+    pub fn hints(&self) -> &'a [Hint] { self.game.hints() }
+
+    pub fn is_over(&self) -> bool { self.game.is_over() }
+    pub fn is_solved(&self) -> bool { self.game.is_solved() }
+    pub fn is_forfeited(&self) -> bool { self.game.is_forfeited() }
+    pub fn is_variable_length(&self) -> bool { self.game.is_variable_length() }
+    pub fn is_guessable(&self, peg: u8) -> bool { self.game.is_guessable(peg) }
+
+    pub fn hits(&self, index: usize) -> Option<(u8, u8)> {
+        self.game.hits(index)
+    }
+    pub fn mask(&self, index: usize) -> Option<Vec<bool>> {
+        self.game.mask(index)
+    }
+    pub fn arithmetic_hints(
+        &self,
+        index: usize,
+    ) -> Option<Vec<ArithmeticHint>> {
+        self.game.arithmetic_hints(index)
+    }
+    pub fn length_hint(&self, index: usize) -> Option<Ordering> {
+        self.game.length_hint(index)
+    }
+
+    // The secret, once there's nothing left to give away.
+    pub fn secret(&self) -> Option<&'a [u8]> {
+        self.is_over().then(|| self.game.pegs())
+    }
+}
+
+impl<'a> From<&'a Game> for BoardView<'a> {
+    fn from(game: &'a Game) -> Self { Self { game } }
+}
+
+impl<'a> From<&'a mut Game> for BoardView<'a> {
+    fn from(game: &'a mut Game) -> Self { Self { game } }
+}
+
+impl Game {
+    pub fn view(&self) -> BoardView<'_> { self.into() }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::GameBuilder;
+
+    #[test]
+    fn views_hide_the_secret_until_the_end() {
+        let mut game = GameBuilder::new().pegs(&[1, 2, 3, 4]).build();
+        game.guess(&[1, 1, 2, 2]).unwrap();
+
+        let view = game.view();
+        assert_eq!(view.guesses(), game.guesses());
+        assert_eq!(view.hits(0), Some((1, 1)));
+        assert_eq!(view.secret(), None);
+
+        game.forfeit();
+        assert_eq!(game.view().secret(), Some(&[1, 2, 3, 4][..]));
+    }
+}
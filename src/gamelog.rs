@@ -0,0 +1,211 @@
+// An append-only archive of finished games, one JSON object per line:
+//
+//     {"finished_at": 1718000000, "duration_ms": 5321, "outcome": "Solved",
+//      "hints": 0, "colors": 6, "allow_empty": false, "secret": [1, 2, 3, 4],
+//      "max_guesses": 12, "guesses": [{"guess": [...], ...}]}
+//
+// The game itself is in the `exchange` game form, flattened into the entry.
+// Once a log grows past its size limit it's rotated: `games.jsonl` becomes
+// `games.jsonl.1`, the old `.1` becomes `.2`, and so on, dropping the oldest.
+
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    exchange::GameRecord,
+    game::{Outcome, Summary},
+    Game,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogEntry {
+    // Seconds since the Unix epoch.
+    pub finished_at: u64,
+    pub duration_ms: u64,
+    pub outcome:     Outcome,
+    pub hints:       usize,
+    #[serde(flatten)]
+    pub game:        GameRecord,
+}
+
+impl LogEntry {
+    // None until the game is over.
+    pub fn of(game: &Game) -> Option<Self> {
+        let summary = Summary::of(game)?;
+        let finished_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_secs());
+        Some(Self {
+            finished_at,
+            duration_ms: summary.duration.as_millis() as u64,
+            outcome: summary.outcome,
+            hints: summary.hints,
+            game: game.into(),
+        })
+    }
+}
+
+pub struct GameLog {
+    path:      PathBuf,
+    max_bytes: Option<u64>,
+    keep:      usize,
+}
+
+impl GameLog {
+    // A log that grows forever, until `rotate_at` is set.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path:      path.into(),
+            max_bytes: None,
+            keep:      5,
+        }
+    }
+
+    pub fn rotate_at(mut self, bytes: u64) -> Self {
+        self.max_bytes = Some(bytes);
+        self
+    }
+
+    // How many rotated files are kept besides the current one.
+    pub fn keep(mut self, files: usize) -> Self {
+        self.keep = files;
+        self
+    }
+
+    pub fn path(&self) -> &Path { &self.path }
+
+    // The path of the `n`th most recent rotated file.
+    pub fn rotated(&self, n: usize) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{n}"));
+        path.into()
+    }
+
+    // Appends a finished game, doing nothing for games still in progress.
+    pub fn append(&self, game: &Game) -> io::Result<()> {
+        match LogEntry::of(game) {
+            Some(entry) => self.write(&entry),
+            None => Ok(()),
+        }
+    }
+
+    pub fn write(&self, entry: &LogEntry) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        self.rotate_if_full()?;
+
+        let mut line = serde_json::to_string(entry)?;
+        line.push('\n');
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?
+            .write_all(line.as_bytes())
+    }
+
+    // The entries in the current file, oldest first.
+    pub fn read(&self) -> io::Result<Vec<LogEntry>> {
+        let file = match File::open(&self.path) {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                return Ok(Vec::new())
+            }
+            file => file?,
+        };
+        BufReader::new(file)
+            .lines()
+            .filter(|line| !line.as_ref().is_ok_and(|l| l.trim().is_empty()))
+            .map(|line| Ok(serde_json::from_str(&line?)?))
+            .collect()
+    }
+
+    fn rotate_if_full(&self) -> io::Result<()> {
+        let full = match (self.max_bytes, fs::metadata(&self.path)) {
+            (Some(max), Ok(metadata)) => metadata.len() >= max,
+            _ => false,
+        };
+        if !full {
+            return Ok(());
+        }
+
+        if self.keep == 0 {
+            return fs::remove_file(&self.path);
+        }
+        for n in (1..self.keep).rev() {
+            let from = self.rotated(n);
+            if from.exists() {
+                fs::rename(from, self.rotated(n + 1))?;
+            }
+        }
+        fs::rename(&self.path, self.rotated(1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+    use crate::GameBuilder;
+
+    fn finished(secret: &[u8]) -> Game {
+        let mut game = GameBuilder::new().pegs(secret).build();
+        game.guess(&[0, 0, 0, 0]).unwrap();
+        game.guess(secret).unwrap();
+        game
+    }
+
+    fn log_in(name: &str) -> GameLog {
+        let dir = env::temp_dir()
+            .join(format!("mastermind-log-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        GameLog::new(dir.join("games.jsonl"))
+    }
+
+    #[test]
+    fn finished_games_are_appended() {
+        let log = log_in("append");
+        log.append(&GameBuilder::new().build()).unwrap();
+        assert!(log.read().unwrap().is_empty());
+
+        log.append(&finished(&[1, 2, 3, 4])).unwrap();
+        log.append(&finished(&[4, 3, 2, 1])).unwrap();
+
+        let entries = log.read().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].outcome, Outcome::Solved);
+        assert_eq!(entries[0].game.secret, [1, 2, 3, 4]);
+        assert_eq!(entries[1].game.guesses.len(), 2);
+
+        let text = fs::read_to_string(log.path()).unwrap();
+        assert_eq!(text.lines().count(), 2);
+        assert!(text.starts_with("{\"finished_at\":"));
+        assert!(text.contains("\"secret\":[4,3,2,1]"));
+
+        fs::remove_dir_all(log.path().parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn full_logs_are_rotated() {
+        let log = log_in("rotate").rotate_at(1).keep(2);
+        for secret in [[1, 1, 1, 1], [2, 2, 2, 2], [3, 3, 3, 3], [4, 4, 4, 4]] {
+            log.append(&finished(&secret)).unwrap();
+        }
+
+        let secret = |path: PathBuf| {
+            GameLog::new(path).read().unwrap()[0].game.secret.clone()
+        };
+        assert_eq!(secret(log.path().to_owned()), [4, 4, 4, 4]);
+        assert_eq!(secret(log.rotated(1)), [3, 3, 3, 3]);
+        assert_eq!(secret(log.rotated(2)), [2, 2, 2, 2]);
+        assert!(!log.rotated(3).exists());
+
+        fs::remove_dir_all(log.path().parent().unwrap()).unwrap();
+    }
+}
@@ -0,0 +1,160 @@
+use std::{fmt, ops::RangeInclusive};
+
+use crate::{
+    game::fits_length,
+    messages::{English, Message},
+    Game, EMPTY,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputError {
+    // A character that doesn't name any peg.
+    NotAPeg(String),
+    // A peg the game's secret can never contain.
+    NotInAlphabet(u8),
+    // The guess already has as many pegs as the longest allowed guess.
+    Full,
+}
+
+impl<'a> From<&'a InputError> for Message<'a> {
+    fn from(error: &'a InputError) -> Self {
+        match error {
+            InputError::NotAPeg(token) => Message::NotAPeg(token),
+            InputError::NotInAlphabet(peg) => Message::NotInAlphabet(*peg),
+            InputError::Full => Message::GuessFull,
+        }
+    }
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use crate::messages::Catalog;
+        f.write_str(&English.message(&self.into()))
+    }
+}
+
+impl std::error::Error for InputError {}
+
+// A guess being entered one peg at a time, checked against the game as it's
+// typed rather than when it's submitted. Pegs are typed as `0`-`9` then `A`-`Z`,
+// with `-` or `_` for an empty hole.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GuessInput {
+    pegs:       Vec<u8>,
+    guessable:  Vec<u8>,
+    peg_counts: RangeInclusive<u8>,
+}
+
+impl GuessInput {
+    pub fn new(game: &Game) -> Self {
+        Self {
+            pegs:       Vec::new(),
+            guessable:  (0..=u8::MAX)
+                .filter(|&peg| game.is_guessable(peg))
+                .collect(),
+            peg_counts: game.peg_counts(),
+        }
+    }
+
+    pub fn pegs(&self) -> &[u8] { &self.pegs }
+    pub fn clear(&mut self) { self.pegs.clear(); }
+    pub fn backspace(&mut self) -> Option<u8> { self.pegs.pop() }
+
+    // Slots left before the guess reaches its longest allowed length.
+    pub fn remaining(&self) -> usize {
+        (*self.peg_counts.end() as usize).saturating_sub(self.pegs.len())
+    }
+
+    // Whether the guess is long enough to submit.
+    pub fn is_complete(&self) -> bool {
+        fits_length(&self.peg_counts, self.pegs.len())
+    }
+
+    pub fn push(&mut self, peg: u8) -> Result<(), InputError> {
+        if self.remaining() == 0 {
+            return Err(InputError::Full);
+        }
+        if !self.guessable.contains(&peg) {
+            return Err(InputError::NotInAlphabet(peg));
+        }
+
+        self.pegs.push(peg);
+        Ok(())
+    }
+
+    pub fn push_char(&mut self, c: char) -> Result<(), InputError> {
+        let peg = match c {
+            '-' | '_' => EMPTY,
+            _ => c
+                .to_digit(36)
+                .ok_or_else(|| InputError::NotAPeg(c.to_string()))?
+                as u8,
+        };
+        self.push(peg)
+    }
+}
+
+// Shows the pegs entered so far followed by a `.` for each remaining slot,
+// e.g. `1 3 . .`.
+impl fmt::Display for GuessInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pegs = self.pegs.iter().map(|&peg| match peg {
+            EMPTY => '_',
+            _ => char::from_digit(peg as u32, 36)
+                .map_or('?', |c| c.to_ascii_uppercase()),
+        });
+        let slots = pegs
+            .chain(std::iter::repeat_n('.', self.remaining()))
+            .map(String::from)
+            .collect::<Vec<_>>();
+        f.write_str(&slots.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GameBuilder;
+
+    #[test]
+    fn validates_as_pegs_are_typed() {
+        let game = GameBuilder::new().peg_range(6).build();
+        let mut input = GuessInput::new(&game);
+
+        assert_eq!(input.push_char('1'), Ok(()));
+        assert_eq!(input.push_char('7'), Err(InputError::NotInAlphabet(7)));
+        assert_eq!(input.push_char('-'), Err(InputError::NotInAlphabet(EMPTY)));
+        assert_eq!(input.push_char('?'), Err(InputError::NotAPeg("?".into())));
+        assert_eq!(input.pegs(), [1]);
+        assert_eq!(input.to_string(), "1 . . .");
+
+        "234".chars().for_each(|c| input.push_char(c).unwrap());
+        assert!(input.is_complete());
+        assert_eq!(input.push(0), Err(InputError::Full));
+
+        assert_eq!(input.backspace(), Some(4));
+        assert_eq!(input.remaining(), 1);
+        input.clear();
+        assert_eq!(input.to_string(), ". . . .");
+    }
+
+    #[test]
+    fn follows_the_game_settings() {
+        let game = GameBuilder::new()
+            .peg_range(12)
+            .peg_count_range(2, 3)
+            .allow_empty(true)
+            .build();
+        let mut input = GuessInput::new(&game);
+
+        input.push_char('b').unwrap();
+        assert!(!input.is_complete());
+        input.push_char('_').unwrap();
+        assert!(input.is_complete());
+        assert_eq!(input.to_string(), "B _ .");
+        assert_eq!(
+            InputError::NotInAlphabet(12).to_string(),
+            "There is no peg 12 in this game."
+        );
+    }
+}
@@ -0,0 +1,561 @@
+use std::{cmp::Ordering, ops::RangeInclusive};
+
+use crate::{
+    game::{FeedbackMode, Hint},
+    BoardView,
+};
+
+// Whether `candidate` could be the secret, given everything the codebreaker
+// has been told: feedback on each guess, arithmetic and length hints, revealed
+// pegs and requested hints.
+pub fn is_consistent<'a>(
+    board: impl Into<BoardView<'a>>,
+    candidate: &[u8],
+) -> bool {
+    is_consistent_since(board.into(), candidate, &Seen::default())
+}
+
+// The guesses and hints on a board at some point, so that candidates can be
+// checked against only what's been added since.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct Seen {
+    guesses: Vec<Vec<u8>>,
+    hints:   Vec<Hint>,
+}
+
+impl Seen {
+    pub(crate) fn of(board: BoardView) -> Self {
+        Self {
+            guesses: board.guesses().to_vec(),
+            hints:   board.hints().to_vec(),
+        }
+    }
+
+    // Whether the board has only had guesses and hints added since, rather
+    // than going back to an earlier point.
+    pub(crate) fn is_before(&self, board: BoardView) -> bool {
+        board.guesses().starts_with(&self.guesses)
+            && board.hints().starts_with(&self.hints)
+    }
+}
+
+// Whether `candidate` fits the guesses and hints added to the board since
+// `seen`, assuming it fit everything before. Revealed pegs never change, so
+// they're only checked from the start.
+pub(crate) fn is_consistent_since(
+    board: BoardView,
+    candidate: &[u8],
+    seen: &Seen,
+) -> bool {
+    let rules = board.rules();
+    let count = |peg| candidate.iter().filter(|&&p| p == peg).count();
+
+    let revealed = seen != &Seen::default()
+        || board
+            .revealed()
+            .iter()
+            .all(|&(position, peg)| candidate.get(position) == Some(&peg));
+
+    let new_hints = &board.hints()[seen.hints.len()..];
+    let hinted = new_hints.iter().all(|hint| match *hint {
+        Hint::Position { position, peg } => {
+            candidate.get(position) == Some(&peg)
+        }
+        Hint::Absent(peg) => count(peg) == 0,
+        Hint::Present(peg) => count(peg) > 0,
+        Hint::ColorCountEven { peg, even } => (count(peg) % 2 == 0) == even,
+    });
+
+    let new_guesses = board.guesses().iter().enumerate();
+    let guessed = new_guesses.skip(seen.guesses.len()).all(|(i, guess)| {
+        rules.score(candidate, guess) == board.hits(i).unwrap()
+            && candidate.len().cmp(&guess.len())
+                == board.length_hint(i).unwrap()
+            && rules.arithmetic_hints(candidate, guess)
+                == board.arithmetic_hints(i).unwrap()
+            && (rules.feedback_mode != FeedbackMode::Positional
+                || rules.mask(candidate, guess) == board.mask(i).unwrap())
+    });
+
+    revealed && hinted && guessed
+}
+
+// Every code the board could produce, of every allowed length, in order.
+pub fn all_codes<'a>(board: impl Into<BoardView<'a>>) -> Vec<Vec<u8>> {
+    let board = board.into();
+    codes(board.alphabet(), board.peg_counts())
+}
+
+// Every code made from `alphabet` with one of the given lengths, in order.
+pub fn codes(alphabet: &[u8], lengths: RangeInclusive<u8>) -> Vec<Vec<u8>> {
+    let mut codes = Vec::new();
+
+    for length in lengths {
+        let mut digits = vec![0; length as usize];
+        loop {
+            codes.push(digits.iter().map(|&d| alphabet[d]).collect());
+
+            // Counts up in base `alphabet.len()`, last position fastest.
+            match digits.iter().rposition(|&d| d + 1 < alphabet.len()) {
+                Some(i) => {
+                    digits[i] += 1;
+                    digits[i + 1..].iter_mut().for_each(|d| *d = 0);
+                }
+                None => break,
+            }
+        }
+    }
+    codes
+}
+
+// What a guess told the codebreaker, worked out once so partial codes can be
+// checked against it cheaply.
+struct Clue<'a> {
+    guess:  &'a [u8],
+    hits:   usize,
+    // Hits and near hits together: how many pegs the code and the guess have
+    // in common, wherever they are.
+    common: usize,
+    mask:   Option<Vec<bool>>,
+    length: Ordering,
+}
+
+// The codes consistent with a board, generated lazily in the same order as
+// `all_codes`. Partial codes that can no longer fit are skipped along with
+// every code starting with them, so finding the first few candidates never
+// means enumerating every code.
+pub struct ConsistentCodes<'a> {
+    board:    BoardView<'a>,
+    clues:    Vec<Clue<'a>>,
+    // Whether partial codes can be pruned by their exact hits, and by the
+    // pegs they share with each guess. Jokers and custom feedback can't.
+    by_hits:  bool,
+    by_pegs:  bool,
+    lengths:  RangeInclusive<u8>,
+    len:      Option<usize>,
+    revealed: Vec<(usize, u8)>,
+    // The partial code being looked at, and the alphabet index of each peg.
+    code:     Vec<u8>,
+    digits:   Vec<usize>,
+    descend:  bool,
+}
+
+pub fn consistent_codes<'a>(
+    board: impl Into<BoardView<'a>>,
+) -> ConsistentCodes<'a> {
+    let board = board.into();
+    let rules = board.rules();
+    let clues = board
+        .guesses()
+        .iter()
+        .enumerate()
+        .map(|(i, guess)| {
+            let (hits, near_hits) = board.hits(i).unwrap();
+            Clue {
+                guess,
+                hits: hits as usize,
+                common: (hits + near_hits) as usize,
+                mask: (rules.feedback_mode == FeedbackMode::Positional)
+                    .then(|| board.mask(i).unwrap()),
+                length: board.length_hint(i).unwrap(),
+            }
+        })
+        .collect();
+
+    let by_hits = rules.joker.is_none()
+        && !matches!(rules.feedback_mode, FeedbackMode::Custom(_));
+    let mut lengths = board.peg_counts();
+    ConsistentCodes {
+        board,
+        clues,
+        by_hits,
+        by_pegs: by_hits && rules.feedback_mode == FeedbackMode::Standard,
+        len: lengths.next().map(usize::from),
+        lengths,
+        revealed: board.revealed(),
+        code: Vec::new(),
+        digits: Vec::new(),
+        descend: true,
+    }
+}
+
+impl ConsistentCodes<'_> {
+    // Whether some code of length `len` starting with `prefix` could still
+    // fit the board.
+    fn could_fit(&self, prefix: &[u8], len: usize) -> bool {
+        let board = self.board;
+        let position = prefix.len() - 1;
+        let peg = prefix[position];
+
+        let revealed = self
+            .revealed
+            .iter()
+            .all(|&(i, revealed)| i != position || revealed == peg);
+        let hinted = board.hints().iter().all(|hint| match *hint {
+            Hint::Position {
+                position: i,
+                peg: hinted,
+            } => i != position || hinted == peg,
+            Hint::Absent(absent) => absent != peg,
+            _ => true,
+        });
+        if !revealed || !hinted {
+            return false;
+        }
+
+        self.clues.iter().all(|clue| {
+            if len.cmp(&clue.guess.len()) != clue.length {
+                return false;
+            }
+            if let Some(mask) = &clue.mask {
+                let exact = clue.guess.get(position) == Some(&peg);
+                if mask.get(position).is_some_and(|&hit| hit != exact) {
+                    return false;
+                }
+            }
+
+            // Whatever the rest of the code is, the hits (or shared pegs) so
+            // far can only grow by one per remaining position.
+            let fits = |so_far: usize, open: usize, wanted: usize| {
+                so_far <= wanted && so_far + open >= wanted
+            };
+            let overlap = len.min(clue.guess.len());
+            let hits = prefix.iter().zip(clue.guess).filter(|(a, b)| a == b);
+            let hits_fit = !self.by_hits
+                || fits(
+                    hits.count(),
+                    overlap.saturating_sub(prefix.len()),
+                    clue.hits,
+                );
+            let pegs_fit = !self.by_pegs
+                || fits(
+                    common_pegs(prefix, clue.guess),
+                    len - prefix.len(),
+                    clue.common,
+                );
+            hits_fit && pegs_fit
+        })
+    }
+}
+
+// How many pegs two codes have in common, ignoring position.
+fn common_pegs(a: &[u8], b: &[u8]) -> usize {
+    let mut b = b.to_vec();
+    a.iter()
+        .filter(|peg| match b.iter().position(|p| p == *peg) {
+            Some(i) => {
+                b.swap_remove(i);
+                true
+            }
+            None => false,
+        })
+        .count()
+}
+
+impl Iterator for ConsistentCodes<'_> {
+    type Item = Vec<u8>;
+
+    fn next(&mut self) -> Option<Vec<u8>> {
+        let alphabet = self.board.alphabet();
+        loop {
+            let len = self.len?;
+
+            // Step to the next partial code, depth first.
+            if self.descend && self.digits.len() < len {
+                self.digits.push(0);
+            }
+            else {
+                loop {
+                    match self.digits.last_mut() {
+                        Some(digit) if *digit + 1 < alphabet.len() => {
+                            *digit += 1;
+                            break;
+                        }
+                        Some(_) => {
+                            self.digits.pop();
+                        }
+                        None => break,
+                    }
+                }
+                if self.digits.is_empty() {
+                    self.len = self.lengths.next().map(usize::from);
+                    self.descend = true;
+                    continue;
+                }
+            }
+
+            // Only the last peg changed.
+            let last = self.digits.len() - 1;
+            self.code.truncate(last);
+            self.code.push(alphabet[self.digits[last]]);
+
+            self.descend = self.could_fit(&self.code, len);
+            if self.descend && self.code.len() == len {
+                self.descend = false;
+                if is_consistent(self.board, &self.code) {
+                    return Some(self.code.clone());
+                }
+            }
+        }
+    }
+}
+
+// What the codebreaker knows about the secret so far: every code still
+// consistent with the game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Knowledge {
+    alphabet:   Vec<u8>,
+    candidates: Vec<Vec<u8>>,
+    // How many codes were possible before anything was known.
+    total:      usize,
+    seen:       Seen,
+}
+
+impl Knowledge {
+    pub fn new<'a>(board: impl Into<BoardView<'a>>) -> Self {
+        let board = board.into();
+        let colors = board.alphabet().len();
+        Self {
+            alphabet:   board.alphabet().to_owned(),
+            total:      board
+                .peg_counts()
+                .map(|len| colors.saturating_pow(len as u32))
+                .fold(0, usize::saturating_add),
+            candidates: consistent_codes(board).collect(),
+            seen:       Seen::of(board),
+        }
+    }
+
+    // Catches up with the board. New guesses and hints only narrow down the
+    // current candidates; if the board went back to an earlier point (say,
+    // after restoring a snapshot) everything is worked out again.
+    pub fn update<'a>(&mut self, board: impl Into<BoardView<'a>>) {
+        let board = board.into();
+        if !self.seen.is_before(board) {
+            *self = Self::new(board);
+            return;
+        }
+        self.candidates
+            .retain(|code| is_consistent_since(board, code, &self.seen));
+        self.seen = Seen::of(board);
+    }
+
+    pub fn alphabet(&self) -> &[u8] { &self.alphabet }
+    pub fn candidates(&self) -> &[Vec<u8>] { &self.candidates }
+    pub fn is_solved(&self) -> bool { self.candidates.len() == 1 }
+
+    // Information about the secret still missing, and already learned, in
+    // bits.
+    pub fn bits_remaining(&self) -> f64 {
+        (self.candidates.len().max(1) as f64).log2()
+    }
+    pub fn bits_learned(&self) -> f64 {
+        (self.total as f64).log2() - self.bits_remaining()
+    }
+
+    // How much of the secret has been worked out, from 0 to 1, measured in
+    // information rather than guesses.
+    pub fn progress(&self) -> f64 {
+        match self.total {
+            0 | 1 => 1.0,
+            total => self.bits_learned() / (total as f64).log2(),
+        }
+    }
+
+    // The colors still possible in `position`, in alphabet order, for
+    // greying out palette choices while composing a guess.
+    pub fn possible_colors(&self, position: usize) -> Vec<u8> {
+        self.alphabet
+            .iter()
+            .copied()
+            .filter(|&peg| self.is_possible(position, peg))
+            .collect()
+    }
+
+    pub fn is_possible(&self, position: usize, peg: u8) -> bool {
+        self.candidates
+            .iter()
+            .any(|candidate| candidate.get(position) == Some(&peg))
+    }
+
+    // For each position and each color in `alphabet()`, the fraction of
+    // candidates with that color in that position. Rows for positions past
+    // the end of shorter candidates may sum to less than one.
+    pub fn heatmap(&self) -> Vec<Vec<f64>> {
+        let positions = self.candidates.iter().map(Vec::len).max().unwrap_or(0);
+        let mut counts = vec![vec![0; self.alphabet.len()]; positions];
+
+        for candidate in &self.candidates {
+            for (position, peg) in candidate.iter().enumerate() {
+                let color =
+                    self.alphabet.iter().position(|c| c == peg).unwrap();
+                counts[position][color] += 1;
+            }
+        }
+
+        let total = self.candidates.len().max(1) as f64;
+        counts
+            .into_iter()
+            .map(|row| row.into_iter().map(|n| n as f64 / total).collect())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{game::HintKind, GameBuilder};
+
+    #[test]
+    fn enumerates_every_code() {
+        let game = GameBuilder::new()
+            .peg_range(3)
+            .peg_count_range(1, 2)
+            .build();
+        let codes = all_codes(&game);
+        assert_eq!(codes.len(), 3 + 9);
+        assert_eq!(codes[0], [0]);
+        assert_eq!(codes[3], [0, 0]);
+        assert_eq!(codes[11], [2, 2]);
+    }
+
+    #[test]
+    fn lazy_codes_match_filtering_every_code() {
+        let boards = [
+            GameBuilder::new().pegs(&[1, 2, 3, 4]),
+            GameBuilder::new()
+                .peg_range(4)
+                .peg_count_range(2, 4)
+                .pegs(&[3, 0, 3]),
+            GameBuilder::new()
+                .pegs(&[1, 2, 3, 4])
+                .feedback_mode(FeedbackMode::Positional),
+            GameBuilder::new()
+                .pegs(&[1, 2, 3, 4])
+                .joker(crate::Joker::InGuess(6)),
+        ];
+        for builder in boards {
+            let mut game = builder.allow_empty(true).build();
+            game.hint(HintKind::EliminateColor).unwrap();
+            for guess in [[0, 0, 1, 1], [2, 3, 0, 6], [1, 3, 3, 3]] {
+                let len = game.peg_counts().end().min(&4).to_owned();
+                game.guess(&guess[..len as usize]).unwrap();
+
+                let brute = all_codes(&game)
+                    .into_iter()
+                    .filter(|code| is_consistent(&game, code))
+                    .collect::<Vec<_>>();
+                assert_eq!(consistent_codes(&game).collect::<Vec<_>>(), brute);
+            }
+        }
+    }
+
+    #[test]
+    fn lazy_codes_skip_huge_spaces() {
+        // Ten billion codes, but only the first few are ever looked at.
+        let mut game = GameBuilder::new()
+            .peg_count(10)
+            .peg_range(10)
+            .pegs(&[9; 10])
+            .build();
+        game.guess(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]).unwrap();
+        let first = consistent_codes(&game).take(3).collect::<Vec<_>>();
+        // One hit and nothing else in common: a single color throughout.
+        assert_eq!(first, [[0; 10], [1; 10], [2; 10]]);
+    }
+
+    #[test]
+    fn narrows_candidates_with_feedback() {
+        let mut game = GameBuilder::new().pegs(&[1, 2, 3, 4]).build();
+        assert_eq!(Knowledge::new(&game).candidates().len(), 1296);
+
+        game.guess(&[0, 0, 1, 1]).unwrap();
+        game.guess(&[2, 2, 3, 3]).unwrap();
+        let knowledge = Knowledge::new(&game);
+        assert!(knowledge.candidates().contains(&vec![1, 2, 3, 4]));
+        assert!(knowledge
+            .candidates()
+            .iter()
+            .all(|code| is_consistent(&game, code)));
+        assert!(knowledge.candidates().len() < 100);
+
+        game.guess(&[1, 2, 3, 4]).unwrap();
+        assert!(Knowledge::new(&game).is_solved());
+    }
+
+    #[test]
+    fn hints_and_reveals_narrow_candidates() {
+        let mut game = GameBuilder::new().pegs(&[1, 2, 3, 4]).build();
+        game.hint(HintKind::RevealPosition).unwrap();
+        game.hint(HintKind::EliminateColor).unwrap();
+        assert_eq!(Knowledge::new(&game).candidates().len(), 5 * 5 * 5);
+    }
+
+    #[test]
+    fn updates_match_starting_over() {
+        let mut game = GameBuilder::new().pegs(&[1, 2, 3, 4]).build();
+        let mut knowledge = Knowledge::new(&game);
+
+        game.guess(&[0, 0, 1, 1]).unwrap();
+        let snapshot = game.snapshot();
+        game.hint(HintKind::EliminateColor).unwrap();
+        game.guess(&[2, 2, 3, 3]).unwrap();
+        knowledge.update(&game);
+        assert_eq!(knowledge, Knowledge::new(&game));
+
+        // Going back means working everything out again.
+        game.restore(&snapshot);
+        game.guess(&[1, 1, 2, 2]).unwrap();
+        knowledge.update(&game);
+        assert_eq!(knowledge, Knowledge::new(&game));
+    }
+
+    #[test]
+    fn heatmap_follows_candidates() {
+        let mut game = GameBuilder::new()
+            .pegs(&[0, 1])
+            .peg_count(2)
+            .peg_range(2)
+            .build();
+        game.guess(&[0, 0]).unwrap();
+
+        // Exactly one 0 in the right place: [0, 1] or [1, 0].
+        let knowledge = Knowledge::new(&game);
+        assert_eq!(knowledge.candidates(), [vec![0, 1], vec![1, 0]]);
+        assert_eq!(knowledge.heatmap(), [vec![0.5, 0.5], vec![0.5, 0.5]]);
+    }
+
+    #[test]
+    fn progress_is_measured_in_bits() {
+        let mut game = GameBuilder::new()
+            .pegs(&[0, 1])
+            .peg_count(2)
+            .peg_range(4)
+            .build();
+        let knowledge = Knowledge::new(&game);
+        assert_eq!(knowledge.bits_remaining(), 4.0);
+        assert_eq!(knowledge.progress(), 0.0);
+
+        // Rules out every code containing a 2 or a 3: 4 of 16 remain.
+        game.guess(&[2, 3]).unwrap();
+        let knowledge = Knowledge::new(&game);
+        assert_eq!(knowledge.bits_learned(), 2.0);
+        assert_eq!(knowledge.progress(), 0.5);
+
+        game.guess(&[0, 1]).unwrap();
+        assert_eq!(Knowledge::new(&game).progress(), 1.0);
+    }
+
+    #[test]
+    fn possible_colors_per_position() {
+        let mut game = GameBuilder::new().pegs(&[1, 2, 3, 4]).build();
+        game.guess(&[0, 5, 5, 5]).unwrap();
+        game.guess(&[1, 2, 0, 0]).unwrap();
+
+        let knowledge = Knowledge::new(&game);
+        assert_eq!(knowledge.possible_colors(0), [1]);
+        assert_eq!(knowledge.possible_colors(2), [1, 2, 3, 4]);
+        assert!(!knowledge.is_possible(1, 5));
+        assert!(knowledge.possible_colors(4).is_empty());
+    }
+}
@@ -0,0 +1,238 @@
+use std::{collections::HashMap, time::Duration};
+
+use crate::Game;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Entry {
+    pub player:   String,
+    pub guesses:  u8,
+    pub duration: Duration,
+    // Submission order, used to break ties in favour of whoever got there
+    // first.
+    pub sequence: u64,
+}
+
+impl Entry {
+    fn beats(&self, other: &Self) -> bool { self.rank_key() < other.rank_key() }
+    fn rank_key(&self) -> (u8, Duration, u64) {
+        (self.guesses, self.duration, self.sequence)
+    }
+}
+
+// Best results per player, grouped into boards. A board is any string that
+// identifies comparable games, such as a config ("4x6") or a daily puzzle.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Leaderboard {
+    boards:   HashMap<String, Vec<Entry>>,
+    sequence: u64,
+}
+
+impl Leaderboard {
+    pub fn new() -> Self { Self::default() }
+
+    // A board name for games with the same shape as `game`.
+    pub fn board_for(game: &Game) -> String {
+        format!("{}x{}", game.pegs().len(), game.alphabet().len())
+    }
+
+    // Submits a solved game, returning the player's rank on the board if
+    // this became their best result.
+    pub fn submit(
+        &mut self,
+        board: &str,
+        player: &str,
+        game: &Game,
+        duration: Duration,
+    ) -> Option<usize> {
+        if !game.is_solved() {
+            return None;
+        }
+
+        self.sequence += 1;
+        let entry = Entry {
+            player: player.to_owned(),
+            guesses: game.guesses().len() as u8,
+            duration,
+            sequence: self.sequence,
+        };
+
+        let entries = self.boards.entry(board.to_owned()).or_default();
+        match entries.iter().position(|e| e.player == player) {
+            Some(i) if !entry.beats(&entries[i]) => return None,
+            Some(i) => entries[i] = entry,
+            None => entries.push(entry),
+        }
+        entries.sort_by_key(Entry::rank_key);
+
+        self.rank(board, player)
+    }
+
+    // Puts back an entry from a stored leaderboard, replacing the player's
+    // entry on that board. Later submissions are numbered after it.
+    pub fn restore(&mut self, board: &str, entry: Entry) {
+        self.sequence = self.sequence.max(entry.sequence);
+        let entries = self.boards.entry(board.to_owned()).or_default();
+        entries.retain(|e| e.player != entry.player);
+        entries.push(entry);
+        entries.sort_by_key(Entry::rank_key);
+    }
+
+    // A page of the board, best first. Pages are numbered from zero.
+    pub fn page(&self, board: &str, page: usize, per_page: usize) -> &[Entry] {
+        let entries = self.entries(board);
+        let start = (page * per_page).min(entries.len());
+        let end = (start + per_page).min(entries.len());
+        &entries[start..end]
+    }
+
+    pub fn top(&self, board: &str, count: usize) -> &[Entry] {
+        self.page(board, 0, count)
+    }
+
+    // The player's 1-based rank on the board.
+    pub fn rank(&self, board: &str, player: &str) -> Option<usize> {
+        self.entries(board)
+            .iter()
+            .position(|entry| entry.player == player)
+            .map(|i| i + 1)
+    }
+
+    pub fn entries(&self, board: &str) -> &[Entry] {
+        self.boards.get(board).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn boards(&self) -> impl Iterator<Item = &str> {
+        self.boards.keys().map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GameBuilder;
+
+    fn solved_in(guesses: usize) -> Game {
+        let mut game = GameBuilder::new().pegs(&[1, 2, 3, 4]).build();
+        for _ in 1..guesses {
+            game.guess(&[0, 0, 0, 0]).unwrap();
+        }
+        game.guess(&[1, 2, 3, 4]).unwrap();
+        game
+    }
+
+    #[test]
+    fn entries_are_ranked() {
+        let mut board = Leaderboard::new();
+        let secs = Duration::from_secs;
+
+        assert_eq!(
+            board.submit("4x6", "ann", &solved_in(5), secs(30)),
+            Some(1)
+        );
+        assert_eq!(
+            board.submit("4x6", "bob", &solved_in(4), secs(90)),
+            Some(1)
+        );
+        // Fewer guesses wins, then faster time, then earlier submission.
+        assert_eq!(
+            board.submit("4x6", "cat", &solved_in(5), secs(20)),
+            Some(2)
+        );
+        assert_eq!(
+            board.submit("4x6", "dan", &solved_in(5), secs(20)),
+            Some(3)
+        );
+
+        let players = board
+            .entries("4x6")
+            .iter()
+            .map(|e| e.player.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(players, ["bob", "cat", "dan", "ann"]);
+    }
+
+    #[test]
+    fn only_personal_bests_are_kept() {
+        let mut board = Leaderboard::new();
+        let secs = Duration::from_secs;
+
+        board.submit("4x6", "ann", &solved_in(4), secs(30));
+        assert_eq!(board.submit("4x6", "ann", &solved_in(6), secs(10)), None);
+        assert_eq!(
+            board.submit("4x6", "ann", &solved_in(3), secs(60)),
+            Some(1)
+        );
+        assert_eq!(board.entries("4x6").len(), 1);
+        assert_eq!(board.entries("4x6")[0].guesses, 3);
+    }
+
+    #[test]
+    fn unsolved_games_are_ignored() {
+        let mut board = Leaderboard::new();
+        let game = GameBuilder::new().build();
+        assert_eq!(board.submit("4x6", "ann", &game, Duration::ZERO), None);
+        assert!(board.entries("4x6").is_empty());
+    }
+
+    #[test]
+    fn pages_split_the_board() {
+        let mut board = Leaderboard::new();
+        for i in 0..25 {
+            let player = format!("player{}", i);
+            board.submit(
+                "daily",
+                &player,
+                &solved_in(3),
+                Duration::from_secs(i),
+            );
+        }
+
+        assert_eq!(board.page("daily", 0, 10).len(), 10);
+        assert_eq!(board.page("daily", 2, 10).len(), 5);
+        assert!(board.page("daily", 3, 10).is_empty());
+        assert_eq!(board.page("daily", 1, 10)[0].player, "player10");
+        assert_eq!(board.top("daily", 1)[0].player, "player0");
+        assert_eq!(board.rank("daily", "player24"), Some(25));
+        assert_eq!(board.rank("other", "player24"), None);
+    }
+
+    #[test]
+    fn restored_entries_keep_their_place() {
+        let mut board = Leaderboard::new();
+        let secs = Duration::from_secs;
+        board.restore(
+            "4x6",
+            Entry {
+                player:   "ann".to_owned(),
+                guesses:  4,
+                duration: secs(30),
+                sequence: 7,
+            },
+        );
+
+        // Ties go to ann, whose entry was submitted first.
+        assert_eq!(
+            board.submit("4x6", "bob", &solved_in(4), secs(30)),
+            Some(2)
+        );
+        assert_eq!(board.entries("4x6")[1].sequence, 8);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn leaderboards_round_trip() {
+        let mut board = Leaderboard::new();
+        board.submit("4x6", "ann", &solved_in(4), Duration::from_secs(30));
+
+        let json = serde_json::to_string(&board).unwrap();
+        assert_eq!(serde_json::from_str::<Leaderboard>(&json).unwrap(), board);
+    }
+
+    #[test]
+    fn boards_are_named_after_game_shape() {
+        let game = GameBuilder::new().peg_count(5).peg_range(8).build();
+        assert_eq!(Leaderboard::board_for(&game), "5x8");
+    }
+}
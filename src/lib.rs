@@ -1,7 +1,51 @@
-mod game;
-mod solver;
+pub mod achievements;
+pub mod adversary;
+pub mod analysis;
+pub mod campaign;
+pub mod challenge;
+pub mod chatbot;
+pub mod coop;
+pub mod crowd;
+pub mod daily;
+pub mod duel;
+mod error;
+#[cfg(feature = "serde")]
+pub mod exchange;
+#[cfg(feature = "arbitrary")]
+mod fuzz;
+pub mod game;
+#[cfg(feature = "serde")]
+pub mod gamelog;
+pub mod input;
+pub mod knowledge;
+pub mod leaderboard;
+pub mod messages;
+#[cfg(feature = "serde")]
+pub mod pipe;
+pub mod profile;
+pub mod properties;
+pub mod puzzle;
+pub mod quiz;
+pub mod relay;
+pub mod render;
+pub mod replay;
+#[cfg(feature = "serde")]
+pub mod rpc;
+#[cfg(feature = "serde")]
+pub mod schema;
+pub mod scoring;
+pub mod solver;
+pub mod speedrun;
+pub mod streams;
+pub mod table;
+pub mod teaching;
+#[cfg(feature = "proptest")]
+pub mod testing;
+pub mod tournament;
+pub mod transcript;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-}
+pub use error::Error;
+pub use game::{
+    BoardView, BuildError, Difficulty, FeedbackFn, FeedbackMode, Game,
+    GameBuilder, GamePlay, GuessError, Handicap, Joker, Rules, Variant, EMPTY,
+};
@@ -1,5 +1,12 @@
-use mastermind;
+mod cli;
+
+use std::{env, process};
 
 fn main() {
-    println!("Hello, world!");
+    let args = env::args().skip(1).collect::<Vec<_>>();
+
+    if let Err(e) = cli::run(&args) {
+        eprintln!("mastermind: {}", e);
+        process::exit(1);
+    }
 }
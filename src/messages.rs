@@ -0,0 +1,286 @@
+use std::{cmp::Ordering, fmt};
+
+use crate::{
+    coop::TurnError, game::ArithmeticHint, BuildError, GuessError, EMPTY,
+};
+
+// Every user-facing string the crate and its frontends produce. Frontends
+// render these through a `Catalog` instead of hard-coding English text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Message<'a> {
+    NoGuessesLeft,
+    WrongLength,
+    GameOver,
+    NotAPeg(&'a str),
+    NotInAlphabet(u8),
+    GuessFull,
+    UnknownVariant(&'a str),
+    // Players and teams are numbered from 0 here and shown from 1.
+    UnknownTeam(usize),
+    UnknownPlayer(usize),
+    NotYourTurn(usize),
+    Prompt {
+        guess:       usize,
+        max_guesses: Option<u8>,
+    },
+    Feedback {
+        hits:      u8,
+        near_hits: u8,
+    },
+    // Which positions of a guess are exactly right, for positional feedback.
+    Positions(&'a [bool]),
+    Arithmetic(ArithmeticHint),
+    // How the secret's length compares to the guess's.
+    Length(Ordering),
+    Solved {
+        guesses: usize,
+    },
+    OutOfGuesses {
+        code: &'a str,
+    },
+    Forfeited {
+        code: &'a str,
+    },
+    NoSavedGames,
+    InvalidGame(&'a BuildError),
+}
+
+impl<'a> From<&GuessError> for Message<'a> {
+    fn from(error: &GuessError) -> Self {
+        match error {
+            GuessError::NoGuessesLeft => Self::NoGuessesLeft,
+            GuessError::WrongLength => Self::WrongLength,
+            GuessError::GameOver => Self::GameOver,
+        }
+    }
+}
+
+impl<'a> From<&TurnError> for Message<'a> {
+    fn from(error: &TurnError) -> Self {
+        match error {
+            TurnError::UnknownTeam(team) => Self::UnknownTeam(*team),
+            TurnError::UnknownPlayer(player) => Self::UnknownPlayer(*player),
+            TurnError::NotYourTurn(player) => Self::NotYourTurn(*player),
+            TurnError::Guess(error) => error.into(),
+        }
+    }
+}
+
+// A translation of `Message`s. Catalogs may be partial: anything `translate`
+// returns `None` for falls back to English.
+pub trait Catalog {
+    fn translate(&self, message: &Message) -> Option<String>;
+
+    fn message(&self, message: &Message) -> String {
+        self.translate(message)
+            .or_else(|| English.translate(message))
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct English;
+
+impl Catalog for English {
+    fn translate(&self, message: &Message) -> Option<String> {
+        let text = match *message {
+            Message::NoGuessesLeft => "There are no guesses left.".to_owned(),
+            Message::WrongLength => {
+                "That guess is the wrong length.".to_owned()
+            }
+            Message::GameOver => "The game is already over.".to_owned(),
+            Message::NotAPeg(token) => format!("`{}` is not a peg", token),
+            Message::NotInAlphabet(EMPTY) => {
+                "This game has no empty holes.".to_owned()
+            }
+            Message::NotInAlphabet(peg) => {
+                format!("There is no peg {} in this game.", peg)
+            }
+            Message::GuessFull => "The guess is already full.".to_owned(),
+            Message::UnknownVariant(name) => {
+                format!("unknown variant `{}`", name)
+            }
+            Message::UnknownTeam(team) => {
+                format!("There is no team {}.", team + 1)
+            }
+            Message::UnknownPlayer(player) => {
+                format!("There is no player {}.", player + 1)
+            }
+            Message::NotYourTurn(player) => {
+                format!("It's player {}'s turn.", player + 1)
+            }
+            Message::Prompt { guess, max_guesses } => match max_guesses {
+                Some(max) => format!("Guess {}/{}: ", guess, max),
+                None => format!("Guess {}: ", guess),
+            },
+            Message::Feedback { hits, near_hits } => {
+                format!("{} exact, {} close", hits, near_hits)
+            }
+            Message::Positions(mask) => {
+                let exact = (1..=mask.len())
+                    .filter(|&position| mask[position - 1])
+                    .map(|position| position.to_string())
+                    .collect::<Vec<_>>();
+                match exact.is_empty() {
+                    true => "nothing in the right place".to_owned(),
+                    false => format!("right place: {}", exact.join(" ")),
+                }
+            }
+            Message::Arithmetic(ArithmeticHint::DigitSumEven(even)) => {
+                let parity = if even { "even" } else { "odd" };
+                format!("the digits add up to an {} number", parity)
+            }
+            Message::Arithmetic(ArithmeticHint::Magnitude(ordering)) => {
+                match ordering {
+                    Ordering::Less => "the code is lower",
+                    Ordering::Equal => "the code is the same number",
+                    Ordering::Greater => "the code is higher",
+                }
+                .to_owned()
+            }
+            Message::Length(ordering) => match ordering {
+                Ordering::Less => "the code is shorter",
+                Ordering::Equal => "the code is this long",
+                Ordering::Greater => "the code is longer",
+            }
+            .to_owned(),
+            Message::Solved { guesses } => {
+                format!("You cracked it in {} guesses!", guesses)
+            }
+            Message::OutOfGuesses { code } => {
+                format!("Out of guesses! The code was {}.", code)
+            }
+            Message::Forfeited { code } => {
+                format!("You gave up. The code was {}.", code)
+            }
+            Message::NoSavedGames => "No saved games.".to_owned(),
+            Message::InvalidGame(BuildError::UnknownVariant(name)) => {
+                return self.translate(&Message::UnknownVariant(name));
+            }
+            Message::InvalidGame(error) => match error {
+                BuildError::UnknownVariant(_) => unreachable!(),
+                BuildError::TooFewColors(range) => {
+                    format!("A game needs at least 2 colors, not {}.", range)
+                }
+                BuildError::NoPegs => "A game needs at least 1 peg.".to_owned(),
+                BuildError::NoGuesses => {
+                    "A game needs at least 1 guess.".to_owned()
+                }
+                BuildError::ConflictingPegCounts { count, range } => format!(
+                    "Cannot have both {} pegs and {} to {} pegs.",
+                    count, range.0, range.1
+                ),
+                BuildError::EmptyPegCountRange(min, max) => {
+                    format!("There are no peg counts from {} to {}.", min, max)
+                }
+                BuildError::SecretWrongLength(len) => {
+                    format!("A secret of {} pegs is the wrong length.", len)
+                }
+                BuildError::SecretNotInAlphabet(peg) => format!(
+                    "The secret contains peg {}, which isn't in this game.",
+                    peg
+                ),
+                BuildError::UnsatisfiableSecret => {
+                    "No secret fits the game's constraints.".to_owned()
+                }
+                BuildError::JokerInAlphabet(joker) => {
+                    format!("The joker {} is also a color in this game.", joker)
+                }
+                BuildError::InvalidColorWeights => {
+                    "Color weights must be one non-negative weight per color."
+                        .to_owned()
+                }
+            },
+        };
+        Some(text)
+    }
+}
+
+impl fmt::Display for GuessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&English.message(&self.into()))
+    }
+}
+
+impl std::error::Error for GuessError {}
+
+impl fmt::Display for TurnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&English.message(&self.into()))
+    }
+}
+
+impl std::error::Error for TurnError {}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&English.message(&Message::InvalidGame(self)))
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct French;
+
+    impl Catalog for French {
+        fn translate(&self, message: &Message) -> Option<String> {
+            match *message {
+                Message::Feedback { hits, near_hits } => Some(format!(
+                    "{} bien placés, {} mal placés",
+                    hits, near_hits
+                )),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn english_covers_every_message() {
+        let feedback = Message::Feedback {
+            hits:      2,
+            near_hits: 1,
+        };
+        assert_eq!(English.message(&feedback), "2 exact, 1 close");
+        assert_eq!(
+            English.message(&Message::Prompt {
+                guess:       3,
+                max_guesses: None,
+            }),
+            "Guess 3: "
+        );
+    }
+
+    #[test]
+    fn partial_catalogs_fall_back_to_english() {
+        let feedback = Message::Feedback {
+            hits:      2,
+            near_hits: 1,
+        };
+        assert_eq!(French.message(&feedback), "2 bien placés, 1 mal placés");
+        assert_eq!(French.message(&Message::NotAPeg("x")), "`x` is not a peg");
+    }
+
+    #[test]
+    fn guess_errors_display_in_english() {
+        assert_eq!(
+            GuessError::WrongLength.to_string(),
+            "That guess is the wrong length."
+        );
+        assert_eq!(
+            BuildError::TooFewColors(1).to_string(),
+            "A game needs at least 2 colors, not 1."
+        );
+        assert_eq!(
+            TurnError::NotYourTurn(1).to_string(),
+            "It's player 2's turn."
+        );
+        assert_eq!(
+            TurnError::Guess(GuessError::GameOver).to_string(),
+            "The game is already over."
+        );
+    }
+}
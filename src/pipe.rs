@@ -0,0 +1,140 @@
+// A JSON-lines interface to a single game, for shell scripts and other
+// programs driving the CLI through a pipe. Each line in is a command and each
+// line out is its response; unlike `rpc` there are no ids or envelopes, and
+// commands always apply to the game most recently started.
+//
+//     --> {"command": "new", "difficulty": "easy", "seed": 7}
+//     <-- {"pegs": 4, "colors": 6, "max_guesses": 12, "challenge": "E-7"}
+//     --> {"command": "guess", "guess": [0, 0, 1, 1]}
+//     <-- {"hits": 1, "near_hits": 0, "over": false, "solved": false}
+//     --> {"command": "state"}
+//     <-- {"guesses": [...], "over": false, "solved": false, "secret": null}
+//
+// `new` takes the same optional `difficulty`, `challenge` code or `seed` as
+// `rpc`'s `create`, and `forfeit` ends the game. A command that fails gets
+// `{"error": message}` instead, and leaves the game as it was.
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::{
+    rpc::{self, CreateParams},
+    Game,
+};
+
+#[derive(Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+enum Command {
+    New(CreateParams),
+    Guess { guess: Vec<u8> },
+    State,
+    Forfeit,
+}
+
+#[derive(Default)]
+pub struct Session {
+    game: Option<Game>,
+}
+
+impl Session {
+    pub fn new() -> Self { Self::default() }
+
+    pub fn game(&self) -> Option<&Game> { self.game.as_ref() }
+
+    // Answers one command line.
+    pub fn handle(&mut self, line: &str) -> String {
+        let response = serde_json::from_str(line)
+            .map_err(|e| e.to_string())
+            .and_then(|command| self.run(command))
+            .unwrap_or_else(|message| json!({ "error": message }));
+        response.to_string()
+    }
+
+    fn run(&mut self, command: Command) -> Result<Value, String> {
+        match command {
+            Command::New(params) => {
+                let (game, description) = rpc::create(params)?;
+                self.game = Some(game);
+                Ok(description)
+            }
+            Command::Guess { guess } => {
+                let game = self.current()?;
+                let score = game.guess(&guess).map_err(|e| e.to_string())?;
+                Ok(rpc::guessed(game, score))
+            }
+            Command::State => Ok(rpc::state(self.current()?)),
+            Command::Forfeit => {
+                let game = self.current()?;
+                game.forfeit();
+                Ok(rpc::state(game))
+            }
+        }
+    }
+
+    // The game the other commands apply to.
+    fn current(&mut self) -> Result<&mut Game, String> {
+        self.game
+            .as_mut()
+            .ok_or_else(|| "no game in progress; start one with `new`".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{challenge::Challenge, Difficulty};
+
+    fn send(session: &mut Session, command: Value) -> Value {
+        serde_json::from_str(&session.handle(&command.to_string())).unwrap()
+    }
+
+    #[test]
+    fn plays_a_game() {
+        let mut session = Session::new();
+        let created = send(
+            &mut session,
+            json!({"command": "new", "difficulty": "easy", "seed": 7}),
+        );
+        assert_eq!(created["pegs"], 4);
+        assert_eq!(created["challenge"], "E-7");
+
+        let secret = Challenge::new(Difficulty::Easy, 7).builder().build();
+        let secret = secret.pegs();
+        let guessed =
+            send(&mut session, json!({"command": "guess", "guess": secret}));
+        assert_eq!(guessed["hits"], 4);
+        assert_eq!(guessed["solved"], true);
+
+        let state = send(&mut session, json!({"command": "state"}));
+        assert_eq!(state["guesses"][0]["guess"], json!(secret));
+        assert_eq!(state["secret"], json!(secret));
+    }
+
+    #[test]
+    fn reports_errors() {
+        let mut session = Session::new();
+        let error = |session: &mut Session, line: &str| {
+            let response = session.handle(line);
+            serde_json::from_str::<Value>(&response).unwrap()["error"].clone()
+        };
+
+        assert!(error(&mut session, "{").is_string());
+        assert!(error(&mut session, r#"{"command": "state"}"#).is_string());
+        assert!(error(&mut session, r#"{"command": "jump"}"#).is_string());
+        assert!(error(
+            &mut session,
+            r#"{"command": "new", "difficulty": "impossible"}"#
+        )
+        .is_string());
+        assert!(session.game().is_none());
+
+        session.handle(r#"{"command": "new"}"#);
+        assert!(error(&mut session, r#"{"command": "guess", "guess": [1]}"#)
+            .is_string());
+        assert_eq!(session.game().unwrap().guesses().len(), 0);
+
+        let forfeited = send(&mut session, json!({"command": "forfeit"}));
+        assert_eq!(forfeited["over"], true);
+        assert!(forfeited["secret"].is_array());
+    }
+}
@@ -0,0 +1,150 @@
+use crate::{
+    achievements::{Achievements, Streak},
+    daily::DailyStreak,
+    Game, GameBuilder, Variant,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Settings {
+    // The name of the player's preferred variant; see `Variant::from_name`.
+    pub variant: String,
+}
+
+impl Settings {
+    pub fn builder(&self) -> GameBuilder {
+        Variant::from_name(&self.variant)
+            .and_then(|variant| GameBuilder::try_from(variant).ok())
+            .unwrap_or_default()
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            variant: Variant::Classic.name().to_owned(),
+        }
+    }
+}
+
+pub const DEFAULT_RATING: f64 = 1000.0;
+
+// Everything the crate knows about a player, in one place so that frontends
+// share a single notion of identity.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Profile {
+    pub name:         String,
+    pub settings:     Settings,
+    pub stats:        Streak,
+    pub achievements: Vec<String>,
+    pub rating:       f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub daily:        DailyStreak,
+}
+
+impl Profile {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name:         name.to_owned(),
+            settings:     Settings::default(),
+            stats:        Streak::default(),
+            achievements: Vec::new(),
+            rating:       DEFAULT_RATING,
+            daily:        DailyStreak::default(),
+        }
+    }
+
+    // An achievements tracker primed with this profile's progress.
+    pub fn tracker(&self) -> Achievements {
+        let mut tracker = Achievements::new();
+        tracker.restore(self.stats, &self.achievements);
+        tracker
+    }
+
+    // Records a finished game against the built-in achievements, returning
+    // any newly unlocked ids.
+    pub fn record(&mut self, game: &Game) -> Vec<String> {
+        self.record_with(game, &mut self.tracker())
+    }
+
+    // Like `record`, but with an application's own achievements tracker.
+    pub fn record_with(
+        &mut self,
+        game: &Game,
+        tracker: &mut Achievements,
+    ) -> Vec<String> {
+        tracker.restore(self.stats, &self.achievements);
+        let unlocked = tracker.record(game);
+
+        self.stats = tracker.streak();
+        self.achievements = tracker.unlocked_ids().to_vec();
+        unlocked
+    }
+
+    // Updates the rating after a game against an opponent, using the Elo
+    // formula. `score` is 1.0 for a win, 0.5 for a draw and 0.0 for a loss.
+    pub fn update_rating(&mut self, opponent: f64, score: f64) {
+        const K: f64 = 32.0;
+
+        let expected =
+            1.0 / (1.0 + 10f64.powf((opponent - self.rating) / 400.0));
+        self.rating += K * (score - expected);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn won_game() -> Game {
+        let mut game = GameBuilder::new().pegs(&[1, 2, 3, 4]).build();
+        game.guess(&[1, 2, 3, 4]).unwrap();
+        game
+    }
+
+    #[test]
+    fn records_stats_and_achievements() {
+        let mut profile = Profile::new("ann");
+        assert_eq!(
+            profile.record(&won_game()),
+            vec!["win-in-3", "no-duplicates"]
+        );
+        assert!(profile.record(&won_game()).is_empty());
+
+        assert_eq!(profile.stats.played, 2);
+        assert_eq!(profile.stats.current, 2);
+        assert_eq!(profile.achievements, ["win-in-3", "no-duplicates"]);
+        assert!(profile.tracker().is_unlocked("win-in-3"));
+    }
+
+    #[test]
+    fn ratings_follow_elo() {
+        let mut ann = Profile::new("ann");
+        ann.update_rating(DEFAULT_RATING, 1.0);
+        assert_eq!(ann.rating, DEFAULT_RATING + 16.0);
+
+        let mut bob = Profile::new("bob");
+        bob.update_rating(DEFAULT_RATING, 0.5);
+        assert_eq!(bob.rating, DEFAULT_RATING);
+    }
+
+    #[test]
+    fn settings_pick_the_variant() {
+        let mut settings = Settings::default();
+        assert_eq!(settings.builder().build().alphabet().len(), 6);
+
+        settings.variant = "number".to_owned();
+        assert_eq!(settings.builder().build().alphabet().len(), 10);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn profiles_round_trip() {
+        let mut profile = Profile::new("ann");
+        profile.record(&won_game());
+
+        let json = serde_json::to_string(&profile).unwrap();
+        assert_eq!(serde_json::from_str::<Profile>(&json).unwrap(), profile);
+    }
+}
@@ -0,0 +1,135 @@
+// The mathematical properties standard Mastermind feedback has, for checking
+// custom feedback functions and rule combinations in tests. Not every valid
+// set of rules has all of them: guess-side jokers, for one, aren't symmetric.
+
+use crate::Rules;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Property {
+    // Scoring `a` against `b` gives the same as `b` against `a`.
+    Symmetry,
+    // A guess gets every peg as an exact hit exactly when it's the secret.
+    FullMatch,
+    // Hits and near hits together never outnumber the pegs of either code.
+    Bounded,
+}
+
+impl Property {
+    pub const ALL: [Property; 3] =
+        [Property::Symmetry, Property::FullMatch, Property::Bounded];
+
+    // Whether the property holds for `secret` and `guess`.
+    pub fn holds(&self, rules: &Rules, secret: &[u8], guess: &[u8]) -> bool {
+        let (hits, near_hits) = rules.score(secret, guess);
+        match self {
+            Self::Symmetry => rules.score(guess, secret) == (hits, near_hits),
+            Self::FullMatch => {
+                let full = hits as usize == secret.len()
+                    && hits as usize == guess.len();
+                full == (secret == guess)
+            }
+            Self::Bounded => {
+                let total = hits as usize + near_hits as usize;
+                total <= secret.len().min(guess.len())
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub property: Property,
+    pub secret:   Vec<u8>,
+    pub guess:    Vec<u8>,
+}
+
+pub fn is_symmetric(rules: &Rules, a: &[u8], b: &[u8]) -> bool {
+    Property::Symmetry.holds(rules, a, b)
+}
+
+pub fn is_full_match_iff_equal(rules: &Rules, a: &[u8], b: &[u8]) -> bool {
+    Property::FullMatch.holds(rules, a, b)
+}
+
+pub fn is_bounded(rules: &Rules, a: &[u8], b: &[u8]) -> bool {
+    Property::Bounded.holds(rules, a, b)
+}
+
+// Checks `properties` for every pair of `codes`, returning the first pair
+// that breaks one.
+pub fn verify(
+    rules: &Rules,
+    properties: &[Property],
+    codes: &[Vec<u8>],
+) -> Result<(), Violation> {
+    for secret in codes {
+        for guess in codes {
+            if let Some(&property) = properties
+                .iter()
+                .find(|property| !property.holds(rules, secret, guess))
+            {
+                return Err(Violation {
+                    property,
+                    secret: secret.clone(),
+                    guess: guess.clone(),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        game::{FeedbackFn, FeedbackMode, Joker},
+        knowledge::codes,
+    };
+
+    #[test]
+    fn built_in_feedback_has_every_property() {
+        let codes = codes(&[0, 1, 2], 1..=3);
+        for feedback_mode in [
+            FeedbackMode::Standard,
+            FeedbackMode::CountOnly,
+            FeedbackMode::Positional,
+        ] {
+            let rules = Rules {
+                feedback_mode,
+                ..Rules::default()
+            };
+            assert_eq!(verify(&rules, &Property::ALL, &codes), Ok(()));
+        }
+    }
+
+    #[test]
+    fn violations_are_reported() {
+        let rules = Rules {
+            joker: Some(Joker::InGuess(9)),
+            ..Rules::default()
+        };
+        assert!(!is_symmetric(&rules, &[1, 2], &[9, 9]));
+        assert!(!is_full_match_iff_equal(&rules, &[1, 2], &[9, 9]));
+        assert!(is_bounded(&rules, &[1, 2], &[9, 9]));
+
+        let generous = FeedbackFn::new(|secret, _| (0, secret.len() as u8 + 1));
+        let rules = Rules {
+            feedback_mode: FeedbackMode::Custom(generous),
+            ..Rules::default()
+        };
+        let codes = codes(&[0, 1], 2..=2);
+        assert_eq!(
+            verify(&rules, &[Property::Bounded], &codes),
+            Err(Violation {
+                property: Property::Bounded,
+                secret:   vec![0, 0],
+                guess:    vec![0, 0],
+            })
+        );
+        assert_eq!(
+            verify(&rules, &Property::ALL, &codes).unwrap_err().property,
+            Property::FullMatch
+        );
+    }
+}
@@ -0,0 +1,209 @@
+use std::cmp::Ordering;
+
+#[cfg(feature = "serde")]
+use crate::{
+    exchange::{self, GameRecord, PuzzleRecord},
+    schema::{self, SchemaError},
+};
+use crate::{
+    game::{ArithmeticHint, FeedbackMode, Rules},
+    knowledge::{all_codes, Knowledge},
+    solver::Solver,
+    BoardView, Game, GameBuilder,
+};
+
+// Working out how many guesses a position needs is an exhaustive search, so
+// only positions with at most this many candidates become puzzles.
+const MAX_CANDIDATES: usize = 16;
+
+// A position from a real game, and the fewest guesses that are guaranteed to
+// solve it from there.
+pub struct Puzzle {
+    game:           Game,
+    guesses_needed: u8,
+}
+
+impl Puzzle {
+    // Tags the current position of `game`, or gives `None` if it's over or
+    // still too open to analyse.
+    pub fn from_position(game: &Game) -> Option<Self> {
+        let knowledge = Knowledge::new(game);
+        let candidates = knowledge.candidates();
+        if game.is_over() || candidates.len() > MAX_CANDIDATES {
+            return None;
+        }
+
+        let codes = all_codes(game);
+        let candidates = candidates.iter().collect::<Vec<_>>();
+        let guesses_needed = (1..)
+            .find(|&depth| solvable(game.rules(), &codes, &candidates, depth))
+            .unwrap();
+
+        Some(Self {
+            game: game.truncated(game.guesses().len()),
+            guesses_needed,
+        })
+    }
+
+    // Plays a game from `builder` with the solver, and turns every position
+    // along the way that is narrow enough into a puzzle. The last is the
+    // position right before the decisive guess.
+    pub fn from_solver_trace(builder: GameBuilder) -> Vec<Self> {
+        let mut game = builder.unlimited_guesses(true).build();
+        let _ = Solver::new(&game).solve(&mut game);
+
+        (1..game.guesses().len())
+            .filter_map(|index| Self::from_position(&game.truncated(index)))
+            .collect()
+    }
+
+    pub fn board(&self) -> BoardView<'_> { self.game.view() }
+    pub fn guesses_needed(&self) -> u8 { self.guesses_needed }
+}
+
+// See `exchange` for the format.
+#[cfg(feature = "serde")]
+impl Puzzle {
+    pub fn to_json(&self) -> Result<String, SchemaError> {
+        schema::to_string(&PuzzleRecord::from(self))
+    }
+
+    pub fn from_json(text: &str) -> Result<Puzzle, SchemaError> {
+        schema::from_str::<PuzzleRecord>(text)?.try_into()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<&Puzzle> for PuzzleRecord {
+    fn from(puzzle: &Puzzle) -> Self {
+        let game = GameRecord::from(&puzzle.game);
+        Self {
+            colors:         game.colors,
+            allow_empty:    game.allow_empty,
+            secret:         game.secret,
+            guesses:        game.guesses,
+            guesses_needed: puzzle.guesses_needed,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<PuzzleRecord> for Puzzle {
+    type Error = SchemaError;
+
+    fn try_from(record: PuzzleRecord) -> Result<Self, Self::Error> {
+        let game = exchange::replay(
+            exchange::builder(
+                record.colors,
+                record.allow_empty,
+                &record.secret,
+            )
+            .unlimited_guesses(true),
+            &record.guesses,
+        )?;
+        Ok(Self {
+            game,
+            guesses_needed: record.guesses_needed,
+        })
+    }
+}
+
+// Everything a guess is told about one possible secret.
+type Response = ((u8, u8), Option<Vec<bool>>, Vec<ArithmeticHint>, Ordering);
+
+fn response(rules: &Rules, secret: &[u8], guess: &[u8]) -> Response {
+    let mask = (rules.feedback_mode == FeedbackMode::Positional)
+        .then(|| rules.mask(secret, guess));
+    (
+        rules.score(secret, guess),
+        mask,
+        rules.arithmetic_hints(secret, guess),
+        secret.len().cmp(&guess.len()),
+    )
+}
+
+// Whether some strategy is guaranteed to find the secret among `candidates`
+// within `depth` guesses.
+fn solvable(
+    rules: &Rules,
+    codes: &[Vec<u8>],
+    candidates: &[&Vec<u8>],
+    depth: u8,
+) -> bool {
+    if candidates.len() <= 1 {
+        return depth >= 1;
+    }
+    if depth <= 1 {
+        return false;
+    }
+
+    // Candidates are tried first, since guessing one might win outright.
+    let guesses = candidates
+        .iter()
+        .copied()
+        .chain(codes.iter().filter(|code| !candidates.contains(code)));
+
+    guesses.into_iter().any(|guess| {
+        let mut partitions: Vec<(Response, Vec<&Vec<u8>>)> = Vec::new();
+        for &candidate in candidates {
+            if candidate == guess {
+                continue;
+            }
+            let response = response(rules, candidate, guess);
+            match partitions.iter_mut().find(|(r, _)| *r == response) {
+                Some((_, partition)) => partition.push(candidate),
+                None => partitions.push((response, vec![candidate])),
+            }
+        }
+
+        partitions
+            .iter()
+            .all(|(_, partition)| partition.len() < candidates.len())
+            && partitions.iter().all(|(_, partition)| {
+                solvable(rules, codes, partition, depth - 1)
+            })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn positions_are_tagged_with_guesses_needed() {
+        let mut game = GameBuilder::new()
+            .pegs(&[1, 1, 2, 2])
+            .unlimited_guesses(true)
+            .build();
+
+        // Still far too open.
+        assert!(Puzzle::from_position(&game).is_none());
+
+        game.guess(&[1, 1, 1, 1]).unwrap();
+        game.guess(&[2, 2, 2, 2]).unwrap();
+        // Six arrangements of two 1s and two 2s are left. No single guess
+        // can tell them all apart, but two always can.
+        let puzzle = Puzzle::from_position(&game).unwrap();
+        assert_eq!(puzzle.board().guesses().len(), 2);
+        assert_eq!(puzzle.guesses_needed(), 3);
+
+        game.guess(&[1, 2, 1, 2]).unwrap();
+        game.guess(&[1, 1, 2, 2]).unwrap();
+        assert!(Puzzle::from_position(&game).is_none());
+    }
+
+    #[test]
+    fn solver_traces_end_before_the_decisive_guess() {
+        let mut game = GameBuilder::new()
+            .pegs(&[3, 1, 4, 1])
+            .unlimited_guesses(true)
+            .build();
+        let _ = Solver::new(&game).solve(&mut game);
+
+        let puzzles =
+            Puzzle::from_solver_trace(GameBuilder::new().pegs(&[3, 1, 4, 1]));
+        let last = puzzles.last().unwrap();
+        assert_eq!(last.board().guesses().len(), game.guesses().len() - 1);
+        assert!(puzzles.iter().all(|puzzle| puzzle.guesses_needed() >= 1));
+    }
+}
@@ -0,0 +1,105 @@
+use std::time::{Duration, Instant};
+
+use rand::{prelude::thread_rng, seq::SliceRandom};
+
+use crate::{solver::Solver, BoardView, Game, GameBuilder};
+
+// A deduction exercise: a finished history of guesses that leaves exactly one
+// possible secret, which the player has to name.
+pub struct Quiz {
+    game:    Game,
+    started: Instant,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Answer {
+    pub correct: bool,
+    pub elapsed: Duration,
+}
+
+impl Answer {
+    // Up to 100 points for a correct answer, losing one for every 3 seconds
+    // taken, down to a minimum of 10.
+    pub fn score(&self) -> u32 {
+        match self.correct {
+            true => {
+                100u64.saturating_sub(self.elapsed.as_secs() / 3).max(10) as u32
+            }
+            false => 0,
+        }
+    }
+}
+
+impl Quiz {
+    // Makes random guesses at a game from `builder` until only the secret is
+    // left. The secret itself is never guessed.
+    pub fn new(builder: GameBuilder) -> Self {
+        let mut game = builder.unlimited_guesses(true).build();
+        let mut solver = Solver::new(&game);
+
+        while solver.candidates().len() > 1 {
+            let guess = solver
+                .candidates()
+                .iter()
+                .filter(|code| code.as_slice() != game.pegs())
+                .collect::<Vec<_>>()
+                .choose(&mut thread_rng())
+                .map(|code| code.to_vec())
+                .unwrap();
+            game.guess(&guess).unwrap();
+            solver.update(&game);
+        }
+
+        Self {
+            game,
+            started: Instant::now(),
+        }
+    }
+
+    pub fn board(&self) -> BoardView<'_> { self.game.view() }
+
+    // Restarts the clock, for frontends that show the quiz some time after
+    // generating it.
+    pub fn start(&mut self) { self.started = Instant::now() }
+
+    pub fn answer(&self, code: &[u8]) -> Answer {
+        self.answer_at(code, Instant::now())
+    }
+
+    pub fn answer_at(&self, code: &[u8], at: Instant) -> Answer {
+        Answer {
+            correct: code == self.game.pegs(),
+            elapsed: at.saturating_duration_since(self.started),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::knowledge::Knowledge;
+
+    #[test]
+    fn quizzes_have_one_answer() {
+        for _ in 0..5 {
+            let quiz = Quiz::new(GameBuilder::new());
+            let knowledge = Knowledge::new(quiz.board());
+            assert_eq!(knowledge.candidates().len(), 1);
+            assert!(!quiz.board().is_solved());
+
+            let answer = &knowledge.candidates()[0];
+            assert!(quiz.answer(answer).correct);
+        }
+    }
+
+    #[test]
+    fn answers_are_scored_on_time() {
+        let quiz = Quiz::new(GameBuilder::new().pegs(&[1, 2, 3, 4]));
+        let after = |secs| quiz.started + Duration::from_secs(secs);
+
+        assert_eq!(quiz.answer_at(&[1, 2, 3, 4], after(0)).score(), 100);
+        assert_eq!(quiz.answer_at(&[1, 2, 3, 4], after(30)).score(), 90);
+        assert_eq!(quiz.answer_at(&[1, 2, 3, 4], after(900)).score(), 10);
+        assert_eq!(quiz.answer_at(&[4, 3, 2, 1], after(0)).score(), 0);
+    }
+}
@@ -0,0 +1,90 @@
+use crate::{
+    coop::{Coop, TurnError},
+    GameBuilder,
+};
+
+// Teams racing to crack the same secret, each on their own board. Within a
+// team, players must guess in turn, so every guess is made by one person
+// working only from what the board shows.
+pub struct Relay {
+    teams: Vec<Coop>,
+}
+
+impl Relay {
+    // A relay between teams of the given sizes.
+    pub fn new(builder: GameBuilder, team_sizes: &[usize]) -> Self {
+        let game = builder.build();
+        Self {
+            teams: team_sizes
+                .iter()
+                .map(|&players| Coop::from_game(game.truncated(0), players))
+                .collect(),
+        }
+    }
+
+    pub fn teams(&self) -> &[Coop] { &self.teams }
+
+    pub fn guess(
+        &mut self,
+        team: usize,
+        player: usize,
+        guess: &[u8],
+    ) -> Result<(u8, u8), TurnError> {
+        self.teams
+            .get_mut(team)
+            .ok_or(TurnError::UnknownTeam(team))?
+            .guess(player, guess)
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.teams.iter().all(|team| team.game().is_over())
+    }
+
+    // Teams from first to last: solving beats not solving, then fewer guesses
+    // win. Teams that tie keep their original order.
+    pub fn standings(&self) -> Vec<usize> {
+        let mut standings = (0..self.teams.len()).collect::<Vec<_>>();
+        standings.sort_by_key(|&team| {
+            let game = self.teams[team].game();
+            (!game.is_solved(), game.guesses().len())
+        });
+        standings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn teams_race_in_rotation() {
+        let builder = GameBuilder::new().pegs(&[1, 2, 3, 4]).max_guesses(3);
+        let mut relay = Relay::new(builder, &[2, 3]);
+        assert_eq!(
+            relay.teams()[0].game().pegs(),
+            relay.teams()[1].game().pegs()
+        );
+
+        relay.guess(0, 0, &[0, 0, 1, 1]).unwrap();
+        assert_eq!(
+            relay.guess(0, 0, &[1, 2, 3, 4]),
+            Err(TurnError::NotYourTurn(1))
+        );
+        relay.guess(0, 1, &[1, 2, 3, 4]).unwrap();
+        assert!(!relay.is_over());
+
+        for (player, guess) in [[0, 0, 0, 0], [5, 5, 5, 5], [4, 4, 4, 4]]
+            .iter()
+            .enumerate()
+        {
+            relay.guess(1, player, guess).unwrap();
+        }
+        assert!(relay.is_over());
+        assert_eq!(relay.standings(), [0, 1]);
+        assert_eq!(relay.teams()[1].guessed_by(), [0, 1, 2]);
+        assert_eq!(
+            relay.guess(2, 0, &[1, 2, 3, 4]),
+            Err(TurnError::UnknownTeam(2))
+        );
+    }
+}
@@ -0,0 +1,177 @@
+use crate::{BoardView, Game, EMPTY};
+
+// Narrates the game as plain sentences, one per line, for screen readers and
+// voice interfaces:
+//
+//     4 pegs, 6 colors. 2 of 12 guesses used.
+//     Guess 1: red, red, blue, green — 2 exact, 1 misplaced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Description {
+    // Spoken names for each peg value, indexed by peg.
+    pub names: Vec<String>,
+}
+
+impl Default for Description {
+    fn default() -> Self {
+        let names = [
+            "red", "green", "blue", "yellow", "orange", "purple", "cyan",
+            "magenta", "brown", "pink",
+        ];
+        Self {
+            names: names.iter().map(|name| name.to_string()).collect(),
+        }
+    }
+}
+
+impl Description {
+    pub fn new(names: Vec<String>) -> Self { Self { names } }
+
+    pub fn name(&self, peg: u8) -> String {
+        match peg {
+            EMPTY => "empty".to_owned(),
+            _ => self
+                .names
+                .get(peg as usize)
+                .cloned()
+                .unwrap_or_else(|| format!("color {}", peg + 1)),
+        }
+    }
+
+    pub fn describe<'a>(&self, board: impl Into<BoardView<'a>>) -> String {
+        let board = board.into();
+        let mut lines = Vec::new();
+
+        let used = board.guesses().len();
+        let limit = match board.max_guesses() {
+            Some(max) => format!("{} of {} guesses used", used, max),
+            None => {
+                format!("{} {} used", used, plural(used, "guess", "guesses"))
+            }
+        };
+        let pegs = board.peg_counts();
+        let pegs = match pegs.start() == pegs.end() {
+            true => plural(
+                *pegs.start() as usize,
+                "1 peg",
+                &format!("{} pegs", pegs.start()),
+            ),
+            false => format!("{} to {} pegs", pegs.start(), pegs.end()),
+        };
+        let colors = board.alphabet().len();
+        lines.push(format!("{}, {} colors. {}.", pegs, colors, limit));
+
+        for (position, peg) in board.revealed() {
+            lines.push(format!(
+                "Position {} is {}.",
+                position + 1,
+                self.name(peg)
+            ));
+        }
+
+        for (i, guess) in board.guesses().iter().enumerate() {
+            let pegs =
+                guess.iter().map(|&peg| self.name(peg)).collect::<Vec<_>>();
+            let (hits, near_hits) = board.hits(i).unwrap();
+            lines.push(format!(
+                "Guess {}: {} — {} exact, {} misplaced.",
+                i + 1,
+                pegs.join(", "),
+                hits,
+                near_hits
+            ));
+        }
+
+        if board.is_solved() {
+            lines.push(format!(
+                "Solved in {} {}.",
+                used,
+                plural(used, "guess", "guesses")
+            ));
+        }
+        else if board.is_forfeited() {
+            lines.push("Given up.".to_owned());
+        }
+        else if board.is_over() {
+            lines.push("Out of guesses.".to_owned());
+        }
+
+        lines.join("\n")
+    }
+}
+
+fn plural(count: usize, one: &str, many: &str) -> String {
+    match count {
+        1 => one.to_owned(),
+        _ => many.to_owned(),
+    }
+}
+
+impl Game {
+    pub fn describe(&self) -> String { Description::default().describe(self) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GameBuilder;
+
+    #[test]
+    fn describes_the_board() {
+        let mut game = GameBuilder::new().pegs(&[0, 0, 2, 1]).build();
+        game.guess(&[0, 0, 1, 3]).unwrap();
+        game.guess(&[0, 0, 2, 1]).unwrap();
+
+        assert_eq!(
+            game.describe(),
+            "4 pegs, 6 colors. 2 of 12 guesses used.\n\
+             Guess 1: red, red, green, yellow — 2 exact, 1 misplaced.\n\
+             Guess 2: red, red, blue, green — 4 exact, 0 misplaced.\n\
+             Solved in 2 guesses."
+        );
+    }
+
+    #[test]
+    fn describes_unusual_games() {
+        let mut game = GameBuilder::new()
+            .pegs(&[EMPTY, 12, 1])
+            .peg_count_range(1, 3)
+            .peg_range(13)
+            .allow_empty(true)
+            .unlimited_guesses(true)
+            .build();
+        game.guess(&[12]).unwrap();
+
+        let names = vec!["ruby".to_owned(), "jade".to_owned()];
+        assert_eq!(
+            Description::new(names).describe(&game),
+            "1 to 3 pegs, 14 colors. 1 guess used.\n\
+             Guess 1: color 13 — 0 exact, 1 misplaced."
+        );
+    }
+
+    #[test]
+    fn describes_lost_games_and_reveals() {
+        let mut game = GameBuilder::new()
+            .pegs(&[3, 3, 3, 3])
+            .max_guesses(1)
+            .handicap(crate::Handicap {
+                bonus_guesses: 0,
+                revealed_pegs: 4,
+            })
+            .build();
+        game.guess(&[1, 1, 1, 1]).unwrap();
+
+        let description = game.describe();
+        assert!(description
+            .contains("Position 1 is yellow.\nPosition 2 is yellow."));
+        assert!(description.ends_with("Out of guesses."));
+    }
+
+    #[test]
+    fn describes_forfeited_games() {
+        let mut game = GameBuilder::new().pegs(&[1, 2, 3, 4]).build();
+        game.guess(&[0, 0, 0, 0]).unwrap();
+        game.forfeit();
+        assert!(game.describe().ends_with("0 misplaced.\nGiven up."));
+    }
+}
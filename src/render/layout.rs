@@ -0,0 +1,133 @@
+use crate::BoardView;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Fill {
+    Peg(u8),
+    Hit,
+    NearHit,
+    // An unfilled feedback hole.
+    Hole,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Circle {
+    pub row:    usize,
+    pub x:      f64,
+    pub y:      f64,
+    pub radius: f64,
+    pub fill:   Fill,
+}
+
+// The geometry of a rendered board, shared by the image renderers: one row
+// per guess, with a square of feedback pins the size of one cell on the right.
+pub struct Layout {
+    pub width:   f64,
+    pub height:  f64,
+    pub rows:    usize,
+    pub circles: Vec<Circle>,
+}
+
+impl Layout {
+    pub fn new(board: BoardView, cell: f64) -> Self {
+        let columns = *board.peg_counts().end() as f64;
+        let rows = board.guesses().len();
+        let mut circles = Vec::new();
+
+        for (row, guess) in board.guesses().iter().enumerate() {
+            let top = row as f64 * cell;
+
+            for (column, &peg) in guess.iter().enumerate() {
+                circles.push(Circle {
+                    row,
+                    x: column as f64 * cell + cell / 2.0,
+                    y: top + cell / 2.0,
+                    radius: cell * 0.35,
+                    fill: Fill::Peg(peg),
+                });
+            }
+
+            // Hits first, then near hits, then empty holes.
+            let (hits, near_hits) = board.hits(row).unwrap();
+            let slots = guess.len();
+            let per_row = (slots as f64).sqrt().ceil().max(1.0) as usize;
+            let spacing = cell / per_row as f64;
+
+            for i in 0..slots {
+                let fill = match i {
+                    i if i < hits as usize => Fill::Hit,
+                    i if i < (hits + near_hits) as usize => Fill::NearHit,
+                    _ => Fill::Hole,
+                };
+                circles.push(Circle {
+                    row,
+                    x: columns * cell
+                        + (i % per_row) as f64 * spacing
+                        + spacing / 2.0,
+                    y: top + (i / per_row) as f64 * spacing + spacing / 2.0,
+                    radius: spacing * 0.3,
+                    fill,
+                });
+            }
+        }
+
+        Self {
+            width: (columns + 1.0) * cell,
+            height: rows.max(1) as f64 * cell,
+            rows,
+            circles,
+        }
+    }
+}
+
+// A shape drawn on top of a peg so colors can be told apart without relying on
+// hue alone. Coordinates are relative to the peg's center, in units of its
+// radius.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mark {
+    Dot,
+    Ring,
+    Square,
+    Diamond,
+    HorizontalBar,
+    VerticalBar,
+    Plus,
+    Cross,
+}
+
+impl Mark {
+    const ALL: [Mark; 8] = [
+        Mark::Dot,
+        Mark::Ring,
+        Mark::Square,
+        Mark::Diamond,
+        Mark::HorizontalBar,
+        Mark::VerticalBar,
+        Mark::Plus,
+        Mark::Cross,
+    ];
+
+    pub fn for_peg(peg: u8) -> Self {
+        Self::ALL[peg as usize % Self::ALL.len()]
+    }
+
+    #[cfg(feature = "png")]
+    pub fn covers(&self, x: f64, y: f64) -> bool {
+        let (ax, ay) = (x.abs(), y.abs());
+        let bar = |along: f64, across: f64| along < 0.55 && across < 0.12;
+
+        match self {
+            Self::Dot => x * x + y * y < 0.3 * 0.3,
+            Self::Ring => (0.3..0.5).contains(&(x * x + y * y).sqrt()),
+            Self::Square => ax < 0.35 && ay < 0.35,
+            Self::Diamond => ax + ay < 0.45,
+            Self::HorizontalBar => bar(ax, ay),
+            Self::VerticalBar => bar(ay, ax),
+            Self::Plus => bar(ax, ay) || bar(ay, ax),
+            Self::Cross => {
+                ax < 0.4
+                    && ay < 0.4
+                    && ((x - y).abs() < 0.17 || (x + y).abs() < 0.17)
+            }
+        }
+    }
+}
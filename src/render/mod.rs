@@ -0,0 +1,146 @@
+mod describe;
+mod layout;
+#[cfg(feature = "png")]
+mod png;
+mod svg;
+mod text;
+#[cfg(feature = "ratatui")]
+mod widget;
+
+pub use describe::Description;
+pub use svg::Svg;
+pub use text::{Charset, Text};
+#[cfg(feature = "ratatui")]
+pub use widget::Board;
+
+#[cfg(feature = "png")]
+pub use self::png::Png;
+use crate::EMPTY;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Palette {
+    // CSS colors for each peg value, indexed by peg.
+    pub pegs:     Vec<String>,
+    // Used for peg values the palette has no color for, like jokers.
+    pub unknown:  String,
+    pub hit:      String,
+    pub near_hit: String,
+    pub board:    String,
+    pub outline:  String,
+    // Draws a distinct mark on each peg color, in the outline color, so the
+    // board can be read without telling colors apart.
+    pub symbols:  bool,
+}
+
+impl Palette {
+    pub fn color(&self, peg: u8) -> Option<&str> {
+        match peg {
+            EMPTY => None,
+            _ => Some(self.pegs.get(peg as usize).unwrap_or(&self.unknown)),
+        }
+    }
+
+    // The Okabe-Ito palette, distinguishable under the common forms of color
+    // blindness.
+    pub fn okabe_ito() -> Self {
+        let pegs = [
+            "#e69f00", "#56b4e9", "#009e73", "#f0e442", "#0072b2", "#d55e00",
+            "#cc79a7", "#000000",
+        ];
+        Self {
+            pegs: pegs.iter().map(|c| c.to_string()).collect(),
+            ..Self::default()
+        }
+    }
+
+    // Paul Tol's "bright" scheme, another colorblind-safe qualitative set.
+    pub fn tol_bright() -> Self {
+        let pegs = [
+            "#4477aa", "#ee6677", "#228833", "#ccbb44", "#66ccee", "#aa3377",
+            "#bbbbbb",
+        ];
+        Self {
+            pegs: pegs.iter().map(|c| c.to_string()).collect(),
+            ..Self::default()
+        }
+    }
+
+    pub fn symbols(mut self, symbols: bool) -> Self {
+        self.symbols = symbols;
+        self
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        let pegs = [
+            "#e6194b", "#3cb44b", "#4363d8", "#ffe119", "#f58231", "#911eb4",
+            "#42d4f4", "#f032e6", "#9a6324", "#fabed4",
+        ];
+
+        Self {
+            pegs:     pegs.iter().map(|c| c.to_string()).collect(),
+            unknown:  "#a9a9a9".to_owned(),
+            hit:      "#000000".to_owned(),
+            near_hit: "#ffffff".to_owned(),
+            board:    "#8b5a2b".to_owned(),
+            outline:  "#333333".to_owned(),
+            symbols:  false,
+        }
+    }
+}
+
+// Parses `#rgb` or `#rrggbb` colors, for renderers that can't use CSS colors
+// directly.
+#[cfg(any(feature = "png", feature = "ratatui"))]
+fn parse_hex(color: &str) -> Option<[u8; 3]> {
+    let hex = color.strip_prefix('#')?;
+    let channel = |s: &str| u8::from_str_radix(s, 16).ok();
+
+    match hex.len() {
+        3 => {
+            let mut rgb = [0; 3];
+            for (i, c) in hex.char_indices() {
+                rgb[i] = channel(&c.to_string())? * 17;
+            }
+            Some(rgb)
+        }
+        6 => Some([
+            channel(hex.get(0..2)?)?,
+            channel(hex.get(2..4)?)?,
+            channel(hex.get(4..6)?)?,
+        ]),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn palette_colors() {
+        let palette = Palette::default();
+        assert_eq!(palette.color(0), Some("#e6194b"));
+        assert_eq!(palette.color(200), Some("#a9a9a9"));
+        assert_eq!(palette.color(EMPTY), None);
+    }
+
+    #[test]
+    fn colorblind_palettes() {
+        let palette = Palette::okabe_ito().symbols(true);
+        assert_eq!(palette.color(1), Some("#56b4e9"));
+        assert_eq!(palette.color(8), Some("#a9a9a9"));
+        assert!(palette.symbols);
+        assert!(!Palette::tol_bright().symbols);
+    }
+
+    #[test]
+    #[cfg(any(feature = "png", feature = "ratatui"))]
+    fn parses_hex_colors() {
+        assert_eq!(parse_hex("#ff8000"), Some([255, 128, 0]));
+        assert_eq!(parse_hex("#f80"), Some([255, 136, 0]));
+        assert_eq!(parse_hex("tomato"), None);
+        assert_eq!(parse_hex("#12345"), None);
+    }
+}
@@ -0,0 +1,170 @@
+use png::{BitDepth, ColorType, Encoder, EncodingError};
+
+use super::{
+    layout::{Fill, Layout, Mark},
+    parse_hex, Palette,
+};
+use crate::{BoardView, EMPTY};
+
+// Rasterizes the same board as `Svg` into a PNG image. Only hex palette colors
+// (`#rgb` or `#rrggbb`) can be rasterized; anything else is drawn in the
+// palette's `unknown` color.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Png {
+    pub palette: Palette,
+    pub cell:    u32,
+}
+
+impl Default for Png {
+    fn default() -> Self {
+        Self {
+            palette: Palette::default(),
+            cell:    40,
+        }
+    }
+}
+
+impl Png {
+    pub fn new() -> Self { Self::default() }
+
+    pub fn palette(mut self, palette: Palette) -> Self {
+        self.palette = palette;
+        self
+    }
+
+    pub fn cell(mut self, cell: u32) -> Self {
+        self.cell = cell;
+        self
+    }
+
+    pub fn render<'a>(
+        &self,
+        board: impl Into<BoardView<'a>>,
+    ) -> Result<Vec<u8>, EncodingError> {
+        let layout = Layout::new(board.into(), self.cell as f64);
+        let (width, height) = (layout.width as u32, layout.height as u32);
+
+        let board = self.rgb(&self.palette.board);
+        let outline = self.rgb(&self.palette.outline);
+        let mut pixels = vec![board; (width * height) as usize];
+
+        for circle in &layout.circles {
+            let fill = match circle.fill {
+                Fill::Peg(peg) => self.palette.color(peg).map(|c| self.rgb(c)),
+                Fill::Hit => Some(self.rgb(&self.palette.hit)),
+                Fill::NearHit => Some(self.rgb(&self.palette.near_hit)),
+                Fill::Hole => None,
+            };
+
+            let mark = match circle.fill {
+                Fill::Peg(peg) if self.palette.symbols && peg != EMPTY => {
+                    Some(Mark::for_peg(peg))
+                }
+                _ => None,
+            };
+
+            let left = (circle.x - circle.radius).floor().max(0.0) as u32;
+            let top = (circle.y - circle.radius).floor().max(0.0) as u32;
+            let right = ((circle.x + circle.radius).ceil() as u32).min(width);
+            let bottom = ((circle.y + circle.radius).ceil() as u32).min(height);
+
+            for y in top..bottom {
+                for x in left..right {
+                    let dx = x as f64 + 0.5 - circle.x;
+                    let dy = y as f64 + 0.5 - circle.y;
+                    let distance = (dx * dx + dy * dy).sqrt();
+                    let marked = mark.is_some_and(|mark| {
+                        mark.covers(dx / circle.radius, dy / circle.radius)
+                    });
+
+                    let color = if distance > circle.radius {
+                        continue;
+                    }
+                    else if distance > circle.radius - 1.5 || marked {
+                        outline
+                    }
+                    else {
+                        fill.unwrap_or(board)
+                    };
+                    pixels[(y * width + x) as usize] = color;
+                }
+            }
+        }
+
+        let mut bytes = Vec::new();
+        let mut encoder = Encoder::new(&mut bytes, width, height);
+        encoder.set_color(ColorType::Rgb);
+        encoder.set_depth(BitDepth::Eight);
+
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&pixels.concat())?;
+        writer.finish()?;
+        Ok(bytes)
+    }
+
+    fn rgb(&self, color: &str) -> [u8; 3] {
+        parse_hex(color)
+            .or_else(|| parse_hex(&self.palette.unknown))
+            .unwrap_or([128, 128, 128])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use png::Decoder;
+
+    use super::*;
+    use crate::GameBuilder;
+
+    fn decode(bytes: &[u8]) -> (u32, u32, Vec<u8>) {
+        let mut reader = Decoder::new(std::io::Cursor::new(bytes))
+            .read_info()
+            .unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        (info.width, info.height, pixels)
+    }
+
+    #[test]
+    fn renders_finished_games() {
+        let mut game = GameBuilder::new().pegs(&[0, 1, 2, 3]).build();
+        game.guess(&[3, 1, 2, 0]).unwrap();
+        game.guess(&[0, 1, 2, 3]).unwrap();
+
+        let bytes = Png::new().cell(20).render(&game).unwrap();
+        let (width, height, pixels) = decode(&bytes);
+        assert_eq!((width, height), (100, 40));
+
+        let pixel = |x: u32, y: u32| {
+            let i = ((y * width + x) * 3) as usize;
+            [pixels[i], pixels[i + 1], pixels[i + 2]]
+        };
+        // The center of the first peg of the first guess, and a corner of the
+        // board.
+        assert_eq!(pixel(10, 10), [0xff, 0xe1, 0x19]);
+        assert_eq!(pixel(0, 0), [0x8b, 0x5a, 0x2b]);
+        // The last guess was all hits.
+        assert_eq!(pixel(85, 25), [0, 0, 0]);
+    }
+
+    #[test]
+    fn symbols_are_drawn_over_pegs() {
+        let mut game = GameBuilder::new().pegs(&[0, 1, 2, 3]).build();
+        game.guess(&[0, 1, 2, 3]).unwrap();
+
+        let palette = Palette::okabe_ito().symbols(true);
+        let bytes = Png::new().palette(palette).cell(40).render(&game).unwrap();
+        let (width, _, pixels) = decode(&bytes);
+        let pixel = |x: u32, y: u32| {
+            let i = ((y * width + x) * 3) as usize;
+            [pixels[i], pixels[i + 1], pixels[i + 2]]
+        };
+
+        // A dot in the middle of the first peg, a ring around the middle of
+        // the second.
+        assert_eq!(pixel(20, 20), [0x33, 0x33, 0x33]);
+        assert_eq!(pixel(22, 20), [0x33, 0x33, 0x33]);
+        assert_eq!(pixel(60, 20), [0x56, 0xb4, 0xe9]);
+        assert_eq!(pixel(65, 20), [0x33, 0x33, 0x33]);
+    }
+}
@@ -0,0 +1,221 @@
+use std::fmt::Write;
+
+use super::{
+    layout::{Circle, Fill, Layout, Mark},
+    Palette,
+};
+use crate::{BoardView, EMPTY};
+
+// Renders the board as a standalone SVG document, one row per guess with
+// feedback pins on the right.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Svg {
+    pub palette: Palette,
+    // The width and height of each peg's cell, in pixels.
+    pub cell:    u32,
+}
+
+impl Default for Svg {
+    fn default() -> Self {
+        Self {
+            palette: Palette::default(),
+            cell:    40,
+        }
+    }
+}
+
+impl Svg {
+    pub fn new() -> Self { Self::default() }
+
+    pub fn palette(mut self, palette: Palette) -> Self {
+        self.palette = palette;
+        self
+    }
+
+    pub fn cell(mut self, cell: u32) -> Self {
+        self.cell = cell;
+        self
+    }
+
+    pub fn render<'a>(&self, board: impl Into<BoardView<'a>>) -> String {
+        let layout = Layout::new(board.into(), self.cell as f64);
+
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+            w = layout.width,
+            h = layout.height,
+        );
+        let _ = writeln!(
+            svg,
+            r#"  <rect width="{}" height="{}" fill="{}"/>"#,
+            layout.width, layout.height, self.palette.board
+        );
+
+        for row in 0..layout.rows {
+            let _ = writeln!(
+                svg,
+                r#"  <g class="guess" data-guess="{}">"#,
+                row + 1
+            );
+            for circle in layout.circles.iter().filter(|c| c.row == row) {
+                svg += &self.circle(circle);
+
+                match circle.fill {
+                    Fill::Peg(peg) if self.palette.symbols && peg != EMPTY => {
+                        svg += &self.mark(Mark::for_peg(peg), circle);
+                    }
+                    _ => {}
+                }
+            }
+            svg += "  </g>\n";
+        }
+
+        svg += "</svg>\n";
+        svg
+    }
+
+    fn circle(&self, circle: &Circle) -> String {
+        let (class, fill) = match circle.fill {
+            Fill::Peg(peg) => ("", self.palette.color(peg).unwrap_or("none")),
+            Fill::Hit => (r#"class="pin" "#, self.palette.hit.as_str()),
+            Fill::NearHit => {
+                (r#"class="pin" "#, self.palette.near_hit.as_str())
+            }
+            Fill::Hole => (r#"class="pin" "#, "none"),
+        };
+
+        format!(
+            r#"    <circle {}cx="{}" cy="{}" r="{}" fill="{}" stroke="{}"/>
+"#,
+            class,
+            circle.x,
+            circle.y,
+            circle.radius,
+            fill,
+            self.palette.outline
+        )
+    }
+
+    fn mark(&self, mark: Mark, circle: &Circle) -> String {
+        let (x, y, r) = (circle.x, circle.y, circle.radius);
+        let color = &self.palette.outline;
+        let rect = |width: f64, height: f64| {
+            format!(
+                r#"    <rect class="mark" x="{}" y="{}" width="{}" height="{}" fill="{}"/>
+"#,
+                x - width * r / 2.0,
+                y - height * r / 2.0,
+                width * r,
+                height * r,
+                color
+            )
+        };
+
+        match mark {
+            Mark::Dot => format!(
+                r#"    <circle class="mark" cx="{}" cy="{}" r="{}" fill="{}"/>
+"#,
+                x,
+                y,
+                0.3 * r,
+                color
+            ),
+            Mark::Ring => format!(
+                r#"    <circle class="mark" cx="{}" cy="{}" r="{}" fill="none" stroke="{}" stroke-width="{}"/>
+"#,
+                x,
+                y,
+                0.4 * r,
+                color,
+                0.2 * r
+            ),
+            Mark::Square => rect(0.7, 0.7),
+            Mark::Diamond => format!(
+                r#"    <polygon class="mark" points="{x},{top} {right},{y} {x},{bottom} {left},{y}" fill="{color}"/>
+"#,
+                top = y - 0.45 * r,
+                right = x + 0.45 * r,
+                bottom = y + 0.45 * r,
+                left = x - 0.45 * r,
+            ),
+            Mark::HorizontalBar => rect(1.1, 0.24),
+            Mark::VerticalBar => rect(0.24, 1.1),
+            Mark::Plus => rect(1.1, 0.24) + rect(0.24, 1.1).as_str(),
+            Mark::Cross => format!(
+                r#"    <path class="mark" d="M{a},{a2} L{b},{b2} M{a},{b2} L{b},{a2}" stroke="{}" stroke-width="{}"/>
+"#,
+                color,
+                0.24 * r,
+                a = x - 0.4 * r,
+                b = x + 0.4 * r,
+                a2 = y - 0.4 * r,
+                b2 = y + 0.4 * r,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GameBuilder;
+
+    #[test]
+    fn renders_guesses_and_pins() {
+        let mut game = GameBuilder::new().pegs(&[0, 1, 2, 3]).build();
+        game.guess(&[0, 2, 1, 5]).unwrap();
+        game.guess(&[0, 1, 2, 3]).unwrap();
+
+        let svg = Svg::new().cell(20).render(&game);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains(r#"width="100" height="40""#));
+        assert_eq!(svg.matches(r#"class="guess""#).count(), 2);
+        assert_eq!(svg.matches(r#"class="pin""#).count(), 8);
+
+        // Peg colors come from the palette.
+        assert_eq!(svg.matches(r##"fill="#e6194b""##).count(), 2);
+        assert_eq!(svg.matches(r##"fill="#911eb4""##).count(), 1);
+        // One row has 1 hit and 2 near hits, the other 4 hits.
+        assert_eq!(svg.matches(r##"class="pin" "##).count(), 8);
+        assert_eq!(svg.matches(r##"fill="#000000""##).count(), 5);
+        assert_eq!(svg.matches(r##"fill="#ffffff""##).count(), 2);
+    }
+
+    #[test]
+    fn custom_palettes_are_used() {
+        let mut game = GameBuilder::new()
+            .pegs(&[0, 0, 0, 0])
+            .allow_empty(true)
+            .build();
+        game.guess(&[0, EMPTY, 0, 0]).unwrap();
+
+        let palette = Palette {
+            pegs: vec!["tomato".to_owned()],
+            ..Palette::default()
+        };
+        let svg = Svg::new().palette(palette).render(&game);
+        assert_eq!(svg.matches(r#"fill="tomato""#).count(), 3);
+        // The empty hole is drawn as an outline only.
+        assert!(svg.contains(r#"r="14" fill="none""#));
+    }
+
+    #[test]
+    fn symbols_mark_each_color() {
+        let mut game = GameBuilder::new()
+            .pegs(&[0, 1, 2, 3])
+            .allow_empty(true)
+            .build();
+        game.guess(&[0, 1, 2, EMPTY]).unwrap();
+
+        let svg = Svg::new().render(&game);
+        assert!(!svg.contains(r#"class="mark""#));
+
+        let palette = Palette::okabe_ito().symbols(true);
+        let svg = Svg::new().palette(palette).render(&game);
+        assert_eq!(svg.matches(r#"<circle class="mark""#).count(), 2);
+        assert_eq!(svg.matches(r#"<rect class="mark""#).count(), 1);
+        assert!(svg.contains(r##"fill="none" stroke="#333333""##));
+    }
+}
@@ -0,0 +1,184 @@
+use std::fmt;
+
+use crate::{BoardView, Game, EMPTY};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Charset {
+    #[default]
+    Ascii,
+    Unicode,
+}
+
+struct Symbols {
+    pegs:     &'static [char],
+    empty:    char,
+    unknown:  char,
+    hit:      char,
+    near_hit: char,
+    hole:     char,
+    divider:  char,
+}
+
+const ASCII: Symbols = Symbols {
+    pegs:     &[
+        '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 'A', 'B', 'C', 'D',
+        'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R',
+        'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z',
+    ],
+    empty:    '_',
+    unknown:  '?',
+    hit:      'X',
+    near_hit: 'o',
+    hole:     '.',
+    divider:  '|',
+};
+
+// Pegs avoid circles, which are left for the feedback pins.
+const UNICODE: Symbols = Symbols {
+    pegs:     &['◆', '◇', '■', '□', '▲', '△', '▼', '▽', '★', '☆', '♥', '♡'],
+    empty:    '◌',
+    unknown:  '?',
+    hit:      '●',
+    near_hit: '○',
+    hole:     '·',
+    divider:  '│',
+};
+
+impl Charset {
+    fn symbols(&self) -> &'static Symbols {
+        match self {
+            Charset::Ascii => &ASCII,
+            Charset::Unicode => &UNICODE,
+        }
+    }
+}
+
+// The glyph `charset` uses for `peg`.
+pub(super) fn glyph(charset: Charset, peg: u8) -> char {
+    let symbols = charset.symbols();
+    match peg {
+        EMPTY => symbols.empty,
+        _ => *symbols.pegs.get(peg as usize).unwrap_or(&symbols.unknown),
+    }
+}
+
+// Renders the board as plain monochrome text, for logs, email and terminals
+// without color:
+//
+//      1 | 0 2 1 5 | X o o .
+//      2 | 0 1 2 3 | X X X X
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Text {
+    pub charset: Charset,
+}
+
+impl Text {
+    pub fn new() -> Self { Self::default() }
+
+    pub fn charset(mut self, charset: Charset) -> Self {
+        self.charset = charset;
+        self
+    }
+
+    pub fn render<'a>(&self, board: impl Into<BoardView<'a>>) -> String {
+        let board = board.into();
+        let symbols = self.charset.symbols();
+        let number_width = board.guesses().len().to_string().len();
+
+        board
+            .guesses()
+            .iter()
+            .enumerate()
+            .map(|(i, guess)| {
+                let pegs = guess
+                    .iter()
+                    .map(|&peg| glyph(self.charset, peg))
+                    .map(String::from)
+                    .collect::<Vec<_>>()
+                    .join(" ");
+
+                let (hits, near_hits) = board.hits(i).unwrap();
+                let pins = (0..guess.len())
+                    .map(|pin| match pin {
+                        pin if pin < hits as usize => symbols.hit,
+                        pin if pin < (hits + near_hits) as usize => {
+                            symbols.near_hit
+                        }
+                        _ => symbols.hole,
+                    })
+                    .map(String::from)
+                    .collect::<Vec<_>>()
+                    .join(" ");
+
+                format!(
+                    "{:>width$} {d} {} {d} {}\n",
+                    i + 1,
+                    pegs,
+                    pins,
+                    width = number_width,
+                    d = symbols.divider
+                )
+            })
+            .collect()
+    }
+}
+
+// Prints the board with the ASCII text renderer, so `println!("{game}")`
+// shows every guess and its feedback.
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&Text::new().render(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GameBuilder;
+
+    fn game() -> Game {
+        let mut game = GameBuilder::new()
+            .pegs(&[0, 1, 2, 3])
+            .allow_empty(true)
+            .build();
+        game.guess(&[0, 2, 1, EMPTY]).unwrap();
+        game.guess(&[0, 1, 2, 3]).unwrap();
+        game
+    }
+
+    #[test]
+    fn renders_ascii() {
+        assert_eq!(
+            Text::new().render(&game()),
+            "1 | 0 2 1 _ | X o o .\n2 | 0 1 2 3 | X X X X\n"
+        );
+    }
+
+    #[test]
+    fn renders_unicode() {
+        assert_eq!(
+            Text::new().charset(Charset::Unicode).render(&game()),
+            "1 │ ◆ ■ ◇ ◌ │ ● ○ ○ ·\n2 │ ◆ ◇ ■ □ │ ● ● ● ●\n"
+        );
+    }
+
+    #[test]
+    fn games_display_as_text() {
+        assert_eq!(game().to_string(), Text::new().render(&game()));
+        assert_eq!(Game::default().to_string(), "");
+    }
+
+    #[test]
+    fn pads_guess_numbers() {
+        let mut game = GameBuilder::new()
+            .pegs(&[0, 1, 2, 3])
+            .max_guesses(20)
+            .build();
+        for _ in 0..10 {
+            game.guess(&[9, 9, 9, 9]).unwrap();
+        }
+        let text = Text::new().render(&game);
+        assert!(text.starts_with(" 1 | 9 9 9 9 | . . . .\n"));
+        assert!(text.ends_with("10 | 9 9 9 9 | . . . .\n"));
+    }
+}
@@ -0,0 +1,136 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::Widget,
+};
+
+use super::{parse_hex, text::glyph, Charset, Palette};
+use crate::{BoardView, EMPTY};
+
+// A ratatui widget drawing the board, one row per guess. Pegs are colored from
+// the palette; with `Palette::symbols` each color also gets its own glyph.
+// When there are more guesses than rows, the latest ones are shown.
+//
+//      1 │ ● ● ● ● │ ● ○ ○ ·
+#[derive(Clone)]
+pub struct Board<'a> {
+    pub board:   BoardView<'a>,
+    pub palette: Palette,
+}
+
+impl<'a> Board<'a> {
+    pub fn new(board: impl Into<BoardView<'a>>) -> Self {
+        Self {
+            board:   board.into(),
+            palette: Palette::default(),
+        }
+    }
+
+    pub fn palette(mut self, palette: Palette) -> Self {
+        self.palette = palette;
+        self
+    }
+
+    fn peg(&self, peg: u8) -> Span<'static> {
+        let symbol = match (peg, self.palette.symbols) {
+            (EMPTY, _) | (_, true) => glyph(Charset::Unicode, peg),
+            (_, false) => '●',
+        };
+        let style = match self.palette.color(peg).and_then(parse_hex) {
+            Some([r, g, b]) => Style::new().fg(Color::Rgb(r, g, b)),
+            None => Style::new(),
+        };
+        Span::styled(symbol.to_string(), style)
+    }
+
+    fn line(&self, index: usize, number_width: usize) -> Line<'static> {
+        let guess = &self.board.guesses()[index];
+        let mut spans = vec![Span::raw(format!(
+            "{:>width$} │",
+            index + 1,
+            width = number_width
+        ))];
+
+        for &peg in guess {
+            spans.push(Span::raw(" "));
+            spans.push(self.peg(peg));
+        }
+
+        let (hits, near_hits) = self.board.hits(index).unwrap();
+        let pins = (0..guess.len())
+            .map(|pin| match pin {
+                pin if pin < hits as usize => '●',
+                pin if pin < (hits + near_hits) as usize => '○',
+                _ => '·',
+            })
+            .collect::<String>();
+        spans.push(Span::raw(format!(" │ {}", pins)));
+
+        Line::from(spans)
+    }
+}
+
+impl Widget for Board<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let rows = self.board.guesses().len();
+        let number_width = rows.to_string().len();
+        let first = rows.saturating_sub(area.height as usize);
+
+        for (y, index) in (first..rows).enumerate() {
+            let row = Rect {
+                y: area.y + y as u16,
+                height: 1,
+                ..area
+            };
+            self.line(index, number_width).render(row, buf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Game, GameBuilder};
+
+    fn game() -> Game {
+        let mut game = GameBuilder::new()
+            .pegs(&[0, 1, 2, 3])
+            .allow_empty(true)
+            .build();
+        game.guess(&[0, 2, 1, EMPTY]).unwrap();
+        game.guess(&[0, 1, 2, 3]).unwrap();
+        game
+    }
+
+    fn row(buf: &Buffer, y: u16) -> String {
+        (0..buf.area.width).map(|x| buf[(x, y)].symbol()).collect()
+    }
+
+    #[test]
+    fn renders_rows_with_colors() {
+        let game = game();
+        let mut buf = Buffer::empty(Rect::new(0, 0, 20, 3));
+        Board::new(&game).render(buf.area, &mut buf);
+
+        assert_eq!(row(&buf, 0), "1 │ ● ● ● ◌ │ ●○○·  ");
+        assert_eq!(row(&buf, 1), "2 │ ● ● ● ● │ ●●●●  ");
+        assert_eq!(row(&buf, 2), " ".repeat(20));
+        assert_eq!(buf[(4, 0)].fg, Color::Rgb(0xe6, 0x19, 0x4b));
+        assert_eq!(buf[(6, 0)].fg, Color::Rgb(0x43, 0x63, 0xd8));
+    }
+
+    #[test]
+    fn shows_the_latest_guesses_and_symbols() {
+        let game = game();
+        let mut buf = Buffer::empty(Rect::new(0, 0, 20, 1));
+        let palette = Palette::okabe_ito().symbols(true);
+        Board::new(&game)
+            .palette(palette)
+            .render(buf.area, &mut buf);
+
+        assert_eq!(row(&buf, 0), "2 │ ◆ ◇ ■ □ │ ●●●●  ");
+        assert_eq!(buf[(4, 0)].fg, Color::Rgb(0xe6, 0x9f, 0x00));
+    }
+}
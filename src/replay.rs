@@ -0,0 +1,100 @@
+use crate::Game;
+
+// Steps through a game one guess at a time, for replay viewers and scrubbers.
+// The position is the number of guesses shown, from 0 (an empty board) to
+// `len()` (the game as it ended).
+pub struct Replay {
+    game:     Game,
+    position: usize,
+}
+
+impl Replay {
+    // Starts at the beginning of `game`.
+    pub fn new(game: &Game) -> Self {
+        Self {
+            game:     game.truncated(game.guesses().len()),
+            position: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize { self.game.guesses().len() }
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+    pub fn position(&self) -> usize { self.position }
+    pub fn is_at_start(&self) -> bool { self.position == 0 }
+    pub fn is_at_end(&self) -> bool { self.position == self.len() }
+
+    // The board as it stood at the current position.
+    pub fn state(&self) -> Game { self.game.truncated(self.position) }
+
+    // The most recently shown guess and its feedback.
+    pub fn current(&self) -> Option<(&[u8], (u8, u8))> {
+        let index = self.position.checked_sub(1)?;
+        Some((&self.game.guesses()[index], self.game.hits(index)?))
+    }
+
+    // These return whether the position changed.
+    pub fn step_forward(&mut self) -> bool { self.seek(self.position + 1) }
+    pub fn step_back(&mut self) -> bool {
+        self.position > 0 && self.seek(self.position - 1)
+    }
+
+    pub fn seek(&mut self, position: usize) -> bool {
+        let position = position.min(self.len());
+        let moved = position != self.position;
+        self.position = position;
+        moved
+    }
+
+    pub fn rewind(&mut self) { self.position = 0; }
+    pub fn skip_to_end(&mut self) { self.position = self.len(); }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GameBuilder;
+
+    fn game() -> Game {
+        let mut game = GameBuilder::new().pegs(&[1, 2, 3, 4]).build();
+        game.guess(&[0, 0, 1, 1]).unwrap();
+        game.guess(&[1, 2, 0, 0]).unwrap();
+        game.guess(&[1, 2, 3, 4]).unwrap();
+        game
+    }
+
+    #[test]
+    fn steps_forward_and_back() {
+        let mut replay = Replay::new(&game());
+        assert_eq!(replay.len(), 3);
+        assert!(replay.is_at_start());
+        assert_eq!(replay.current(), None);
+        assert!(!replay.step_back());
+
+        assert!(replay.step_forward());
+        assert_eq!(replay.current(), Some((&[0, 0, 1, 1][..], (0, 1))));
+        assert!(replay.step_forward());
+        assert!(replay.step_forward());
+        assert!(!replay.step_forward());
+        assert!(replay.is_at_end());
+        assert!(replay.state().is_solved());
+
+        assert!(replay.step_back());
+        let state = replay.state();
+        assert_eq!(state.guesses().len(), 2);
+        assert!(!state.is_solved());
+        assert_eq!(state.hits(1), Some((2, 0)));
+    }
+
+    #[test]
+    fn seeks_within_bounds() {
+        let mut replay = Replay::new(&game());
+        assert!(replay.seek(10));
+        assert_eq!(replay.position(), 3);
+        assert!(!replay.seek(3));
+
+        replay.rewind();
+        assert_eq!(replay.state().guesses().len(), 0);
+        replay.skip_to_end();
+        assert!(replay.is_at_end());
+    }
+}
@@ -0,0 +1,368 @@
+// A JSON-RPC 2.0 interface to games, for embedders that would rather talk
+// to the crate over stdio or a socket than link it. Each request is one JSON
+// value; see `Handler::handle`.
+//
+//     --> {"jsonrpc": "2.0", "id": 1, "method": "create",
+//          "params": {"difficulty": "hard"}}
+//     <-- {"jsonrpc": "2.0", "id": 1, "result": {"game": 0, "pegs": 5,
+//          "colors": 8, "max_guesses": 10, "challenge": "H-..."}}
+//     --> {"jsonrpc": "2.0", "id": 2, "method": "guess",
+//          "params": {"game": 0, "guess": [0, 0, 1, 1, 2]}}
+//     <-- {"jsonrpc": "2.0", "id": 2, "result": {"hits": 1, "near_hits": 0,
+//          "over": false, "solved": false}}
+//
+// Methods:
+//
+// - `create`, with an optional `difficulty`, `challenge` code or `seed`.
+// - `guess`, with a `game` and a `guess`.
+// - `state`, with a `game`: its `GuessRecord`s so far, whether it's over, and
+//   the secret once it is.
+// - `forfeit`, with a `game`.
+//
+// Game errors have code -32000.
+
+use std::collections::HashMap;
+
+use rand::random;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::{
+    challenge::Challenge, exchange::GuessRecord, Difficulty, Game, GuessError,
+};
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const GAME_ERROR: i64 = -32000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct RpcError {
+    code:    i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    fn invalid_params(error: serde_json::Error) -> Self {
+        Self::new(INVALID_PARAMS, error.to_string())
+    }
+}
+
+impl From<GuessError> for RpcError {
+    fn from(error: GuessError) -> Self {
+        Self::new(GAME_ERROR, error.to_string())
+    }
+}
+
+#[derive(Deserialize)]
+pub(crate) struct CreateParams {
+    difficulty: Option<String>,
+    challenge:  Option<String>,
+    seed:       Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct GameParams {
+    game: u64,
+}
+
+#[derive(Deserialize)]
+struct GuessParams {
+    game:  u64,
+    guess: Vec<u8>,
+}
+
+// The games of one connection, by id.
+#[derive(Default)]
+pub struct Handler {
+    games:   HashMap<u64, Game>,
+    next_id: u64,
+}
+
+impl Handler {
+    pub fn new() -> Self { Self::default() }
+
+    // Answers one request or batch. Notifications, which have no id, get no
+    // response.
+    pub fn handle(&mut self, request: &str) -> Option<String> {
+        let response = match serde_json::from_str::<Value>(request) {
+            Ok(Value::Array(batch)) if !batch.is_empty() => {
+                let responses = batch
+                    .into_iter()
+                    .filter_map(|request| self.respond(request))
+                    .collect::<Vec<_>>();
+                if responses.is_empty() {
+                    return None;
+                }
+                Value::Array(responses)
+            }
+            Ok(Value::Array(_)) => error(
+                Value::Null,
+                RpcError::new(INVALID_REQUEST, "empty batch"),
+            ),
+            Ok(request) => self.respond(request)?,
+            Err(e) => {
+                error(Value::Null, RpcError::new(PARSE_ERROR, e.to_string()))
+            }
+        };
+        Some(response.to_string())
+    }
+
+    fn respond(&mut self, request: Value) -> Option<Value> {
+        let id = request.get("id").cloned();
+        let method = request.get("method").and_then(Value::as_str);
+        let (Some("2.0"), Some(method)) =
+            (request.get("jsonrpc").and_then(Value::as_str), method)
+        else {
+            let message = "not a JSON-RPC 2.0 request";
+            return Some(error(
+                id.unwrap_or(Value::Null),
+                RpcError::new(INVALID_REQUEST, message),
+            ));
+        };
+
+        let params = request.get("params").cloned().unwrap_or(json!({}));
+        let result = self.call(method, params);
+        let id = id?;
+        Some(match result {
+            Ok(result) => json!({"jsonrpc": "2.0", "id": id, "result": result}),
+            Err(e) => error(id, e),
+        })
+    }
+
+    fn call(&mut self, method: &str, params: Value) -> Result<Value, RpcError> {
+        match method {
+            "create" => {
+                let params = serde_json::from_value::<CreateParams>(params)
+                    .map_err(RpcError::invalid_params)?;
+                self.create(params)
+            }
+            "guess" => {
+                let params = serde_json::from_value::<GuessParams>(params)
+                    .map_err(RpcError::invalid_params)?;
+                let game = self.game(params.game)?;
+                let score = game.guess(&params.guess)?;
+                Ok(guessed(game, score))
+            }
+            "state" => {
+                let params = serde_json::from_value::<GameParams>(params)
+                    .map_err(RpcError::invalid_params)?;
+                Ok(state(self.game(params.game)?))
+            }
+            "forfeit" => {
+                let params = serde_json::from_value::<GameParams>(params)
+                    .map_err(RpcError::invalid_params)?;
+                let game = self.game(params.game)?;
+                game.forfeit();
+                Ok(state(game))
+            }
+            _ => Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                format!("no method `{}`", method),
+            )),
+        }
+    }
+
+    fn create(&mut self, params: CreateParams) -> Result<Value, RpcError> {
+        let (game, mut result) = create(params)
+            .map_err(|message| RpcError::new(INVALID_PARAMS, message))?;
+        let id = self.next_id;
+        self.next_id += 1;
+        result["game"] = id.into();
+        self.games.insert(id, game);
+        Ok(result)
+    }
+
+    fn game(&mut self, id: u64) -> Result<&mut Game, RpcError> {
+        self.games
+            .get_mut(&id)
+            .ok_or_else(|| RpcError::new(GAME_ERROR, format!("no game {}", id)))
+    }
+}
+
+// A new game for `params`, and a description of it.
+pub(crate) fn create(params: CreateParams) -> Result<(Game, Value), String> {
+    let challenge = match (params.challenge, params.difficulty) {
+        (Some(code), _) => {
+            code.parse::<Challenge>().map_err(|e| e.to_string())?
+        }
+        (None, name) => {
+            let name = name.as_deref().unwrap_or("medium");
+            let difficulty = Difficulty::from_name(name)
+                .ok_or_else(|| format!("unknown difficulty `{}`", name))?;
+            let seed = params.seed.unwrap_or_else(|| random::<u64>() >> 24);
+            Challenge::new(difficulty, seed)
+        }
+    };
+
+    let game = challenge.builder().build();
+    let description = json!({
+        "pegs": game.peg_counts().start(),
+        "colors": game.alphabet().len(),
+        "max_guesses": game.max_guesses(),
+        "challenge": challenge.code(),
+    });
+    Ok((game, description))
+}
+
+pub(crate) fn guessed(game: &Game, (hits, near_hits): (u8, u8)) -> Value {
+    json!({
+        "hits": hits,
+        "near_hits": near_hits,
+        "over": game.is_over(),
+        "solved": game.is_solved(),
+    })
+}
+
+pub(crate) fn state(game: &Game) -> Value {
+    let guesses = (0..game.guesses().len())
+        .map(|i| {
+            let (hits, near_hits) = game.hits(i).unwrap_or_default();
+            GuessRecord {
+                guess: game.guesses()[i].clone(),
+                hits,
+                near_hits,
+            }
+        })
+        .collect::<Vec<_>>();
+    json!({
+        "guesses": guesses,
+        "over": game.is_over(),
+        "solved": game.is_solved(),
+        "secret": game.is_over().then(|| game.pegs()),
+    })
+}
+
+fn error(id: Value, error: RpcError) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": {"code": error.code, "message": error.message},
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(handler: &mut Handler, request: Value) -> Value {
+        let response = handler.handle(&request.to_string()).unwrap();
+        serde_json::from_str(&response).unwrap()
+    }
+
+    #[test]
+    fn plays_a_game() {
+        let mut handler = Handler::new();
+        let created = call(
+            &mut handler,
+            json!({"jsonrpc": "2.0", "id": 1, "method": "create",
+                   "params": {"difficulty": "easy", "seed": 7}}),
+        );
+        assert_eq!(created["id"], 1);
+        assert_eq!(created["result"]["game"], 0);
+        assert_eq!(created["result"]["pegs"], 4);
+        assert_eq!(created["result"]["challenge"], "E-7");
+
+        let secret = Challenge::new(Difficulty::Easy, 7).builder().build();
+        let secret = secret.pegs();
+        let guessed = call(
+            &mut handler,
+            json!({"jsonrpc": "2.0", "id": 2, "method": "guess",
+                   "params": {"game": 0, "guess": secret}}),
+        );
+        assert_eq!(guessed["result"]["hits"], 4);
+        assert_eq!(guessed["result"]["solved"], true);
+
+        let state = call(
+            &mut handler,
+            json!({"jsonrpc": "2.0", "id": 3, "method": "state",
+                   "params": {"game": 0}}),
+        );
+        assert_eq!(state["result"]["guesses"][0]["guess"], json!(secret));
+        assert_eq!(state["result"]["secret"], json!(secret));
+    }
+
+    #[test]
+    fn hides_the_secret_until_the_end() {
+        let mut handler = Handler::new();
+        handler.handle(r#"{"jsonrpc": "2.0", "id": 1, "method": "create"}"#);
+        let state = call(
+            &mut handler,
+            json!({"jsonrpc": "2.0", "id": 2, "method": "state",
+                   "params": {"game": 0}}),
+        );
+        assert_eq!(state["result"]["secret"], Value::Null);
+
+        let forfeited = call(
+            &mut handler,
+            json!({"jsonrpc": "2.0", "id": 3, "method": "forfeit",
+                   "params": {"game": 0}}),
+        );
+        assert_eq!(forfeited["result"]["over"], true);
+        assert!(forfeited["result"]["secret"].is_array());
+    }
+
+    #[test]
+    fn reports_errors() {
+        let mut handler = Handler::new();
+        let code = |handler: &mut Handler, request: &str| {
+            let response = handler.handle(request).unwrap();
+            serde_json::from_str::<Value>(&response).unwrap()["error"]["code"]
+                .clone()
+        };
+
+        assert_eq!(code(&mut handler, "{"), PARSE_ERROR);
+        assert_eq!(code(&mut handler, r#"{"id": 1}"#), INVALID_REQUEST);
+        assert_eq!(
+            code(
+                &mut handler,
+                r#"{"jsonrpc": "2.0", "id": 1, "method": "x"}"#
+            ),
+            METHOD_NOT_FOUND
+        );
+        assert_eq!(
+            code(
+                &mut handler,
+                r#"{"jsonrpc": "2.0", "id": 1, "method": "guess",
+                    "params": {"game": 3, "guess": [1]}}"#
+            ),
+            GAME_ERROR
+        );
+        assert_eq!(
+            code(
+                &mut handler,
+                r#"{"jsonrpc": "2.0", "id": 1, "method": "state",
+                    "params": {}}"#
+            ),
+            INVALID_PARAMS
+        );
+    }
+
+    #[test]
+    fn handles_batches_and_notifications() {
+        let mut handler = Handler::new();
+        assert_eq!(
+            handler.handle(r#"{"jsonrpc": "2.0", "method": "create"}"#),
+            None
+        );
+
+        let response = handler
+            .handle(
+                r#"[{"jsonrpc": "2.0", "id": 1, "method": "state",
+                     "params": {"game": 0}},
+                    {"jsonrpc": "2.0", "method": "create"}]"#,
+            )
+            .unwrap();
+        let response = serde_json::from_str::<Value>(&response).unwrap();
+        assert_eq!(response.as_array().unwrap().len(), 1);
+        assert_eq!(response[0]["result"]["over"], false);
+    }
+}
@@ -0,0 +1,427 @@
+use std::fmt;
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::{game::Checkpoint, profile::Profile, speedrun::Run, BuildError};
+
+// A type saved in a versioned envelope. Whenever the serialized form of a type
+// changes, bump its `VERSION` and teach `migrate` how to upgrade data from the
+// previous version, so that older saves keep loading.
+pub trait Schema: Serialize + DeserializeOwned {
+    const KIND: &'static str;
+    const VERSION: u32;
+
+    // Upgrades `data` from `version` to `version + 1`.
+    fn migrate(version: u32, data: Value) -> Result<Value, SchemaError> {
+        let _ = data;
+        Err(SchemaError::NoMigration(version))
+    }
+}
+
+#[derive(Debug)]
+pub enum SchemaError {
+    Json(serde_json::Error),
+    WrongKind {
+        expected: String,
+        found:    String,
+    },
+    // The data was saved by a newer version of the crate.
+    TooNew(u32),
+    NoMigration(u32),
+    // The data describes a game that can't be built.
+    Build(BuildError),
+    // The guess at this index doesn't fit the game, or its recorded feedback
+    // is wrong.
+    InvalidHistory(usize),
+    #[cfg(feature = "msgpack")]
+    MsgPackEncode(rmp_serde::encode::Error),
+    #[cfg(feature = "msgpack")]
+    MsgPackDecode(rmp_serde::decode::Error),
+    #[cfg(feature = "ron")]
+    Ron(String),
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Json(e) => write!(f, "{}", e),
+            Self::WrongKind { expected, found } => {
+                write!(f, "expected a saved {}, found a {}", expected, found)
+            }
+            Self::TooNew(version) => {
+                write!(
+                    f,
+                    "version {} is newer than this build supports",
+                    version
+                )
+            }
+            Self::NoMigration(version) => {
+                write!(f, "don't know how to upgrade from version {}", version)
+            }
+            Self::Build(e) => write!(f, "{}", e),
+            Self::InvalidHistory(index) => {
+                write!(f, "guess {} doesn't match the game", index + 1)
+            }
+            #[cfg(feature = "msgpack")]
+            Self::MsgPackEncode(e) => write!(f, "{}", e),
+            #[cfg(feature = "msgpack")]
+            Self::MsgPackDecode(e) => write!(f, "{}", e),
+            #[cfg(feature = "ron")]
+            Self::Ron(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for SchemaError {}
+
+impl From<serde_json::Error> for SchemaError {
+    fn from(e: serde_json::Error) -> Self { Self::Json(e) }
+}
+
+#[cfg(feature = "msgpack")]
+impl From<rmp_serde::encode::Error> for SchemaError {
+    fn from(e: rmp_serde::encode::Error) -> Self { Self::MsgPackEncode(e) }
+}
+
+#[cfg(feature = "msgpack")]
+impl From<rmp_serde::decode::Error> for SchemaError {
+    fn from(e: rmp_serde::decode::Error) -> Self { Self::MsgPackDecode(e) }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Envelope {
+    kind:    String,
+    version: u32,
+    data:    Value,
+}
+
+pub fn to_string<T: Schema>(value: &T) -> Result<String, SchemaError> {
+    Ok(serde_json::to_string(&to_value(value)?)?)
+}
+
+pub fn to_value<T: Schema>(value: &T) -> Result<Value, SchemaError> {
+    let envelope = Envelope {
+        kind:    T::KIND.to_owned(),
+        version: T::VERSION,
+        data:    serde_json::to_value(value)?,
+    };
+    Ok(serde_json::to_value(envelope)?)
+}
+
+pub fn from_str<T: Schema>(text: &str) -> Result<T, SchemaError> {
+    from_value(serde_json::from_str(text)?)
+}
+
+// Loads a saved value, upgrading it to the current version first.
+pub fn from_value<T: Schema>(value: Value) -> Result<T, SchemaError> {
+    let Envelope {
+        kind,
+        mut version,
+        mut data,
+    } = serde_json::from_value(value)?;
+
+    if kind != T::KIND {
+        return Err(SchemaError::WrongKind {
+            expected: T::KIND.to_owned(),
+            found:    kind,
+        });
+    }
+    if version > T::VERSION {
+        return Err(SchemaError::TooNew(version));
+    }
+
+    while version < T::VERSION {
+        data = T::migrate(version, data)?;
+        version += 1;
+    }
+    Ok(serde_json::from_value(data)?)
+}
+
+// The same envelope as the JSON form, encoded as MessagePack, for when JSON is
+// too bulky.
+#[cfg(feature = "msgpack")]
+pub fn to_msgpack<T: Schema>(value: &T) -> Result<Vec<u8>, SchemaError> {
+    Ok(rmp_serde::to_vec_named(&to_value(value)?)?)
+}
+
+#[cfg(feature = "msgpack")]
+pub fn from_msgpack<T: Schema>(bytes: &[u8]) -> Result<T, SchemaError> {
+    from_value(rmp_serde::from_slice(bytes)?)
+}
+
+// A human-editable form for fixtures and puzzle packs. Comments are allowed
+// when reading.
+#[cfg(feature = "ron")]
+pub fn to_ron<T: Schema>(value: &T) -> Result<String, SchemaError> {
+    #[derive(Serialize)]
+    struct Typed<'a, T> {
+        kind:    &'static str,
+        version: u32,
+        data:    &'a T,
+    }
+
+    let typed = Typed {
+        kind:    T::KIND,
+        version: T::VERSION,
+        data:    value,
+    };
+    ron::ser::to_string_pretty(&typed, ron::ser::PrettyConfig::default())
+        .map_err(|e| SchemaError::Ron(e.to_string()))
+}
+
+#[cfg(feature = "ron")]
+pub fn from_ron<T: Schema>(text: &str) -> Result<T, SchemaError> {
+    from_value(
+        ron::from_str(text).map_err(|e| SchemaError::Ron(e.to_string()))?,
+    )
+}
+
+impl Schema for Checkpoint {
+    const KIND: &'static str = "checkpoint";
+    const VERSION: u32 = 2;
+
+    fn migrate(version: u32, mut data: Value) -> Result<Value, SchemaError> {
+        match (version, &mut data) {
+            // Version 2 added hints, forfeits and the seed of seeded games,
+            // which store it in place of their secret.
+            (1, Value::Object(fields)) => {
+                fields.entry("hints").or_insert(json!([]));
+                fields.entry("seed").or_insert(Value::Null);
+                fields.entry("forfeited").or_insert(json!(false));
+                Ok(data)
+            }
+            _ => Err(SchemaError::NoMigration(version)),
+        }
+    }
+}
+
+impl Schema for Profile {
+    const KIND: &'static str = "profile";
+    const VERSION: u32 = 1;
+}
+
+impl Schema for Run {
+    const KIND: &'static str = "run";
+    const VERSION: u32 = 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{achievements::Streak, game::Hint};
+
+    // Saved data from every released version must keep loading. When a
+    // version is bumped, add a new fixture rather than editing these.
+
+    #[test]
+    fn loads_checkpoint_v1() {
+        let saved = r#"{"kind":"checkpoint","version":1,"data":{
+            "pegs":[1,2,3,4],"revealed":[2],"guesses":[[0,0,0,0],[1,2,0,0]]
+        }}"#;
+        let checkpoint = from_str::<Checkpoint>(saved).unwrap();
+        assert_eq!(checkpoint.pegs, [1, 2, 3, 4]);
+        assert_eq!(checkpoint.revealed, [2]);
+        assert_eq!(checkpoint.guesses.len(), 2);
+        assert!(checkpoint.hints.is_empty());
+        assert_eq!(checkpoint.seed, None);
+    }
+
+    #[test]
+    fn loads_checkpoint_v2() {
+        let saved = r#"{"kind":"checkpoint","version":2,"data":{
+            "pegs":[],"revealed":[],"guesses":[[0,0,0,0]],
+            "hints":[{"Absent":5},{"Position":{"position":1,"peg":2}}],
+            "seed":42,"forfeited":true
+        }}"#;
+        let checkpoint = from_str::<Checkpoint>(saved).unwrap();
+        assert!(checkpoint.pegs.is_empty());
+        assert!(checkpoint.revealed.is_empty());
+        assert_eq!(checkpoint.guesses, [[0, 0, 0, 0]]);
+        assert_eq!(
+            checkpoint.hints,
+            [
+                Hint::Absent(5),
+                Hint::Position {
+                    position: 1,
+                    peg:      2,
+                }
+            ]
+        );
+        assert_eq!(checkpoint.seed, Some(42));
+        assert!(checkpoint.forfeited);
+    }
+
+    #[test]
+    fn loads_profile_v1() {
+        let saved = r#"{"kind":"profile","version":1,"data":{
+            "name":"ann",
+            "settings":{"variant":"number"},
+            "stats":{"played":3,"won":2,"current":1,"best":2},
+            "achievements":["win-in-3"],
+            "rating":1016.0
+        }}"#;
+        let profile = from_str::<Profile>(saved).unwrap();
+        assert_eq!(profile.name, "ann");
+        assert_eq!(profile.settings.variant, "number");
+        assert_eq!(
+            profile.stats,
+            Streak {
+                played:  3,
+                won:     2,
+                current: 1,
+                best:    2,
+            }
+        );
+        assert_eq!(profile.achievements, ["win-in-3"]);
+        assert_eq!(profile.rating, 1016.0);
+    }
+
+    #[test]
+    fn loads_run_v1() {
+        let saved = r#"{"kind":"run","version":1,"data":{
+            "splits":[{
+                "guess":[1,2,3,4],"hits":4,"near_hits":0,
+                "split":{"secs":3,"nanos":500000000},
+                "elapsed":{"secs":3,"nanos":500000000}
+            }],
+            "solved":true
+        }}"#;
+        let run = from_str::<Run>(saved).unwrap();
+        assert!(run.solved);
+        assert_eq!(run.total(), std::time::Duration::from_millis(3500));
+    }
+
+    #[test]
+    fn values_round_trip() {
+        let profile = Profile::new("bob");
+        let saved = to_string(&profile).unwrap();
+        assert_eq!(from_str::<Profile>(&saved).unwrap(), profile);
+    }
+
+    #[test]
+    fn rejects_other_kinds_and_future_versions() {
+        let saved = to_string(&Profile::new("bob")).unwrap();
+        assert!(matches!(
+            from_str::<Checkpoint>(&saved),
+            Err(SchemaError::WrongKind { .. })
+        ));
+
+        let future = json!({"kind": "profile", "version": 99, "data": {}});
+        assert!(matches!(
+            from_value::<Profile>(future),
+            Err(SchemaError::TooNew(99))
+        ));
+    }
+
+    #[cfg(feature = "msgpack")]
+    #[test]
+    fn msgpack_matches_json() {
+        let mut profile = Profile::new("ann");
+        profile.achievements.push("win-in-3".to_owned());
+        profile.rating = 1016.5;
+
+        let checkpoint = Checkpoint {
+            pegs:      vec![1, 2, 3, 4],
+            revealed:  vec![0],
+            guesses:   vec![vec![4, 3, 2, 1]; 10],
+            hints:     Vec::new(),
+            seed:      None,
+            forfeited: false,
+        };
+
+        let bytes = to_msgpack(&profile).unwrap();
+        let decoded = from_msgpack::<Profile>(&bytes).unwrap();
+        assert_eq!(to_value(&decoded).unwrap(), to_value(&profile).unwrap());
+
+        let bytes = to_msgpack(&checkpoint).unwrap();
+        assert_eq!(from_msgpack::<Checkpoint>(&bytes).unwrap(), checkpoint);
+        assert!(bytes.len() < to_string(&checkpoint).unwrap().len());
+
+        // Versioning works the same way as for JSON.
+        assert!(matches!(
+            from_msgpack::<Checkpoint>(&to_msgpack(&profile).unwrap()),
+            Err(SchemaError::WrongKind { .. })
+        ));
+    }
+
+    #[cfg(feature = "ron")]
+    #[test]
+    fn ron_round_trips() {
+        let profile = Profile::new("ann");
+        let text = to_ron(&profile).unwrap();
+        assert_eq!(from_ron::<Profile>(&text).unwrap(), profile);
+    }
+
+    #[cfg(feature = "ron")]
+    #[test]
+    fn loads_hand_written_ron() {
+        let saved = r#"
+            // A checkpoint written by hand.
+            (
+                kind: "checkpoint",
+                version: 1,
+                data: (
+                    pegs: [1, 2, 3, 4],
+                    revealed: [],
+                    guesses: [
+                        [0, 0, 1, 1], // one close
+                    ],
+                ),
+            )
+        "#;
+        let checkpoint = from_ron::<Checkpoint>(saved).unwrap();
+        assert_eq!(checkpoint.pegs, [1, 2, 3, 4]);
+        assert_eq!(checkpoint.guesses, [[0, 0, 1, 1]]);
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Renamed {
+        pegs:  Vec<u8>,
+        label: String,
+    }
+
+    // v1 called `pegs` `code`, and v2 added `label`.
+    impl Schema for Renamed {
+        const KIND: &'static str = "renamed";
+        const VERSION: u32 = 3;
+
+        fn migrate(
+            version: u32,
+            mut data: Value,
+        ) -> Result<Value, SchemaError> {
+            match version {
+                1 => {
+                    let code = data["code"].take();
+                    Ok(json!({ "pegs": code }))
+                }
+                2 => {
+                    data["label"] = json!("untitled");
+                    Ok(data)
+                }
+                _ => Err(SchemaError::NoMigration(version)),
+            }
+        }
+    }
+
+    #[test]
+    fn migrations_are_applied_in_order() {
+        let v1 =
+            json!({"kind": "renamed", "version": 1, "data": {"code": [1, 2]}});
+        assert_eq!(
+            from_value::<Renamed>(v1).unwrap(),
+            Renamed {
+                pegs:  vec![1, 2],
+                label: "untitled".to_owned(),
+            }
+        );
+
+        let v0 = json!({"kind": "renamed", "version": 0, "data": {}});
+        assert!(matches!(
+            from_value::<Renamed>(v0),
+            Err(SchemaError::NoMigration(0))
+        ));
+    }
+}
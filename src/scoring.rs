@@ -0,0 +1,154 @@
+use std::time::Duration;
+
+use crate::{game::HintKind, Game, Handicap};
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Round {
+    pub guesses_used: u8,
+    pub solved:       bool,
+    pub duration:     Duration,
+    pub hints_used:   u8,
+    // The kind of each hint used, for scoring that prices hints differently.
+    pub hints:        Vec<HintKind>,
+    pub handicap:     Handicap,
+}
+
+impl Round {
+    pub fn from_game(game: &Game, duration: Duration) -> Self {
+        Self {
+            guesses_used: game.guesses().len() as u8,
+            solved: game.is_solved(),
+            duration,
+            hints_used: game.hints().len() as u8,
+            hints: game.hints().iter().map(|hint| hint.kind()).collect(),
+            handicap: game.handicap(),
+        }
+    }
+}
+
+pub type ScoringFn = Box<dyn Fn(&Round) -> i64>;
+
+// Standard Mastermind scoring: the codemaker earns a point for every guess,
+// plus a bonus point if the code was never broken.
+pub fn classic_scoring(round: &Round) -> i64 {
+    round.guesses_used as i64 + if round.solved { 0 } else { 1 }
+}
+
+pub struct Match {
+    rounds:  Vec<Round>,
+    scoring: ScoringFn,
+}
+
+impl Match {
+    pub fn new() -> Self { Self::with_scoring(classic_scoring) }
+
+    pub fn with_scoring(scoring: impl Fn(&Round) -> i64 + 'static) -> Self {
+        Self {
+            rounds:  Vec::new(),
+            scoring: Box::new(scoring),
+        }
+    }
+
+    pub fn rounds(&self) -> &[Round] { &self.rounds }
+    pub fn record(&mut self, round: Round) { self.rounds.push(round); }
+
+    pub fn round_scores(&self) -> Vec<i64> {
+        self.rounds
+            .iter()
+            .map(|round| (self.scoring)(round))
+            .collect()
+    }
+
+    pub fn total(&self) -> i64 { self.round_scores().iter().sum() }
+}
+
+impl Default for Match {
+    fn default() -> Self { Self::new() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GameBuilder;
+
+    #[test]
+    fn classic_scoring_counts_guesses() {
+        let mut scores = Match::new();
+        scores.record(Round {
+            guesses_used: 4,
+            solved: true,
+            ..Round::default()
+        });
+        scores.record(Round {
+            guesses_used: 12,
+            solved: false,
+            ..Round::default()
+        });
+        assert_eq!(scores.round_scores(), vec![4, 13]);
+        assert_eq!(scores.total(), 17);
+    }
+
+    #[test]
+    fn custom_scoring_is_used() {
+        let mut scores = Match::with_scoring(|round| {
+            let mut points = 100 - 10 * round.guesses_used as i64;
+            points -= 5 * round.hints_used as i64;
+            points -= round.duration.as_secs() as i64;
+            points
+        });
+        scores.record(Round {
+            guesses_used: 3,
+            solved:       true,
+            duration:     Duration::from_secs(20),
+            hints_used:   2,
+            hints:        Vec::new(),
+            handicap:     Handicap::default(),
+        });
+        assert_eq!(scores.total(), 40);
+    }
+
+    #[test]
+    fn scoring_can_price_hints() {
+        let mut scores = Match::with_scoring(|round| {
+            let hints = round.hints.iter().map(|kind| kind.cost() as i64);
+            round.guesses_used as i64 + hints.sum::<i64>()
+        });
+        let mut game = GameBuilder::new().pegs(&[1, 2, 3, 4]).build();
+        game.hint(HintKind::EliminateColor).unwrap();
+        game.hint(HintKind::RevealPosition).unwrap();
+        game.guess(&[1, 2, 3, 4]).unwrap();
+
+        let round = Round::from_game(&game, Duration::ZERO);
+        assert_eq!(round.hints_used, 2);
+        scores.record(round);
+        assert_eq!(scores.total(), 1 + 1 + 3);
+    }
+
+    #[test]
+    fn scoring_can_account_for_handicaps() {
+        let mut scores = Match::with_scoring(|round| {
+            round.guesses_used as i64 - round.handicap.bonus_guesses as i64
+        });
+        scores.record(Round {
+            guesses_used: 5,
+            handicap: Handicap {
+                bonus_guesses: 2,
+                revealed_pegs: 0,
+            },
+            ..Round::default()
+        });
+        assert_eq!(scores.total(), 3);
+    }
+
+    #[test]
+    fn round_from_game() {
+        let mut game = GameBuilder::new().pegs(&[1, 2, 3, 4]).build();
+        game.guess(&[0, 0, 0, 0]).unwrap();
+        game.guess(&[1, 2, 3, 4]).unwrap();
+
+        let round = Round::from_game(&game, Duration::from_secs(5));
+        assert_eq!(round.guesses_used, 2);
+        assert!(round.solved);
+        assert_eq!(round.duration, Duration::from_secs(5));
+    }
+}
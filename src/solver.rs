@@ -0,0 +1,846 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use rand::{seq::SliceRandom, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::{
+    knowledge::{codes, is_consistent, is_consistent_since, Knowledge, Seen},
+    table::FeedbackTable,
+    Game, GameBuilder, GuessError, Rules,
+};
+
+// How a solver picks its next guess, usually among the codes still consistent
+// with the board.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Strategy {
+    // Knuth's minimax: whichever code, consistent or not, leaves the fewest
+    // candidates in the worst case, preferring candidates on ties.
+    #[default]
+    Knuth,
+    // Whichever code is expected to tell the most about the secret.
+    Entropy,
+    // Any consistent code at all.
+    Random,
+    // Whichever code changes the fewest pegs of the last guess, the way people
+    // tend to tweak a guess rather than start over.
+    Human,
+}
+
+impl Strategy {
+    pub const ALL: [Self; 4] =
+        [Self::Knuth, Self::Entropy, Self::Random, Self::Human];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|strategy| strategy.name().eq_ignore_ascii_case(name))
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Knuth => "knuth",
+            Self::Entropy => "entropy",
+            Self::Random => "random",
+            Self::Human => "human",
+        }
+    }
+}
+
+// Solver settings under names players can choose between.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Personality {
+    // Goes for the most information, at the risk of an unlucky long game.
+    Aggressive,
+    // Keeps the worst case as short as possible.
+    Safe,
+    // Only makes guesses a person might: small changes to the last one.
+    HumanLike,
+}
+
+impl Personality {
+    pub const ALL: [Self; 3] = [Self::Aggressive, Self::Safe, Self::HumanLike];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|personality| personality.name().eq_ignore_ascii_case(name))
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Aggressive => "aggressive",
+            Self::Safe => "safe",
+            Self::HumanLike => "human-like",
+        }
+    }
+
+    pub fn strategy(&self) -> Strategy {
+        match self {
+            Self::Aggressive => Strategy::Entropy,
+            Self::Safe => Strategy::Knuth,
+            Self::HumanLike => Strategy::Human,
+        }
+    }
+}
+
+// How well a computer codebreaker plays against a human codemaker. Lower
+// levels now and then waste a guess on a random code.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Level {
+    Beginner,
+    #[default]
+    Casual,
+    Expert,
+}
+
+impl Level {
+    pub const ALL: [Self; 3] = [Self::Beginner, Self::Casual, Self::Expert];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|level| level.name().eq_ignore_ascii_case(name))
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Beginner => "beginner",
+            Self::Casual => "casual",
+            Self::Expert => "expert",
+        }
+    }
+
+    // The chance of each guess being a blunder.
+    pub fn blunder_rate(&self) -> f64 {
+        match self {
+            Self::Beginner => 0.4,
+            Self::Casual => 0.15,
+            Self::Expert => 0.0,
+        }
+    }
+}
+
+// Knuth's search scores every code against every candidate; beyond this many
+// scores it's limited to the candidates.
+const MAX_FULL_SEARCH: usize = 1 << 20;
+
+// A solver for any game. It keeps every code still consistent with the board,
+// and picks its guesses according to its strategy (by default Knuth's
+// minimax).
+pub struct Solver {
+    candidates:   Vec<Vec<u8>>,
+    strategy:     Strategy,
+    blunder_rate: f64,
+    rng:          ChaCha8Rng,
+    evaluations:  Cell<u64>,
+    stats:        Vec<SearchStats>,
+    buffers:      RefCell<Buffers>,
+    // The board as of the last update, if it's known.
+    seen:         Option<Seen>,
+    table:        Option<Arc<FeedbackTable>>,
+    plan:         Option<Plan>,
+}
+
+// The guesses already chosen for each board, as its guesses and their scores.
+// Solvers of one deterministic strategy playing games of one configuration
+// can share a plan, so each board is only searched once however many games
+// reach it.
+#[derive(Debug, Clone, Default)]
+pub struct Plan(Arc<Mutex<HashMap<Board, Vec<u8>>>>);
+
+type Board = Vec<(Vec<u8>, (u8, u8))>;
+
+impl Plan {
+    fn guess(&self, game: &Game, choose: impl FnOnce() -> Vec<u8>) -> Vec<u8> {
+        let board = game
+            .guesses()
+            .iter()
+            .enumerate()
+            .map(|(i, guess)| (guess.clone(), game.hits(i).unwrap_or_default()))
+            .collect::<Board>();
+        if let Some(guess) = self.0.lock().unwrap().get(&board) {
+            return guess.clone();
+        }
+
+        let guess = choose();
+        self.0.lock().unwrap().insert(board, guess.clone());
+        guess
+    }
+}
+
+// Scratch space for scoring, kept between calls since a search scores every
+// candidate against every possible guess.
+#[derive(Debug, Default)]
+struct Buffers {
+    scores: Vec<((u8, u8), usize)>,
+    sizes:  Vec<usize>,
+}
+
+// The work behind one guess: how long it took to choose, and how many times a
+// candidate was scored against a possible guess.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchStats {
+    pub elapsed:     Duration,
+    pub evaluations: u64,
+}
+
+impl Solver {
+    pub fn new(game: &Game) -> Self {
+        Self {
+            seen: Some(Seen::of(game.view())),
+            ..Self::from_candidates(Knowledge::new(game).candidates().to_vec())
+        }
+    }
+
+    // A solver that only knows the secret is one of `candidates`.
+    pub fn from_candidates(candidates: Vec<Vec<u8>>) -> Self {
+        Self {
+            candidates,
+            strategy: Strategy::default(),
+            blunder_rate: 0.0,
+            rng: ChaCha8Rng::from_entropy(),
+            evaluations: Cell::new(0),
+            stats: Vec::new(),
+            buffers: RefCell::default(),
+            seen: None,
+            table: None,
+            plan: None,
+        }
+    }
+
+    pub fn strategy(mut self, strategy: Strategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    // Makes each guess a random code, consistent or not, with probability
+    // `rate`.
+    pub fn blunder_rate(mut self, rate: f64) -> Self {
+        self.blunder_rate = rate.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn personality(self, personality: Personality) -> Self {
+        self.strategy(personality.strategy())
+    }
+
+    pub fn level(self, level: Level) -> Self {
+        self.blunder_rate(level.blunder_rate())
+    }
+
+    // Looks scores up in `table` instead of working them out, for games with
+    // the rules it was built for. Tables are worth building once and sharing
+    // when solving many games of the same configuration.
+    pub fn table(mut self, table: Arc<FeedbackTable>) -> Self {
+        self.table = Some(table);
+        self
+    }
+
+    // Looks guesses up in `plan`, and adds the ones it has to search for. Only
+    // used when the solver's guesses can't vary, i.e. it makes no random
+    // choices.
+    pub fn plan(mut self, plan: Plan) -> Self {
+        self.plan = Some(plan);
+        self
+    }
+
+    // Seeds the random choices of `Strategy::Random` and of blunders.
+    pub fn seed(self, seed: u64) -> Self {
+        self.rng(ChaCha8Rng::seed_from_u64(seed))
+    }
+
+    // Uses `rng` for random choices, e.g. one of a set of `Streams`.
+    pub fn rng(mut self, rng: ChaCha8Rng) -> Self {
+        self.rng = rng;
+        self
+    }
+
+    pub fn candidates(&self) -> &[Vec<u8>] { &self.candidates }
+    // The search behind each guess from `next_guess`, in order.
+    pub fn stats(&self) -> &[SearchStats] { &self.stats }
+    // Every evaluation made so far, including by `best_guess` and
+    // `most_informative_guess` called directly.
+    pub fn evaluations(&self) -> u64 { self.evaluations.get() }
+
+    pub fn next_guess(&mut self, game: &Game) -> Vec<u8> {
+        let started = Instant::now();
+        let evaluations = self.evaluations();
+        let deterministic =
+            self.blunder_rate == 0.0 && self.strategy != Strategy::Random;
+        let guess = match self.plan.clone().filter(|_| deterministic) {
+            Some(plan) => plan.guess(game, || self.choose(game)),
+            None => self.choose(game),
+        };
+        self.stats.push(SearchStats {
+            elapsed:     started.elapsed(),
+            evaluations: self.evaluations() - evaluations,
+        });
+        guess
+    }
+
+    fn choose(&mut self, game: &Game) -> Vec<u8> {
+        if self.blunder_rate > 0.0 && self.rng.gen_bool(self.blunder_rate) {
+            let alphabet = game.alphabet();
+            let len = *game.peg_counts().start() as usize;
+            return (0..len)
+                .map(|_| alphabet[self.rng.gen_range(0..alphabet.len())])
+                .collect();
+        }
+        if self.strategy == Strategy::Random {
+            return self
+                .candidates
+                .choose(&mut self.rng)
+                .cloned()
+                .unwrap_or_default();
+        }
+
+        // Scoring every candidate against every other is too slow before
+        // anything is known, so the first guess is a fixed opener in the style
+        // of Knuth's `1122`.
+        if game.guesses().is_empty() && game.revealed().is_empty() {
+            let alphabet = game.alphabet();
+            let len = *game.peg_counts().start() as usize;
+            return (0..len)
+                .map(|i| alphabet[i / 2 % alphabet.len()])
+                .collect();
+        }
+        match self.strategy {
+            Strategy::Entropy => self.most_informative_guess(game.rules()),
+            Strategy::Human => self.smallest_change(game),
+            _ => self.knuth_guess(game),
+        }
+        .unwrap_or_default()
+    }
+
+    // Knuth's minimax over every code of the game, so that a guess which
+    // can't be the secret is still made when it splits the candidates best.
+    // Candidates are tried first and so win ties, as they might be the
+    // secret. Scoring every code is skipped, in favor of `best_guess`, when
+    // there would be more than `MAX_FULL_SEARCH` scores to work out.
+    fn knuth_guess(&self, game: &Game) -> Option<Vec<u8>> {
+        if self.candidates.len() <= 2 {
+            return self.candidates.first().cloned();
+        }
+        let colors = game.alphabet().len();
+        let all_codes = game
+            .peg_counts()
+            .map(|len| colors.checked_pow(len as u32).unwrap_or(usize::MAX))
+            .fold(0, usize::saturating_add);
+        if all_codes.saturating_mul(self.candidates.len()) > MAX_FULL_SEARCH {
+            return self.best_guess(game.rules());
+        }
+
+        let candidates = self
+            .candidates
+            .iter()
+            .map(Vec::as_slice)
+            .collect::<HashSet<_>>();
+        let all = codes(game.alphabet(), game.peg_counts());
+        let others = all.iter().filter(|code| !candidates.contains(&code[..]));
+        self.minimax(game.rules(), self.candidates.iter().chain(others))
+    }
+
+    // Calls `f` with how many candidates each possible score of `guess`
+    // would leave, in ascending order so that sums over them don't depend on
+    // the order the scores turned up in.
+    fn partitions<R>(
+        &self,
+        rules: &Rules,
+        guess: &[u8],
+        f: impl FnOnce(&[usize]) -> R,
+    ) -> R {
+        self.evaluations
+            .set(self.evaluations.get() + self.candidates.len() as u64);
+
+        let mut buffers = self.buffers.borrow_mut();
+        let Buffers { scores, sizes } = &mut *buffers;
+        // There are only a handful of distinct scores, so a linear search
+        // beats hashing.
+        scores.clear();
+        let table = self
+            .table
+            .as_deref()
+            .filter(|table| table.rules() == rules)
+            .and_then(|table| Some((table, table.index(guess)?)));
+        for candidate in &self.candidates {
+            let score = table
+                .and_then(|(table, guess)| {
+                    Some(table.score_indices(table.index(candidate)?, guess))
+                })
+                .unwrap_or_else(|| rules.score(candidate, guess));
+            match scores.iter_mut().find(|(seen, _)| *seen == score) {
+                Some((_, size)) => *size += 1,
+                None => scores.push((score, 1)),
+            }
+        }
+        sizes.clear();
+        sizes.extend(scores.iter().map(|&(_, size)| size));
+        sizes.sort_unstable();
+        f(sizes)
+    }
+
+    // The candidate leaving the fewest candidates in the worst case, or `None`
+    // if there are no candidates left.
+    pub fn best_guess(&self, rules: &Rules) -> Option<Vec<u8>> {
+        self.minimax(rules, &self.candidates)
+    }
+
+    fn minimax<'a>(
+        &self,
+        rules: &Rules,
+        guesses: impl IntoIterator<Item = &'a Vec<u8>>,
+    ) -> Option<Vec<u8>> {
+        guesses
+            .into_iter()
+            .min_by_key(|guess| {
+                self.partitions(rules, guess, |sizes| {
+                    sizes.last().copied().unwrap_or(0)
+                })
+            })
+            .cloned()
+    }
+
+    // Of the candidates changing the fewest pegs of the last guess, the one
+    // leaving the fewest candidates in the worst case.
+    fn smallest_change(&self, game: &Game) -> Option<Vec<u8>> {
+        let Some(last) = game.guesses().last()
+        else {
+            return self.best_guess(game.rules());
+        };
+        let changes = |code: &Vec<u8>| {
+            let same = code.iter().zip(last).filter(|(a, b)| a == b).count();
+            code.len().max(last.len()) - same
+        };
+
+        let fewest = self.candidates.iter().map(changes).min()?;
+        let closest = self
+            .candidates
+            .iter()
+            .filter(|code| changes(code) == fewest);
+        self.minimax(game.rules(), closest)
+    }
+
+    // The candidate whose score has the highest entropy, or `None` if there
+    // are no candidates left.
+    pub fn most_informative_guess(&self, rules: &Rules) -> Option<Vec<u8>> {
+        let total = self.candidates.len() as f64;
+        let entropy = |guess: &Vec<u8>| -> f64 {
+            self.partitions(rules, guess, |sizes| {
+                sizes
+                    .iter()
+                    .map(|&size| {
+                        let p = size as f64 / total;
+                        -p * p.log2()
+                    })
+                    .sum()
+            })
+        };
+        self.candidates
+            .iter()
+            .map(|guess| (guess, entropy(guess)))
+            .fold(None, |best: Option<(&Vec<u8>, f64)>, (guess, bits)| {
+                match best {
+                    Some((_, best_bits)) if best_bits >= bits => best,
+                    _ => Some((guess, bits)),
+                }
+            })
+            .map(|(guess, _)| guess.clone())
+    }
+
+    // Narrows the candidates down to those fitting the board, checking only
+    // what was added since the last update when that's possible.
+    pub fn update(&mut self, game: &Game) {
+        let board = game.view();
+        match self.seen.take().filter(|seen| seen.is_before(board)) {
+            Some(seen) => self
+                .candidates
+                .retain(|code| is_consistent_since(board, code, &seen)),
+            None => self.candidates.retain(|code| is_consistent(game, code)),
+        }
+        self.seen = Some(Seen::of(board));
+    }
+
+    // Plays `game` to completion, returning the number of guesses it took.
+    pub fn solve(&mut self, game: &mut Game) -> Result<usize, GuessError> {
+        while !game.is_solved() {
+            let guess = self.next_guess(game);
+            game.guess(&guess)?;
+            self.update(game);
+        }
+        Ok(game.guesses().len())
+    }
+
+    // Plays every secret of a standard game with `peg_count` pegs of `colors`
+    // colors, each with a fresh copy of this solver.
+    pub fn evaluate(
+        &self,
+        peg_count: u8,
+        colors: u8,
+    ) -> Result<SolverProfile, GuessError> {
+        let started = Instant::now();
+        let alphabet = (0..colors).collect::<Vec<_>>();
+        let table = self.table.clone().or_else(|| {
+            FeedbackTable::new(&Rules::default(), &alphabet, peg_count)
+                .map(Arc::new)
+        });
+        let plan = self.plan.clone().unwrap_or_default();
+        let guesses = codes(&alphabet, peg_count..=peg_count)
+            .iter()
+            .map(|secret| {
+                let mut game = GameBuilder::new()
+                    .peg_range(colors)
+                    .peg_count(peg_count)
+                    .pegs(secret)
+                    .unlimited_guesses(true)
+                    .build();
+                Solver {
+                    candidates:   Knowledge::new(&game).candidates().to_vec(),
+                    strategy:     self.strategy,
+                    blunder_rate: self.blunder_rate,
+                    rng:          self.rng.clone(),
+                    evaluations:  Cell::new(0),
+                    stats:        Vec::new(),
+                    buffers:      RefCell::default(),
+                    seen:         Some(Seen::of(game.view())),
+                    table:        table.clone(),
+                    plan:         Some(plan.clone()),
+                }
+                .solve(&mut game)
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(SolverProfile {
+            peg_count,
+            colors,
+            guesses,
+            elapsed: started.elapsed(),
+        })
+    }
+}
+
+// How a solver did over every secret of a configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolverProfile {
+    pub peg_count: u8,
+    pub colors:    u8,
+    // The guesses each secret took, in the order of `knowledge::codes`.
+    pub guesses:   Vec<usize>,
+    pub elapsed:   Duration,
+}
+
+impl SolverProfile {
+    pub fn worst(&self) -> usize {
+        self.guesses.iter().copied().max().unwrap_or(0)
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.guesses.iter().sum::<usize>() as f64
+            / self.guesses.len().max(1) as f64
+    }
+
+    pub fn std_dev(&self) -> f64 {
+        let mean = self.mean();
+        let variance = self
+            .guesses
+            .iter()
+            .map(|&n| (n as f64 - mean).powi(2))
+            .sum::<f64>()
+            / self.guesses.len().max(1) as f64;
+        variance.sqrt()
+    }
+
+    // How many secrets took each number of guesses.
+    pub fn distribution(&self) -> Vec<(usize, usize)> {
+        (1..=self.worst())
+            .map(|n| (n, self.guesses.iter().filter(|&&g| g == n).count()))
+            .filter(|&(_, count)| count > 0)
+            .collect()
+    }
+
+    // Each secret with the guesses it took. Secrets are rebuilt as they're
+    // iterated rather than stored.
+    pub fn results(&self) -> impl Iterator<Item = (Vec<u8>, usize)> + '_ {
+        self.guesses.iter().enumerate().map(|(index, &guesses)| {
+            let mut code = vec![0; self.peg_count as usize];
+            let mut rest = index;
+            for peg in code.iter_mut().rev() {
+                *peg = (rest % self.colors as usize) as u8;
+                rest /= self.colors as usize;
+            }
+            (code, guesses)
+        })
+    }
+}
+
+// A solver for games using positional feedback. Every position is solved
+// independently: colors are tried in order until the mask confirms one.
+pub struct PositionalSolver {
+    // The colors each position could still be.
+    possible: Vec<Vec<u8>>,
+}
+
+impl PositionalSolver {
+    pub fn new(game: &Game) -> Self {
+        let peg_count = *game.peg_counts().start() as usize;
+        Self {
+            possible: vec![game.alphabet().to_vec(); peg_count],
+        }
+    }
+
+    pub fn next_guess(&self) -> Vec<u8> {
+        self.possible
+            .iter()
+            .map(|colors| colors.first().copied().unwrap_or_default())
+            .collect()
+    }
+
+    pub fn update(&mut self, guess: &[u8], mask: &[bool]) {
+        for ((colors, peg), correct) in
+            self.possible.iter_mut().zip(guess).zip(mask)
+        {
+            if *correct {
+                colors.retain(|color| color == peg);
+            }
+            else {
+                colors.retain(|color| color != peg);
+            }
+        }
+    }
+
+    // Plays `game` to completion, returning the number of guesses it took.
+    pub fn solve(&mut self, game: &mut Game) -> Result<usize, GuessError> {
+        while !game.is_solved() {
+            let guess = self.next_guess();
+            game.guess(&guess)?;
+
+            let mask = game.mask(game.guesses().len() - 1).unwrap();
+            self.update(&guess, &mask);
+        }
+        Ok(game.guesses().len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FeedbackMode, GameBuilder};
+
+    #[test]
+    fn tables_give_the_same_guesses() {
+        let mut game = GameBuilder::new().peg_count(3).peg_range(4).build();
+        game.guess(&[0, 0, 1]).unwrap();
+        let table = FeedbackTable::new(game.rules(), game.alphabet(), 3);
+        let table = Arc::new(table.unwrap());
+
+        let plain = Solver::new(&game);
+        let tabled = Solver::new(&game).table(table.clone());
+        let rules = game.rules();
+        assert_eq!(plain.best_guess(rules), tabled.best_guess(rules));
+        assert_eq!(
+            plain.most_informative_guess(rules),
+            tabled.most_informative_guess(rules)
+        );
+
+        let profile = |solver: Solver| solver.evaluate(3, 4).unwrap().guesses;
+        assert_eq!(
+            profile(Solver::from_candidates(Vec::new())),
+            profile(Solver::from_candidates(Vec::new()).table(table))
+        );
+    }
+
+    #[test]
+    fn searches_are_measured() {
+        let mut game = GameBuilder::new().pegs(&[1, 2, 3, 4]).build();
+        let mut solver = Solver::new(&game);
+        solver.solve(&mut game).unwrap();
+
+        let stats = solver.stats();
+        assert_eq!(stats.len(), game.guesses().len());
+        // The opener is fixed, so nothing is evaluated for it.
+        assert_eq!(stats[0].evaluations, 0);
+        assert!(stats[1].evaluations > 0);
+        assert_eq!(
+            stats.iter().map(|s| s.evaluations).sum::<u64>(),
+            solver.evaluations()
+        );
+    }
+
+    #[test]
+    fn profiles_cover_every_secret() {
+        let profile =
+            Solver::from_candidates(Vec::new()).evaluate(3, 4).unwrap();
+        assert_eq!(profile.guesses.len(), 64);
+        assert_eq!(
+            profile.distribution().iter().map(|(_, n)| n).sum::<usize>(),
+            64
+        );
+        assert!(profile.mean() <= profile.worst() as f64);
+        assert!(profile.std_dev() > 0.0);
+
+        let results = profile.results().collect::<Vec<_>>();
+        assert_eq!(results[0], (vec![0, 0, 0], profile.guesses[0]));
+        assert_eq!(results[6].0, [0, 1, 2]);
+        assert_eq!(results[63].0, [3, 3, 3]);
+
+        // The opener is solved in one.
+        let opener = results.iter().find(|(code, _)| code == &[0, 0, 1]);
+        assert_eq!(opener.unwrap().1, 1);
+    }
+
+    #[test]
+    fn solver_solves_classic_games() {
+        for _ in 0..10 {
+            let mut game = GameBuilder::new().unlimited_guesses(true).build();
+            let guesses = Solver::new(&game).solve(&mut game);
+            assert!(guesses.unwrap() <= 6);
+            assert!(game.is_solved());
+        }
+    }
+
+    #[test]
+    fn knuth_guesses_codes_that_cannot_be_the_secret() {
+        let mut game = GameBuilder::new()
+            .peg_count(3)
+            .peg_range(4)
+            .pegs(&[0, 0, 0])
+            .build();
+        game.guess(&[0, 0, 1]).unwrap();
+        let solver = Solver::new(&game);
+
+        let guess = solver.knuth_guess(&game).unwrap();
+        let worst = |guess: &[u8]| {
+            solver
+                .partitions(game.rules(), guess, |sizes| sizes[sizes.len() - 1])
+        };
+        let consistent = solver.best_guess(game.rules()).unwrap();
+        assert!(!solver.candidates().contains(&guess));
+        assert!(worst(&guess) < worst(&consistent));
+    }
+
+    #[test]
+    fn plans_are_shared_between_solvers() {
+        let plan = Plan::default();
+        let play = || {
+            let mut game = GameBuilder::new().pegs(&[1, 2, 3, 4]).build();
+            let mut solver = Solver::new(&game).plan(plan.clone());
+            solver.solve(&mut game).unwrap();
+            (game.guesses().to_vec(), solver.evaluations())
+        };
+
+        let (first, searched) = play();
+        let (second, evaluations) = play();
+        assert_eq!(first, second);
+        assert!(searched > 0);
+        assert_eq!(evaluations, 0);
+    }
+
+    #[test]
+    fn solver_opens_with_pairs() {
+        let game = GameBuilder::new().peg_count(5).build();
+        assert_eq!(Solver::new(&game).next_guess(&game), vec![0, 0, 1, 1, 2]);
+    }
+
+    #[test]
+    fn every_strategy_solves_classic_games() {
+        for strategy in Strategy::ALL {
+            for seed in 0..5 {
+                let mut game = GameBuilder::new()
+                    .seed(seed)
+                    .unlimited_guesses(true)
+                    .build();
+                let mut solver =
+                    Solver::new(&game).strategy(strategy).seed(seed);
+                assert!(solver.solve(&mut game).is_ok());
+                assert!(game.is_solved());
+            }
+        }
+    }
+
+    #[test]
+    fn blunders_ignore_the_board() {
+        let mut inconsistent = 0;
+        for seed in 0..10 {
+            let mut game = GameBuilder::new().seed(seed).build();
+            let mut solver = Solver::new(&game).blunder_rate(1.0).seed(seed);
+            for _ in 0..3 {
+                let guess = solver.next_guess(&game);
+                inconsistent += !is_consistent(&game, &guess) as usize;
+                game.guess(&guess).unwrap();
+                solver.update(&game);
+            }
+        }
+        assert!(inconsistent > 0);
+
+        let mut game = GameBuilder::new().unlimited_guesses(true).build();
+        let mut solver = Solver::new(&game).level(Level::Expert);
+        assert!(solver.solve(&mut game).unwrap() <= 6);
+        assert_eq!(Level::from_name("BEGINNER"), Some(Level::Beginner));
+    }
+
+    #[test]
+    fn human_like_solvers_tweak_their_last_guess() {
+        let mut game = GameBuilder::new().pegs(&[1, 2, 3, 4]).build();
+        game.guess(&[1, 2, 3, 5]).unwrap();
+
+        // Only one peg of the last guess needs to change.
+        let mut solver = Solver::new(&game).personality(Personality::HumanLike);
+        let guess = solver.next_guess(&game);
+        let changed = guess.iter().zip([1, 2, 3, 5]).filter(|(a, b)| **a != *b);
+        assert_eq!(changed.count(), 1);
+
+        assert_eq!(
+            Personality::from_name("Human-Like").map(|p| p.strategy()),
+            Some(Strategy::Human)
+        );
+    }
+
+    #[test]
+    fn entropy_prefers_informative_guesses() {
+        // 10 splits these into groups of 3, 1, 1, 1 and 1, which says more on
+        // average than the 2, 1, 2, 2 split of 02, but is worse when the
+        // group of 3 comes up.
+        let solver = Solver::from_candidates(
+            [[0, 1], [0, 2], [1, 0], [1, 1], [2, 1], [2, 2], [3, 1]]
+                .map(Vec::from)
+                .to_vec(),
+        );
+        let rules = Rules::default();
+        assert_eq!(solver.most_informative_guess(&rules), Some(vec![1, 0]));
+        assert_eq!(solver.best_guess(&rules), Some(vec![0, 2]));
+        assert_eq!(Strategy::from_name("Entropy"), Some(Strategy::Entropy));
+    }
+
+    #[test]
+    fn positional_solver_solves_within_range() {
+        for _ in 0..50 {
+            let mut game = GameBuilder::new()
+                .feedback_mode(FeedbackMode::Positional)
+                .peg_range(6)
+                .build();
+
+            // Each position needs at most one guess per color, and the last
+            // remaining color is never wrong.
+            let guesses = PositionalSolver::new(&game).solve(&mut game);
+            assert!(guesses.unwrap() <= 6);
+            assert!(game.is_solved());
+        }
+    }
+
+    #[test]
+    fn positional_solver_narrows_positions() {
+        let game = GameBuilder::new()
+            .feedback_mode(FeedbackMode::Positional)
+            .peg_range(3)
+            .build();
+        let mut solver = PositionalSolver::new(&game);
+        assert_eq!(solver.next_guess(), vec![0, 0, 0, 0]);
+
+        solver.update(&[0, 0, 0, 0], &[false, true, false, false]);
+        assert_eq!(solver.next_guess(), vec![1, 0, 1, 1]);
+    }
+}
@@ -0,0 +1,153 @@
+use std::{
+    fmt::Write,
+    time::{Duration, Instant},
+};
+
+use crate::{Game, GuessError};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Split {
+    pub guess:     Vec<u8>,
+    pub hits:      u8,
+    pub near_hits: u8,
+    // Time spent on this guess alone.
+    pub split:     Duration,
+    // Time since the start of the run.
+    pub elapsed:   Duration,
+}
+
+// A finished or in-progress run, in a form that can be exported and compared
+// between frontends.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Run {
+    pub splits: Vec<Split>,
+    pub solved: bool,
+}
+
+impl Run {
+    pub fn total(&self) -> Duration {
+        self.splits
+            .last()
+            .map_or(Duration::ZERO, |split| split.elapsed)
+    }
+
+    // One row per guess, with times in milliseconds:
+    //
+    //     guess,pegs,hits,near_hits,split_ms,elapsed_ms
+    //     1,0 0 1 1,1,0,5230,5230
+    pub fn to_csv(&self) -> String {
+        let mut csv =
+            "guess,pegs,hits,near_hits,split_ms,elapsed_ms\n".to_owned();
+        for (i, split) in self.splits.iter().enumerate() {
+            let pegs = split.guess.iter().map(|peg| peg.to_string());
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{},{}",
+                i + 1,
+                pegs.collect::<Vec<_>>().join(" "),
+                split.hits,
+                split.near_hits,
+                split.split.as_millis(),
+                split.elapsed.as_millis()
+            );
+        }
+        csv
+    }
+}
+
+// Times a game guess by guess. Frontends make guesses through the timer, or
+// call `split` after making one themselves.
+#[derive(Debug, Clone)]
+pub struct Timer {
+    started: Instant,
+    run:     Run,
+}
+
+impl Timer {
+    pub fn start() -> Self { Self::start_at(Instant::now()) }
+
+    pub fn start_at(started: Instant) -> Self {
+        Self {
+            started,
+            run: Run::default(),
+        }
+    }
+
+    pub fn run(&self) -> &Run { &self.run }
+    pub fn into_run(self) -> Run { self.run }
+
+    pub fn guess(
+        &mut self,
+        game: &mut Game,
+        guess: &[u8],
+    ) -> Result<(u8, u8), GuessError> {
+        let feedback = game.guess(guess)?;
+        self.split(game);
+        Ok(feedback)
+    }
+
+    // Records the game's latest guess as a split ending now.
+    pub fn split(&mut self, game: &Game) { self.split_at(game, Instant::now()) }
+
+    pub fn split_at(&mut self, game: &Game, at: Instant) {
+        let index = game.guesses().len();
+        if index == 0 || index <= self.run.splits.len() {
+            return;
+        }
+
+        let elapsed = at.saturating_duration_since(self.started);
+        let (hits, near_hits) = game.hits(index - 1).unwrap();
+        self.run.splits.push(Split {
+            guess: game.guesses()[index - 1].clone(),
+            hits,
+            near_hits,
+            split: elapsed.saturating_sub(self.run.total()),
+            elapsed,
+        });
+        self.run.solved = game.is_solved();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GameBuilder;
+
+    #[test]
+    fn records_a_split_per_guess() {
+        let start = Instant::now();
+        let mut game = GameBuilder::new().pegs(&[1, 2, 3, 4]).build();
+        let mut timer = Timer::start_at(start);
+
+        game.guess(&[0, 0, 1, 1]).unwrap();
+        timer.split_at(&game, start + Duration::from_millis(5230));
+        // Splitting twice for the same guess does nothing.
+        timer.split_at(&game, start + Duration::from_secs(6));
+        game.guess(&[1, 2, 3, 4]).unwrap();
+        timer.split_at(&game, start + Duration::from_millis(8000));
+
+        let run = timer.into_run();
+        assert!(run.solved);
+        assert_eq!(run.splits.len(), 2);
+        assert_eq!(run.splits[1].split, Duration::from_millis(2770));
+        assert_eq!(run.total(), Duration::from_secs(8));
+        assert_eq!(
+            run.to_csv(),
+            "guess,pegs,hits,near_hits,split_ms,elapsed_ms\n\
+             1,0 0 1 1,0,1,5230,5230\n\
+             2,1 2 3 4,4,0,2770,8000\n"
+        );
+    }
+
+    #[test]
+    fn guesses_through_the_timer() {
+        let mut game = GameBuilder::new().pegs(&[1, 2, 3, 4]).build();
+        let mut timer = Timer::start();
+        assert_eq!(timer.guess(&mut game, &[1, 2, 3, 3]), Ok((3, 0)));
+        assert!(timer.guess(&mut game, &[1]).is_err());
+        assert_eq!(timer.run().splits.len(), 1);
+        assert!(!timer.run().solved);
+    }
+}
@@ -0,0 +1,118 @@
+use crate::{knowledge::codes, Rules};
+
+// Tables for more codes than this aren't built: a table takes a byte per pair
+// of codes, so this caps it at 4 MiB.
+pub const MAX_CODES: usize = 2048;
+
+// Every code of a fixed-length game scored against every other, worked out
+// once so solvers can look scores up instead of recomputing them in their
+// inner loop.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeedbackTable {
+    rules:     Rules,
+    alphabet:  Vec<u8>,
+    peg_count: usize,
+    // Each peg's position in the alphabet, or `None` if it isn't in it.
+    digits:    Vec<Option<usize>>,
+    // Hits in the high nibble and near hits in the low one, indexed by
+    // `secret * codes + guess`.
+    scores:    Vec<u8>,
+}
+
+impl FeedbackTable {
+    // None if the game has more than `MAX_CODES` codes, or codes too long for
+    // scores to fit in a nibble.
+    pub fn new(rules: &Rules, alphabet: &[u8], peg_count: u8) -> Option<Self> {
+        let count = alphabet.len().checked_pow(peg_count as u32)?;
+        if count > MAX_CODES || peg_count > 15 {
+            return None;
+        }
+
+        let all = codes(alphabet, peg_count..=peg_count);
+        let mut scores = Vec::with_capacity(count * count);
+        for secret in &all {
+            for guess in &all {
+                let (hits, near_hits) = rules.score(secret, guess);
+                scores.push(hits.min(15) << 4 | near_hits.min(15));
+            }
+        }
+
+        let mut digits = vec![None; 256];
+        for (i, &peg) in alphabet.iter().enumerate() {
+            digits[peg as usize] = Some(i);
+        }
+        Some(Self {
+            rules: rules.clone(),
+            alphabet: alphabet.to_owned(),
+            peg_count: peg_count as usize,
+            digits,
+            scores,
+        })
+    }
+
+    pub fn codes(&self) -> usize {
+        self.alphabet.len().pow(self.peg_count as u32)
+    }
+
+    // Scores only depend on the pegs in each code, so a table is right for
+    // any game with the same rules, even over part of the alphabet.
+    pub fn rules(&self) -> &Rules { &self.rules }
+
+    // A code's place in the order of `knowledge::codes`, if the table has it.
+    pub fn index(&self, code: &[u8]) -> Option<usize> {
+        if code.len() != self.peg_count {
+            return None;
+        }
+        code.iter().try_fold(0, |index, &peg| {
+            Some(index * self.alphabet.len() + self.digits[peg as usize]?)
+        })
+    }
+
+    pub fn score_indices(&self, secret: usize, guess: usize) -> (u8, u8) {
+        let score = self.scores[secret * self.codes() + guess];
+        (score >> 4, score & 15)
+    }
+
+    pub fn score(&self, secret: &[u8], guess: &[u8]) -> Option<(u8, u8)> {
+        Some(self.score_indices(self.index(secret)?, self.index(guess)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{game::EMPTY, Joker};
+
+    #[test]
+    fn tables_match_scoring() {
+        let rules = Rules {
+            joker: Some(Joker::InGuess(EMPTY)),
+            ..Rules::default()
+        };
+        let alphabet = [0, 1, 2, EMPTY];
+        let table = FeedbackTable::new(&rules, &alphabet, 3).unwrap();
+        assert_eq!(table.codes(), 64);
+        assert_eq!(table.rules(), &rules);
+
+        let all = codes(&alphabet, 3..=3);
+        for (i, secret) in all.iter().enumerate() {
+            assert_eq!(table.index(secret), Some(i));
+            for guess in &all {
+                assert_eq!(
+                    table.score(secret, guess),
+                    Some(rules.score(secret, guess))
+                );
+            }
+        }
+        assert_eq!(table.index(&[0, 1]), None);
+        assert_eq!(table.index(&[0, 1, 5]), None);
+    }
+
+    #[test]
+    fn large_games_get_no_table() {
+        let rules = Rules::default();
+        assert!(FeedbackTable::new(&rules, &[0, 1, 2, 3, 4, 5], 4).is_some());
+        assert!(FeedbackTable::new(&rules, &[0, 1, 2, 3, 4, 5], 5).is_none());
+        assert!(FeedbackTable::new(&rules, &[0; 200], 200).is_none());
+    }
+}
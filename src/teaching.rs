@@ -0,0 +1,182 @@
+use std::cmp::Ordering;
+
+use crate::{game::FeedbackMode, knowledge::Knowledge, Game};
+
+// Why a guess ruled a candidate out: had the candidate been the secret, the
+// guess would have been answered differently.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Reason {
+    // The candidate is longer (`Greater`) or shorter than the secret.
+    Length(Ordering),
+    Score { hits: u8, near_hits: u8 },
+    ArithmeticHints,
+    Mask,
+}
+
+// Candidates ruled out for the same reason.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Group {
+    pub reason: Reason,
+    pub codes:  Vec<Vec<u8>>,
+}
+
+// What one guess taught the codebreaker: which candidates it eliminated,
+// grouped by why, and which survived.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lesson {
+    pub index:      usize,
+    pub guess:      Vec<u8>,
+    pub hits:       u8,
+    pub near_hits:  u8,
+    // Largest group first.
+    pub eliminated: Vec<Group>,
+    pub remaining:  Vec<Vec<u8>>,
+}
+
+impl Lesson {
+    pub fn eliminated_count(&self) -> usize {
+        self.eliminated.iter().map(|group| group.codes.len()).sum()
+    }
+}
+
+// Explains the guess at `index`, starting from the candidates left by the
+// guesses before it.
+pub fn lesson(game: &Game, index: usize) -> Option<Lesson> {
+    let guess = game.guesses().get(index)?;
+    let (hits, near_hits) = game.hits(index)?;
+    let rules = game.rules();
+    let before = Knowledge::new(&game.truncated(index));
+
+    let reason = |code: &[u8]| {
+        let length = code.len().cmp(&guess.len());
+        let score = rules.score(code, guess);
+
+        if Some(length) != game.length_hint(index) {
+            Some(Reason::Length(code.len().cmp(&game.pegs().len())))
+        }
+        else if score != (hits, near_hits) {
+            Some(Reason::Score {
+                hits:      score.0,
+                near_hits: score.1,
+            })
+        }
+        else if Some(rules.arithmetic_hints(code, guess))
+            != game.arithmetic_hints(index)
+        {
+            Some(Reason::ArithmeticHints)
+        }
+        else if rules.feedback_mode == FeedbackMode::Positional
+            && Some(rules.mask(code, guess)) != game.mask(index)
+        {
+            Some(Reason::Mask)
+        }
+        else {
+            None
+        }
+    };
+
+    let mut eliminated: Vec<Group> = Vec::new();
+    let mut remaining = Vec::new();
+    for code in before.candidates() {
+        match reason(code) {
+            None => remaining.push(code.clone()),
+            Some(reason) => {
+                match eliminated.iter_mut().find(|g| g.reason == reason) {
+                    Some(group) => group.codes.push(code.clone()),
+                    None => eliminated.push(Group {
+                        reason,
+                        codes: vec![code.clone()],
+                    }),
+                }
+            }
+        }
+    }
+    eliminated.sort_by(|a, b| {
+        b.codes
+            .len()
+            .cmp(&a.codes.len())
+            .then(a.reason.cmp(&b.reason))
+    });
+
+    Some(Lesson {
+        index,
+        guess: guess.clone(),
+        hits,
+        near_hits,
+        eliminated,
+        remaining,
+    })
+}
+
+// A lesson for every guess made so far.
+pub fn lessons(game: &Game) -> Vec<Lesson> {
+    (0..game.guesses().len())
+        .filter_map(|index| lesson(game, index))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GameBuilder;
+
+    #[test]
+    fn eliminations_are_grouped_by_score() {
+        let mut game = GameBuilder::new().pegs(&[1, 1, 2, 2]).build();
+        game.guess(&[1, 1, 1, 1]).unwrap();
+
+        let lesson = lesson(&game, 0).unwrap();
+        assert_eq!((lesson.hits, lesson.near_hits), (2, 0));
+        assert_eq!(lesson.remaining.len(), 150);
+        assert_eq!(lesson.eliminated_count(), 1296 - 150);
+
+        let groups = lesson
+            .eliminated
+            .iter()
+            .map(|group| (group.reason, group.codes.len()))
+            .collect::<Vec<_>>();
+        let score = |hits| Reason::Score { hits, near_hits: 0 };
+        assert_eq!(
+            groups,
+            [
+                (score(0), 625),
+                (score(1), 500),
+                (score(3), 20),
+                (score(4), 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn later_lessons_start_from_earlier_ones() {
+        let mut game = GameBuilder::new().pegs(&[1, 1, 2, 2]).build();
+        game.guess(&[1, 1, 1, 1]).unwrap();
+        game.guess(&[2, 2, 2, 2]).unwrap();
+
+        let lessons = lessons(&game);
+        assert_eq!(lessons.len(), 2);
+        assert_eq!(
+            lessons[1].eliminated_count() + lessons[1].remaining.len(),
+            lessons[0].remaining.len()
+        );
+        assert_eq!(lessons[1].remaining.len(), 6);
+        assert!(lesson(&game, 2).is_none());
+    }
+
+    #[test]
+    fn length_eliminations_are_explained() {
+        let mut game = GameBuilder::new()
+            .peg_count_range(2, 3)
+            .pegs(&[1, 2])
+            .build();
+        game.guess(&[0, 0, 0]).unwrap();
+
+        let lesson = lesson(&game, 0).unwrap();
+        assert!(lesson.eliminated.iter().any(|group| {
+            group.reason == Reason::Length(Ordering::Greater)
+                && group.codes.iter().all(|code| code.len() == 3)
+        }));
+        assert!(!lesson.remaining.is_empty());
+        assert!(lesson.remaining.iter().all(|code| code.len() == 2));
+    }
+}
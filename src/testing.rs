@@ -0,0 +1,115 @@
+// Proptest strategies for property-testing code built on this crate. Every
+// value they produce is valid: configurations build, codes fit their
+// configuration, and histories only contain guesses the game would accept.
+
+use proptest::{collection::vec, option, prelude::*};
+
+use crate::{Game, GameBuilder};
+
+// A fixed-length game with standard rules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub peg_count:   u8,
+    pub colors:      u8,
+    // None for unlimited guesses.
+    pub max_guesses: Option<u8>,
+}
+
+impl Config {
+    pub fn builder(&self) -> GameBuilder {
+        let builder = GameBuilder::new()
+            .peg_count(self.peg_count)
+            .peg_range(self.colors);
+        match self.max_guesses {
+            Some(max) => builder.max_guesses(max),
+            None => builder.unlimited_guesses(true),
+        }
+    }
+}
+
+// A secret and the guesses made against it, in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct History {
+    pub config:  Config,
+    pub secret:  Vec<u8>,
+    pub guesses: Vec<Vec<u8>>,
+}
+
+impl History {
+    // The game this history describes.
+    pub fn game(&self) -> Game {
+        let mut game = self.config.builder().pegs(&self.secret).build();
+        for guess in &self.guesses {
+            game.guess(guess).unwrap();
+        }
+        game
+    }
+}
+
+// Configurations of 1-6 pegs and 2-10 colors.
+pub fn configs() -> impl Strategy<Value = Config> {
+    (1..=6u8, 2..=10u8, option::of(1..=20u8)).prop_map(
+        |(peg_count, colors, max_guesses)| Config {
+            peg_count,
+            colors,
+            max_guesses,
+        },
+    )
+}
+
+// Codes that could be the secret, or a guess, in a game of `config`.
+pub fn codes(config: &Config) -> impl Strategy<Value = Vec<u8>> {
+    vec(0..config.colors, config.peg_count as usize)
+}
+
+// Histories of any length up to the guess limit (or 20 guesses for unlimited
+// games), ending early if the secret is guessed.
+pub fn histories() -> impl Strategy<Value = History> {
+    configs()
+        .prop_flat_map(|config| {
+            let max = config.max_guesses.unwrap_or(20) as usize;
+            (codes(&config), vec(codes(&config), 0..=max), Just(config))
+        })
+        .prop_map(|(secret, mut guesses, config)| {
+            if let Some(solved) = guesses.iter().position(|g| *g == secret) {
+                guesses.truncate(solved + 1);
+            }
+            History {
+                config,
+                secret,
+                guesses,
+            }
+        })
+}
+
+// Histories of games still being played: unsolved, with guesses left.
+pub fn boards() -> impl Strategy<Value = History> {
+    histories().prop_map(|mut history| {
+        let secret = history.secret.clone();
+        history.guesses.retain(|guess| *guess != secret);
+        if let Some(max) = history.config.max_guesses {
+            history.guesses.truncate(max as usize - 1);
+        }
+        history
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn histories_replay(history in histories()) {
+            let game = history.game();
+            prop_assert_eq!(game.guesses(), &history.guesses[..]);
+            let solved = history.guesses.last() == Some(&history.secret);
+            prop_assert_eq!(game.is_solved(), solved);
+        }
+
+        #[test]
+        fn boards_are_in_progress(board in boards()) {
+            prop_assert!(!board.game().is_over());
+        }
+    }
+}
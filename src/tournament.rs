@@ -0,0 +1,239 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use rand::seq::SliceRandom;
+
+use crate::{
+    knowledge::codes,
+    solver::{Plan, Solver, Strategy},
+    streams::Streams,
+    table::FeedbackTable,
+    GameBuilder, GuessError, Rules,
+};
+
+// How one strategy did over every game of a tournament.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Standing {
+    pub strategy: Strategy,
+    // The number of guesses each game took, in the order they were played.
+    pub guesses:  Vec<usize>,
+    pub elapsed:  Duration,
+}
+
+impl Standing {
+    pub fn games(&self) -> usize { self.guesses.len() }
+
+    pub fn average(&self) -> f64 {
+        self.guesses.iter().sum::<usize>() as f64 / self.games().max(1) as f64
+    }
+
+    pub fn worst(&self) -> usize {
+        self.guesses.iter().copied().max().unwrap_or(0)
+    }
+
+    pub fn solved_within(&self, guesses: usize) -> usize {
+        self.guesses.iter().filter(|&&n| n <= guesses).count()
+    }
+}
+
+// Plays several solver strategies against the same secrets. By default every
+// possible secret is played once; `games` plays a seeded sample instead, so
+// runs on larger configurations stay reproducible. Random choices come from
+// `Streams` of the seed: stream 0 picks the sample, and stream `i + 1` is
+// used by the solver playing secret `i`.
+#[derive(Debug, Clone)]
+pub struct Tournament {
+    pub peg_count:  u8,
+    pub colors:     u8,
+    pub strategies: Vec<Strategy>,
+    pub games:      Option<usize>,
+    pub seed:       u64,
+    // Whether to spread the games over rayon's thread pool.
+    #[cfg(feature = "rayon")]
+    pub parallel:   bool,
+}
+
+impl Default for Tournament {
+    fn default() -> Self {
+        Self {
+            peg_count: 4,
+            colors: 6,
+            strategies: Strategy::ALL.to_vec(),
+            games: None,
+            seed: 0,
+            #[cfg(feature = "rayon")]
+            parallel: false,
+        }
+    }
+}
+
+impl Tournament {
+    pub fn new() -> Self { Self::default() }
+
+    pub fn config(mut self, peg_count: u8, colors: u8) -> Self {
+        self.peg_count = peg_count;
+        self.colors = colors;
+        self
+    }
+
+    pub fn strategies(mut self, strategies: &[Strategy]) -> Self {
+        self.strategies = strategies.to_vec();
+        self
+    }
+
+    pub fn games(mut self, games: usize) -> Self {
+        self.games = Some(games);
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    // Plays each strategy's games across threads. Every game still gets the
+    // same seed and standings list games in the same order, so the results
+    // are the same as playing them one at a time.
+    #[cfg(feature = "rayon")]
+    pub fn parallel(mut self, parallel: bool) -> Self {
+        self.parallel = parallel;
+        self
+    }
+
+    pub fn secrets(&self) -> Vec<Vec<u8>> {
+        let alphabet = (0..self.colors).collect::<Vec<_>>();
+        let all = codes(&alphabet, self.peg_count..=self.peg_count);
+        match self.games {
+            Some(games) => {
+                let mut rng = Streams::new(self.seed).stream(0);
+                (0..games)
+                    .filter_map(|_| all.choose(&mut rng).cloned())
+                    .collect()
+            }
+            None => all,
+        }
+    }
+
+    pub fn run(&self) -> Result<Vec<Standing>, GuessError> {
+        let secrets = self.secrets();
+        let alphabet = (0..self.colors).collect::<Vec<_>>();
+        let table =
+            FeedbackTable::new(&Rules::default(), &alphabet, self.peg_count)
+                .map(Arc::new);
+
+        // Each game's solver gets the stream for its secret's place in the
+        // list, so it plays the same whichever thread it ends up on.
+        let streams = Streams::new(self.seed);
+        let play =
+            |strategy: Strategy, plan: &Plan, i: usize, secret: &Vec<u8>| {
+                let mut game = GameBuilder::new()
+                    .peg_range(self.colors)
+                    .peg_count(self.peg_count)
+                    .pegs(secret)
+                    .unlimited_guesses(true)
+                    .build();
+                let mut solver = Solver::new(&game)
+                    .strategy(strategy)
+                    .rng(streams.stream(i as u64 + 1))
+                    .plan(plan.clone());
+                if let Some(table) = &table {
+                    solver = solver.table(table.clone());
+                }
+                solver.solve(&mut game)
+            };
+
+        self.strategies
+            .iter()
+            .map(|&strategy| {
+                let started = Instant::now();
+                let plan = Plan::default();
+                let games = secrets.iter().enumerate();
+                #[cfg(feature = "rayon")]
+                let guesses = if self.parallel {
+                    use rayon::prelude::*;
+                    secrets
+                        .par_iter()
+                        .enumerate()
+                        .map(|(i, secret)| play(strategy, &plan, i, secret))
+                        .collect::<Result<_, _>>()?
+                }
+                else {
+                    games
+                        .map(|(i, secret)| play(strategy, &plan, i, secret))
+                        .collect::<Result<_, _>>()?
+                };
+                #[cfg(not(feature = "rayon"))]
+                let guesses = games
+                    .map(|(i, secret)| play(strategy, &plan, i, secret))
+                    .collect::<Result<_, _>>()?;
+
+                Ok(Standing {
+                    strategy,
+                    guesses,
+                    elapsed: started.elapsed(),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn knuth_solves_small_games_quickly() {
+        let standings = Tournament::new()
+            .config(3, 4)
+            .strategies(&[Strategy::Knuth, Strategy::Random])
+            .run()
+            .unwrap();
+
+        assert_eq!(standings.len(), 2);
+        assert!(standings.iter().all(|standing| standing.games() == 64));
+        assert!(standings[0].average() <= standings[1].average());
+        assert_eq!(standings[0].solved_within(standings[0].worst()), 64);
+    }
+
+    #[test]
+    fn samples_are_reproducible() {
+        let tournament = Tournament::new().games(20).seed(3);
+        assert_eq!(tournament.secrets().len(), 20);
+        assert_eq!(tournament.secrets(), tournament.clone().secrets());
+        assert_ne!(tournament.secrets(), tournament.seed(4).secrets());
+
+        let tournament = Tournament::new()
+            .config(3, 3)
+            .strategies(&[Strategy::Random])
+            .games(10);
+        let guesses = |t: &Tournament| t.run().unwrap()[0].guesses.clone();
+        assert_eq!(guesses(&tournament), guesses(&tournament));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_runs_match_sequential_ones() {
+        let tournament = Tournament::new()
+            .config(3, 4)
+            .strategies(&[Strategy::Knuth, Strategy::Random])
+            .seed(7);
+        let guesses = |t: Tournament| {
+            t.run()
+                .unwrap()
+                .into_iter()
+                .map(|standing| standing.guesses)
+                .collect::<Vec<_>>()
+        };
+        let sequential = guesses(tournament.clone());
+        for threads in [1, 2, 5] {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+            let parallel = tournament.clone().parallel(true);
+            assert_eq!(pool.install(|| guesses(parallel)), sequential);
+        }
+    }
+}
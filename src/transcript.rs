@@ -0,0 +1,396 @@
+use std::fmt;
+
+use crate::{
+    knowledge::codes, render::Description, BuildError, Game, GameBuilder,
+    Rules, EMPTY,
+};
+
+// One move from a transcript.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub guess:     Vec<u8>,
+    pub hits:      u8,
+    pub near_hits: u8,
+}
+
+// A game history read from the informal formats people post online, such as
+//
+//     1. RGBY -> 2 black 1 white
+//     guess: 1122 | 1B 2W
+//     Guess 3: red, green, blue, yellow — 2 exact, 1 misplaced.
+//     0 0 1 1  2 1
+//
+// Pegs are color names, their initials, or digits taken as peg values as-is.
+// Lines that can't be read are skipped rather than failing the whole import.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Transcript {
+    pub entries: Vec<Entry>,
+    // The line numbers (from 1) of non-blank lines that couldn't be read.
+    pub skipped: Vec<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportError {
+    // The transcript never reaches a solving guess, so its secret is unknown.
+    Unsolved,
+    Build(BuildError),
+    // The entry at this index doesn't fit the game, or its feedback is wrong.
+    InvalidHistory(usize),
+}
+
+const LABELS: [&str; 5] = ["guess", "turn", "move", "try", "attempt"];
+const HIT_WORDS: [&str; 11] = [
+    "b", "black", "blacks", "exact", "hit", "hits", "correct", "right", "bull",
+    "bulls", "red",
+];
+const NEAR_WORDS: [&str; 10] = [
+    "w",
+    "white",
+    "whites",
+    "close",
+    "near",
+    "misplaced",
+    "partial",
+    "cow",
+    "cows",
+    "wrong",
+];
+const FILLER: [&str; 6] = ["and", "peg", "pegs", "place", "spot", "position"];
+
+impl Transcript {
+    pub fn parse(text: &str) -> Self {
+        Self::parse_with(text, &Description::default())
+    }
+
+    // Reads color names (and their initials) from `names`.
+    pub fn parse_with(text: &str, names: &Description) -> Self {
+        let mut transcript = Self::default();
+        for (i, line) in text.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match parse_line(line, names) {
+                Some(entry) => transcript.entries.push(entry),
+                None => transcript.skipped.push(i + 1),
+            }
+        }
+        transcript
+    }
+
+    // The secret, if the transcript ends with it being guessed.
+    pub fn secret(&self) -> Option<&[u8]> {
+        self.entries
+            .iter()
+            .find(|entry| {
+                entry.hits as usize == entry.guess.len() && entry.near_hits == 0
+            })
+            .map(|entry| entry.guess.as_slice())
+    }
+
+    // Every secret made of pegs `0..colors` that would have given the recorded
+    // feedback, assuming standard rules. Secrets are as long as the first
+    // guess.
+    pub fn candidates(&self, colors: u8) -> Vec<Vec<u8>> {
+        let Some(first) = self.entries.first()
+        else {
+            return Vec::new();
+        };
+        let rules = Rules::default();
+        let Ok(len) = u8::try_from(first.guess.len())
+        else {
+            return Vec::new();
+        };
+
+        codes(&(0..colors).collect::<Vec<_>>(), len..=len)
+            .into_iter()
+            .filter(|code| {
+                self.entries.iter().all(|entry| {
+                    rules.score(code, &entry.guess)
+                        == (entry.hits, entry.near_hits)
+                })
+            })
+            .collect()
+    }
+
+    // Replays a solved transcript as a game configured by `builder`, checking
+    // every recorded score against the secret.
+    pub fn to_game(&self, builder: GameBuilder) -> Result<Game, ImportError> {
+        let secret = self.secret().ok_or(ImportError::Unsolved)?;
+        let mut game = builder
+            .pegs(secret)
+            .peg_count(secret.len() as u8)
+            .unlimited_guesses(true)
+            .try_build()
+            .map_err(ImportError::Build)?;
+
+        for (i, entry) in self.entries.iter().enumerate() {
+            match game.guess(&entry.guess) {
+                Ok(score) if score == (entry.hits, entry.near_hits) => (),
+                _ => return Err(ImportError::InvalidHistory(i)),
+            }
+        }
+        Ok(game)
+    }
+}
+
+fn parse_line(line: &str, names: &Description) -> Option<Entry> {
+    let mut line = line.to_lowercase();
+    for arrow in ["->", "=>", "→", "—", "–", " - "] {
+        line = line.replace(arrow, "|");
+    }
+    let line = strip_label(&line.replace(|c: char| ",;:=([]".contains(c), " "))
+        .replace(')', " ");
+
+    let (guess, feedback) = match line.split_once('|') {
+        Some((guess, feedback)) => (guess, feedback.replace('|', " ")),
+        None => (line.as_str(), String::new()),
+    };
+    let guess = words(guess);
+    let feedback = words(&feedback);
+
+    // Without a separator, the guess runs up to the longest tail of the line
+    // that reads as feedback.
+    let split = match feedback.is_empty() {
+        true => (1..guess.len()).find(|&i| {
+            parse_feedback(&guess[i..]).is_some()
+                && parse_guess(&guess[..i], names).is_some()
+        })?,
+        false => guess.len(),
+    };
+    let feedback = [&guess[split..], &feedback[..]].concat();
+
+    let (hits, near_hits) = parse_feedback(&feedback)?;
+    Some(Entry {
+        guess: parse_guess(&guess[..split], names)?,
+        hits,
+        near_hits,
+    })
+}
+
+fn words(text: &str) -> Vec<&str> {
+    text.split_whitespace()
+        .map(|word| word.trim_end_matches('.'))
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+// Drops move labels like `guess 3`, `#3` and `3.` from the start of a line.
+fn strip_label(line: &str) -> &str {
+    let line = line.trim_start();
+    let (first, rest) = line.split_once(' ').unwrap_or((line, ""));
+    let numbered = |word: &str| {
+        is_number(word.trim_start_matches('#').trim_end_matches(['.', ')']))
+    };
+
+    if LABELS.contains(&first) {
+        let rest = rest.trim_start();
+        match rest.split_once(' ') {
+            // Longer numbers are more likely a guess written in digits.
+            Some((number, rest)) if numbered(number) && number.len() <= 3 => {
+                rest
+            }
+            _ => rest,
+        }
+    }
+    else if numbered(first)
+        && (first.starts_with('#') || first.ends_with(['.', ')']))
+    {
+        rest
+    }
+    else {
+        line
+    }
+}
+
+fn is_number(word: &str) -> bool {
+    !word.is_empty() && word.chars().all(|c| c.is_ascii_digit())
+}
+
+pub(crate) fn parse_guess(
+    words: &[&str],
+    names: &Description,
+) -> Option<Vec<u8>> {
+    let initial =
+        |c: char| names.names.iter().position(|name| name.starts_with(c));
+
+    let mut pegs = Vec::new();
+    for word in words {
+        if let Some(peg) = names.names.iter().position(|name| name == word) {
+            pegs.push(peg as u8);
+        }
+        else if *word == "empty" {
+            pegs.push(EMPTY);
+        }
+        else {
+            for c in word.chars() {
+                let peg = match c {
+                    '-' | '_' => EMPTY,
+                    _ => c
+                        .to_digit(10)
+                        .or_else(|| initial(c).map(|peg| peg as u32))?
+                        as u8,
+                };
+                pegs.push(peg);
+            }
+        }
+    }
+    (!pegs.is_empty()).then_some(pegs)
+}
+
+// Reads feedback such as `2 black 1 white`, `2b1w`, `1 exact` or a bare
+// `2 1` (exact hits first).
+fn parse_feedback(words: &[&str]) -> Option<(u8, u8)> {
+    let mut hits = None;
+    let mut near_hits = None;
+    let mut bare = Vec::new();
+
+    let mut words = words
+        .iter()
+        .filter(|word| !FILLER.contains(word))
+        .flat_map(|word| split_compact(word))
+        .peekable();
+
+    while let Some(word) = words.next() {
+        let count = match word.as_str() {
+            "no" | "none" | "zero" => 0,
+            word => word.parse::<u8>().ok()?,
+        };
+        match words.peek().map(String::as_str) {
+            Some(word) if HIT_WORDS.contains(&word) => {
+                hits = Some(count);
+                words.next();
+            }
+            Some(word) if NEAR_WORDS.contains(&word) => {
+                near_hits = Some(count);
+                words.next();
+            }
+            _ => bare.push(count),
+        }
+    }
+
+    match (hits, near_hits, bare.as_slice()) {
+        (None, None, &[hits, near_hits]) => Some((hits, near_hits)),
+        (None, None, _) => None,
+        (hits, near_hits, &[]) => {
+            Some((hits.unwrap_or(0), near_hits.unwrap_or(0)))
+        }
+        _ => None,
+    }
+}
+
+// Splits compact feedback like `2b1w` into `2 b 1 w`.
+fn split_compact(word: &str) -> Vec<String> {
+    let mut parts: Vec<String> = Vec::new();
+    for c in word.chars() {
+        match parts.last_mut() {
+            Some(part)
+                if part.chars().all(|p| p.is_ascii_digit())
+                    == c.is_ascii_digit() =>
+            {
+                part.push(c)
+            }
+            _ => parts.push(c.to_string()),
+        }
+    }
+    parts
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unsolved => {
+                f.write_str("the transcript never finds the code")
+            }
+            Self::Build(e) => write!(f, "{}", e),
+            Self::InvalidHistory(index) => {
+                write!(f, "guess {} doesn't match the code", index + 1)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(guess: &[u8], hits: u8, near_hits: u8) -> Entry {
+        Entry {
+            guess: guess.to_vec(),
+            hits,
+            near_hits,
+        }
+    }
+
+    #[test]
+    fn reads_common_formats() {
+        let lines = [
+            ("guess: RGBY -> 2 black 1 white", entry(&[0, 1, 2, 3], 2, 1)),
+            ("1. RGBY  2B 1W", entry(&[0, 1, 2, 3], 2, 1)),
+            ("#4 1122 | 1 black, 2 white", entry(&[1, 1, 2, 2], 1, 2)),
+            ("0 0 1 1  2 1", entry(&[0, 0, 1, 1], 2, 1)),
+            ("1122 2b1w", entry(&[1, 1, 2, 2], 2, 1)),
+            ("guess 1122 2 1", entry(&[1, 1, 2, 2], 2, 1)),
+            ("Guess 2: oopp => 3 exact", entry(&[4, 4, 5, 5], 3, 0)),
+            (
+                "Guess 3: red, green, blue, yellow — 2 exact, 1 misplaced.",
+                entry(&[0, 1, 2, 3], 2, 1),
+            ),
+            (
+                "turn 5: 1234 (no hits, 2 close)",
+                entry(&[1, 2, 3, 4], 0, 2),
+            ),
+        ];
+        for (line, expected) in lines {
+            let transcript = Transcript::parse(line);
+            assert_eq!(transcript.entries, [expected], "{}", line);
+        }
+    }
+
+    #[test]
+    fn unreadable_lines_are_skipped() {
+        let transcript = Transcript::parse(
+            "Game from last night\n\n1122 -> 1 black\nwhat a game!\n",
+        );
+        assert_eq!(transcript.entries, [entry(&[1, 1, 2, 2], 1, 0)]);
+        assert_eq!(transcript.skipped, [1, 4]);
+    }
+
+    #[test]
+    fn candidates_fit_every_entry() {
+        let transcript = Transcript::parse("1122 -> 1 black\n1344 -> 2 white");
+        let candidates = transcript.candidates(6);
+        assert!(candidates.contains(&vec![3, 1, 5, 3]));
+        assert!(!candidates.contains(&vec![1, 1, 3, 4]));
+        assert!(candidates.iter().all(|code| code.len() == 4));
+
+        assert!(Transcript::default().candidates(6).is_empty());
+    }
+
+    #[test]
+    fn solved_transcripts_become_games() {
+        let transcript = Transcript::parse(
+            "1. 0011 -> 0 black 1 white\n\
+             2. 1223 -> 2 black 1 white\n\
+             3. 1234 -> 4 black",
+        );
+        assert_eq!(transcript.secret(), Some(&[1, 2, 3, 4][..]));
+
+        let game = transcript.to_game(GameBuilder::new()).unwrap();
+        assert!(game.is_solved());
+        assert_eq!(game.guesses().len(), 3);
+
+        let wrong = Transcript::parse("0011 -> 2 black\n1234 -> 4 black");
+        assert_eq!(
+            wrong.to_game(GameBuilder::new()).err(),
+            Some(ImportError::InvalidHistory(0))
+        );
+        assert_eq!(
+            Transcript::parse("1234 2 0")
+                .to_game(GameBuilder::new())
+                .err(),
+            Some(ImportError::Unsolved)
+        );
+    }
+}
@@ -0,0 +1,74 @@
+// Counts the allocations made by the solvers' inner loop on the 5x8 game,
+// which should score guesses without allocating once its buffers have grown.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+use mastermind::{solver::Solver, GameBuilder};
+
+struct Counting;
+
+thread_local! {
+    // Per thread, so tests running in parallel don't count each other.
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+fn allocations<R>(f: impl FnOnce() -> R) -> (R, usize) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let result = f();
+    (result, ALLOCATIONS.with(Cell::get) - before)
+}
+
+#[test]
+fn scoring_does_not_allocate() {
+    let rules = GameBuilder::new().build().rules().clone();
+    let codes = [[0, 1, 2, 3, 4], [7, 7, 1, 0, 2], [4, 3, 2, 1, 0]];
+    let (_, count) = allocations(|| {
+        for secret in &codes {
+            for guess in &codes {
+                rules.score(secret, guess);
+            }
+        }
+    });
+    assert_eq!(count, 0);
+}
+
+#[test]
+fn searches_allocate_only_their_result() {
+    let mut game = GameBuilder::new()
+        .pegs(&[1, 3, 5, 7, 0])
+        .peg_count(5)
+        .peg_range(8)
+        .build();
+    game.guess(&[0, 1, 2, 3, 4]).unwrap();
+    game.guess(&[5, 6, 7, 0, 1]).unwrap();
+    let mut solver = Solver::new(&game);
+    solver.update(&game);
+    let rules = game.rules();
+
+    // The first search grows the buffers; later ones reuse them.
+    solver.best_guess(rules);
+    let (guess, count) = allocations(|| solver.best_guess(rules));
+    assert!(guess.is_some());
+    assert!(count <= 1, "best_guess allocated {} times", count);
+
+    let (guess, count) = allocations(|| solver.most_informative_guess(rules));
+    assert!(guess.is_some());
+    assert!(count <= 1, "most_informative_guess allocated {} times", count);
+}